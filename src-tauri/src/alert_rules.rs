@@ -0,0 +1,359 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use tauri::State;
+use uuid::Uuid;
+
+const MAX_RULE_NAME_LEN: usize = 100;
+
+/// What a rule watches for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertCondition {
+    PodFailure { reasons: Vec<String> }, // e.g. CrashLoopBackOff, OOMKilled
+    NodeNotReady,
+    DeploymentUnavailable { for_seconds: i64 },
+    WarningEvent { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub cluster_ids: Vec<String>, // Empty applies to every monitored cluster
+    pub namespace: Option<String>, // None applies to all namespaces
+    pub condition: AlertCondition,
+    pub created_at: i64,
+}
+
+impl AlertRule {
+    /// Whether the rule covers an object in `cluster_id`/`namespace`. Cluster-scoped
+    /// objects (no namespace) are only filtered by cluster.
+    pub fn applies_to(&self, cluster_id: &str, namespace: Option<&str>) -> bool {
+        let cluster_matches =
+            self.cluster_ids.is_empty() || self.cluster_ids.iter().any(|id| id == cluster_id);
+        let namespace_matches = match (self.namespace.as_deref(), namespace) {
+            (Some(wanted), Some(ns)) => wanted == ns,
+            _ => true,
+        };
+        self.enabled && cluster_matches && namespace_matches
+    }
+}
+
+/// A rule as submitted from the UI; without an id a new rule is created
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlertRuleInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub cluster_ids: Vec<String>,
+    pub namespace: Option<String>,
+    pub condition: AlertCondition,
+}
+
+fn validate_rule(mut input: AlertRuleInput) -> Result<AlertRuleInput, String> {
+    input.name = input.name.trim().to_string();
+    if input.name.is_empty() {
+        return Err("Rule name cannot be empty".to_string());
+    }
+    if input.name.len() > MAX_RULE_NAME_LEN {
+        return Err(format!(
+            "Rule name must be {} characters or fewer",
+            MAX_RULE_NAME_LEN
+        ));
+    }
+    input.namespace = input
+        .namespace
+        .map(|ns| ns.trim().to_string())
+        .filter(|ns| !ns.is_empty());
+
+    match &mut input.condition {
+        AlertCondition::PodFailure { reasons } => {
+            reasons.retain(|r| !r.trim().is_empty());
+            if reasons.is_empty() {
+                return Err("Pod failure rules need at least one reason".to_string());
+            }
+        }
+        AlertCondition::DeploymentUnavailable { for_seconds } if *for_seconds < 0 => {
+            return Err("Unavailable duration cannot be negative".to_string());
+        }
+        AlertCondition::WarningEvent { reason } if reason.trim().is_empty() => {
+            return Err("Warning event rules need a reason".to_string());
+        }
+        _ => {}
+    }
+    Ok(input)
+}
+
+/// Alert rules persisted in SQLite, with an in-memory copy for the evaluators
+pub struct AlertRuleStore {
+    conn: Mutex<Connection>,
+    rules: RwLock<Vec<AlertRule>>,
+}
+
+impl AlertRuleStore {
+    pub fn new(db_path: PathBuf) -> Result<Self, String> {
+        let conn =
+            Connection::open(&db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS alert_rules (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                enabled INTEGER NOT NULL,
+                cluster_ids TEXT NOT NULL DEFAULT '[]',
+                namespace TEXT,
+                condition TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| format!("Failed to create alert_rules table: {}", e))?;
+
+        let rules = Self::load(&conn)?;
+        Ok(AlertRuleStore {
+            conn: Mutex::new(conn),
+            rules: RwLock::new(rules),
+        })
+    }
+
+    fn load(conn: &Connection) -> Result<Vec<AlertRule>, String> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, enabled, cluster_ids, namespace, condition, created_at
+                 FROM alert_rules ORDER BY created_at",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, bool>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, i64>(6)?,
+                ))
+            })
+            .map_err(|e| format!("Failed to query alert rules: {}", e))?;
+
+        let mut rules = Vec::new();
+        for row in rows {
+            let (id, name, enabled, cluster_ids, namespace, condition, created_at) =
+                row.map_err(|e| format!("Failed to read alert rule: {}", e))?;
+            // A rule written by a newer version may have an unknown condition; skip it
+            let Ok(condition) = serde_json::from_str(&condition) else {
+                eprintln!("Skipping alert rule '{}' with unknown condition", id);
+                continue;
+            };
+            rules.push(AlertRule {
+                id,
+                name,
+                enabled,
+                cluster_ids: serde_json::from_str(&cluster_ids).unwrap_or_default(),
+                namespace,
+                condition,
+                created_at,
+            });
+        }
+        Ok(rules)
+    }
+
+    pub fn list(&self) -> Result<Vec<AlertRule>, String> {
+        Ok(self
+            .rules
+            .read()
+            .map_err(|e| format!("Alert rules lock poisoned: {}", e))?
+            .clone())
+    }
+
+    /// Insert a new rule or replace an existing one
+    pub fn save(&self, input: AlertRuleInput) -> Result<AlertRule, String> {
+        let input = validate_rule(input)?;
+        let mut rules = self
+            .rules
+            .write()
+            .map_err(|e| format!("Alert rules lock poisoned: {}", e))?;
+
+        let existing = input
+            .id
+            .as_ref()
+            .and_then(|id| rules.iter().position(|r| &r.id == id));
+        let rule = AlertRule {
+            id: input.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            name: input.name,
+            enabled: input.enabled,
+            cluster_ids: input.cluster_ids,
+            namespace: input.namespace,
+            condition: input.condition,
+            created_at: existing
+                .map(|i| rules[i].created_at)
+                .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+        };
+
+        let cluster_ids = serde_json::to_string(&rule.cluster_ids)
+            .map_err(|e| format!("Failed to serialize cluster ids: {}", e))?;
+        let condition = serde_json::to_string(&rule.condition)
+            .map_err(|e| format!("Failed to serialize condition: {}", e))?;
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| format!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO alert_rules (id, name, enabled, cluster_ids, namespace, condition, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                &rule.id,
+                &rule.name,
+                rule.enabled,
+                &cluster_ids,
+                &rule.namespace,
+                &condition,
+                rule.created_at,
+            ],
+        )
+        .map_err(|e| format!("Failed to save alert rule: {}", e))?;
+
+        match existing {
+            Some(i) => rules[i] = rule.clone(),
+            None => rules.push(rule.clone()),
+        }
+        Ok(rule)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| format!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM alert_rules WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete alert rule: {}", e))?;
+        self.rules
+            .write()
+            .map_err(|e| format!("Alert rules lock poisoned: {}", e))?
+            .retain(|r| r.id != id);
+        Ok(())
+    }
+}
+
+pub struct AlertRuleState(pub Arc<AlertRuleStore>);
+
+#[tauri::command]
+pub fn db_list_alert_rules(state: State<AlertRuleState>) -> Result<Vec<AlertRule>, String> {
+    state.0.list()
+}
+
+#[tauri::command]
+pub fn db_save_alert_rule(
+    rule: AlertRuleInput,
+    state: State<AlertRuleState>,
+) -> Result<AlertRule, String> {
+    state.0.save(rule)
+}
+
+#[tauri::command]
+pub fn db_delete_alert_rule(id: String, state: State<AlertRuleState>) -> Result<(), String> {
+    state.0.delete(&id)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn input(name: &str, condition: AlertCondition) -> AlertRuleInput {
+        AlertRuleInput {
+            id: None,
+            name: name.to_string(),
+            enabled: true,
+            cluster_ids: vec![],
+            namespace: Some("prod".to_string()),
+            condition,
+        }
+    }
+
+    #[test]
+    fn save_persists_and_updates_rules() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("clusters.db");
+        let store = AlertRuleStore::new(db_path.clone()).unwrap();
+
+        let rule = store
+            .save(input(
+                "Crash loops",
+                AlertCondition::PodFailure {
+                    reasons: vec!["CrashLoopBackOff".to_string()],
+                },
+            ))
+            .unwrap();
+        let mut update = input("Crash loops in prod", rule.condition.clone());
+        update.id = Some(rule.id.clone());
+        store.save(update).unwrap();
+
+        let reopened = AlertRuleStore::new(db_path).unwrap().list().unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(reopened[0].id, rule.id);
+        assert_eq!(reopened[0].name, "Crash loops in prod");
+        assert_eq!(reopened[0].created_at, rule.created_at);
+        assert_eq!(reopened[0].condition, rule.condition);
+    }
+
+    #[test]
+    fn delete_removes_rule() {
+        let temp = TempDir::new().unwrap();
+        let store = AlertRuleStore::new(temp.path().join("clusters.db")).unwrap();
+        let rule = store
+            .save(input("Nodes", AlertCondition::NodeNotReady))
+            .unwrap();
+        store.delete(&rule.id).unwrap();
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn save_rejects_invalid_rules() {
+        let temp = TempDir::new().unwrap();
+        let store = AlertRuleStore::new(temp.path().join("clusters.db")).unwrap();
+        assert!(store
+            .save(input("  ", AlertCondition::NodeNotReady))
+            .is_err());
+        assert!(store
+            .save(input("x", AlertCondition::PodFailure { reasons: vec![] }))
+            .is_err());
+        assert!(store
+            .save(input(
+                "x",
+                AlertCondition::WarningEvent {
+                    reason: " ".to_string()
+                }
+            ))
+            .is_err());
+    }
+
+    #[test]
+    fn applies_to_scopes_by_cluster_and_namespace() {
+        let rule = AlertRule {
+            id: "r".to_string(),
+            name: "r".to_string(),
+            enabled: true,
+            cluster_ids: vec!["prod-eu".to_string()],
+            namespace: Some("payments".to_string()),
+            condition: AlertCondition::NodeNotReady,
+            created_at: 0,
+        };
+        assert!(rule.applies_to("prod-eu", Some("payments")));
+        assert!(rule.applies_to("prod-eu", None));
+        assert!(!rule.applies_to("prod-eu", Some("web")));
+        assert!(!rule.applies_to("staging", Some("payments")));
+
+        let disabled = AlertRule {
+            enabled: false,
+            ..rule
+        };
+        assert!(!disabled.applies_to("prod-eu", Some("payments")));
+    }
+}
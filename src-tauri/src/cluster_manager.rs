@@ -18,6 +18,14 @@ pub struct Cluster {
     pub tags: String, // JSON-encoded array
     pub created_at: i64,
     pub last_accessed: i64,
+    pub credentials_expires_at: Option<i64>,
+}
+
+impl Cluster {
+    /// Decoded `tags`; malformed JSON yields no tags
+    pub fn tag_list(&self) -> Vec<String> {
+        serde_json::from_str(&self.tags).unwrap_or_default()
+    }
 }
 
 pub struct ClusterManager {
@@ -40,12 +48,25 @@ impl ClusterManager {
                 description TEXT,
                 tags TEXT NOT NULL DEFAULT '[]',
                 created_at INTEGER NOT NULL,
-                last_accessed INTEGER NOT NULL
+                last_accessed INTEGER NOT NULL,
+                credentials_expires_at INTEGER
             )",
             [],
         )
         .map_err(|e| format!("Failed to create clusters table: {}", e))?;
 
+        // Databases created before credential expiry tracking lack the column
+        let has_expiry_column = conn
+            .prepare("SELECT credentials_expires_at FROM clusters LIMIT 0")
+            .is_ok();
+        if !has_expiry_column {
+            conn.execute(
+                "ALTER TABLE clusters ADD COLUMN credentials_expires_at INTEGER",
+                [],
+            )
+            .map_err(|e| format!("Failed to migrate clusters table: {}", e))?;
+        }
+
         Ok(ClusterManager {
             conn: Mutex::new(conn),
         })
@@ -107,6 +128,7 @@ impl ClusterManager {
             tags: tags_json,
             created_at: now,
             last_accessed: now,
+            credentials_expires_at: None,
         })
     }
 
@@ -116,7 +138,7 @@ impl ClusterManager {
             .lock()
             .map_err(|e| format!("Database lock poisoned: {}", e))?;
         let mut stmt = conn
-            .prepare("SELECT id, name, context_name, config_path, icon, description, tags, created_at, last_accessed FROM clusters ORDER BY last_accessed DESC")
+            .prepare("SELECT id, name, context_name, config_path, icon, description, tags, created_at, last_accessed, credentials_expires_at FROM clusters ORDER BY last_accessed DESC")
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
         let clusters = stmt
@@ -131,6 +153,7 @@ impl ClusterManager {
                     tags: row.get(6)?,
                     created_at: row.get(7)?,
                     last_accessed: row.get(8)?,
+                    credentials_expires_at: row.get(9)?,
                 })
             })
             .map_err(|e| format!("Failed to query clusters: {}", e))?
@@ -146,7 +169,7 @@ impl ClusterManager {
             .lock()
             .map_err(|e| format!("Database lock poisoned: {}", e))?;
         let mut stmt = conn
-            .prepare("SELECT id, name, context_name, config_path, icon, description, tags, created_at, last_accessed FROM clusters WHERE id = ?1")
+            .prepare("SELECT id, name, context_name, config_path, icon, description, tags, created_at, last_accessed, credentials_expires_at FROM clusters WHERE id = ?1")
             .map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
         let cluster = stmt
@@ -161,6 +184,7 @@ impl ClusterManager {
                     tags: row.get(6)?,
                     created_at: row.get(7)?,
                     last_accessed: row.get(8)?,
+                    credentials_expires_at: row.get(9)?,
                 })
             })
             .optional()
@@ -245,6 +269,20 @@ impl ClusterManager {
         Ok(())
     }
 
+    pub fn set_credentials_expiry(&self, id: &str, expires_at: Option<i64>) -> Result<(), String> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| format!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE clusters SET credentials_expires_at = ?1 WHERE id = ?2",
+            params![expires_at, id],
+        )
+        .map_err(|e| format!("Failed to update credentials_expires_at: {}", e))?;
+
+        Ok(())
+    }
+
     pub fn delete_cluster(&self, id: &str) -> Result<(), String> {
         let conn = self
             .conn
@@ -423,9 +461,12 @@ pub fn db_migrate_legacy_configs(state: State<ClusterManagerState>) -> Result<Ve
             .unwrap_or_else(|_| std::time::Duration::from_secs(0))
             .as_secs() as i64;
 
+        let credentials_expires_at =
+            crate::credentials::credential_expiry_for_kubeconfig(&config_path).unwrap_or(None);
+
         conn.execute(
-            "INSERT INTO clusters (id, name, context_name, config_path, created_at, last_accessed)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO clusters (id, name, context_name, config_path, created_at, last_accessed, credentials_expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             (
                 &id,
                 &validated_name, // Use context name as display name initially
@@ -433,6 +474,7 @@ pub fn db_migrate_legacy_configs(state: State<ClusterManagerState>) -> Result<Ve
                 config_path.to_string_lossy().to_string(),
                 now,
                 now,
+                credentials_expires_at,
             ),
         )
         .map_err(|e| format!("Failed to insert cluster: {}", e))?;
@@ -479,6 +521,41 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn new_migrates_clusters_table_without_expiry_column() {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("clusters.db");
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute(
+                "CREATE TABLE clusters (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    context_name TEXT NOT NULL,
+                    config_path TEXT NOT NULL,
+                    icon TEXT,
+                    description TEXT,
+                    tags TEXT NOT NULL DEFAULT '[]',
+                    created_at INTEGER NOT NULL,
+                    last_accessed INTEGER NOT NULL
+                )",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO clusters (id, name, context_name, config_path, created_at, last_accessed)
+                 VALUES ('old', 'old', 'ctx', '/tmp/old.yaml', 0, 0)",
+                [],
+            )
+            .unwrap();
+        }
+
+        let manager = ClusterManager::new(db_path).unwrap();
+        manager.set_credentials_expiry("old", Some(42)).unwrap();
+        let cluster = manager.get_cluster("old").unwrap().unwrap();
+        assert_eq!(cluster.credentials_expires_at, Some(42));
+    }
+
     #[test]
     fn update_cluster_rejects_invalid_description() {
         let temp = TempDir::new().unwrap();
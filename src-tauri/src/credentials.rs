@@ -0,0 +1,335 @@
+use crate::cluster_manager::{ClusterManager, ClusterManagerState};
+use base64::Engine;
+use kube::config::{AuthInfo, Kubeconfig};
+use secrecy::ExposeSecret;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+use tauri_plugin_notification::NotificationExt;
+
+const DEFAULT_EXPIRY_WARNING_DAYS: i64 = 7;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialExpiryWarning {
+    pub cluster_id: String,
+    pub cluster_name: String,
+    pub expires_at: i64,
+    pub expired: bool,
+}
+
+/// Decode a certificate (PEM or DER) and return its notAfter as a unix timestamp
+fn certificate_expiry(bytes: &[u8]) -> Option<i64> {
+    if let Ok((_, pem)) = x509_parser::pem::parse_x509_pem(bytes) {
+        return pem
+            .parse_x509()
+            .ok()
+            .map(|cert| cert.validity().not_after.timestamp());
+    }
+
+    x509_parser::parse_x509_certificate(bytes)
+        .ok()
+        .map(|(_, cert)| cert.validity().not_after.timestamp())
+}
+
+/// Read the `exp` claim of a JWT bearer token, if the token is a JWT
+fn token_expiry(token: &str) -> Option<i64> {
+    let payload = token.split('.').nth(1)?;
+    let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&decoded).ok()?;
+    claims.get("exp")?.as_i64()
+}
+
+fn auth_info_expiry(auth_info: &AuthInfo) -> Option<i64> {
+    let mut expiries = Vec::new();
+
+    if let Some(data) = auth_info.client_certificate_data.as_ref() {
+        if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(data.trim()) {
+            expiries.extend(certificate_expiry(&bytes));
+        }
+    } else if let Some(path) = auth_info.client_certificate.as_ref() {
+        if let Ok(bytes) = std::fs::read(path) {
+            expiries.extend(certificate_expiry(&bytes));
+        }
+    }
+
+    if let Some(token) = auth_info.token.as_ref() {
+        expiries.extend(token_expiry(token.expose_secret()));
+    }
+
+    expiries.into_iter().min()
+}
+
+/// Earliest credential expiry found in a kubeconfig file, as a unix timestamp.
+/// Returns `None` when the credentials carry no expiry (exec plugins, opaque tokens, ...).
+pub fn credential_expiry_for_kubeconfig(path: &Path) -> Result<Option<i64>, String> {
+    let kubeconfig =
+        Kubeconfig::read_from(path).map_err(|e| format!("Failed to read kubeconfig: {}", e))?;
+
+    Ok(kubeconfig
+        .auth_infos
+        .iter()
+        .filter_map(|named| named.auth_info.as_ref())
+        .filter_map(auth_info_expiry)
+        .min())
+}
+
+/// Re-read the credentials of every stored cluster and persist their expiry
+fn refresh_credential_expiry(manager: &ClusterManager) -> Result<(), String> {
+    for cluster in manager.list_clusters()? {
+        match credential_expiry_for_kubeconfig(&PathBuf::from(&cluster.config_path)) {
+            Ok(expires_at) => manager.set_credentials_expiry(&cluster.id, expires_at)?,
+            Err(e) => eprintln!(
+                "Failed to read credentials for cluster '{}': {}",
+                cluster.name, e
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Clusters whose credentials expire before `now + window_secs` (including already expired ones)
+fn expiring_clusters(
+    manager: &ClusterManager,
+    now: i64,
+    window_secs: i64,
+) -> Result<Vec<CredentialExpiryWarning>, String> {
+    let mut warnings: Vec<CredentialExpiryWarning> = manager
+        .list_clusters()?
+        .into_iter()
+        .filter_map(|cluster| {
+            let expires_at = cluster.credentials_expires_at?;
+            if expires_at > now + window_secs {
+                return None;
+            }
+            Some(CredentialExpiryWarning {
+                cluster_id: cluster.id,
+                cluster_name: cluster.name,
+                expires_at,
+                expired: expires_at <= now,
+            })
+        })
+        .collect();
+
+    warnings.sort_by_key(|w| w.expires_at);
+    Ok(warnings)
+}
+
+fn describe_expiry(warning: &CredentialExpiryWarning, now: i64) -> String {
+    if warning.expired {
+        return format!(
+            "Credentials for cluster '{}' have expired",
+            warning.cluster_name
+        );
+    }
+
+    let remaining = warning.expires_at - now;
+    let when = if remaining >= SECONDS_PER_DAY {
+        format!("{}d", remaining / SECONDS_PER_DAY)
+    } else if remaining >= 3600 {
+        format!("{}h", remaining / 3600)
+    } else {
+        format!("{}m", (remaining / 60).max(1))
+    };
+
+    format!(
+        "Credentials for cluster '{}' expire in {}",
+        warning.cluster_name, when
+    )
+}
+
+// Tauri Commands
+
+/// Refresh stored credential expiry for all clusters and show a desktop notification
+/// for each cluster whose credentials expire within `warn_within_days`.
+#[tauri::command]
+pub fn credentials_check_expiry(
+    warn_within_days: Option<i64>,
+    app: AppHandle,
+    state: State<ClusterManagerState>,
+) -> Result<Vec<CredentialExpiryWarning>, String> {
+    let window_days = warn_within_days
+        .unwrap_or(DEFAULT_EXPIRY_WARNING_DAYS)
+        .max(0);
+
+    let manager = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock: {}", e))?;
+    refresh_credential_expiry(&manager)?;
+
+    let now = chrono::Utc::now().timestamp();
+    let warnings = expiring_clusters(&manager, now, window_days * SECONDS_PER_DAY)?;
+
+    for warning in &warnings {
+        if let Err(e) = app
+            .notification()
+            .builder()
+            .title("Kubeconfig credentials expiring")
+            .body(describe_expiry(warning, now))
+            .show()
+        {
+            eprintln!("Failed to show notification: {}", e);
+        }
+    }
+
+    Ok(warnings)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn jwt_with_exp(exp: i64) -> String {
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        format!(
+            "{}.{}.signature",
+            engine.encode(r#"{"alg":"RS256"}"#),
+            engine.encode(format!(
+                r#"{{"sub":"system:serviceaccount:a:b","exp":{}}}"#,
+                exp
+            ))
+        )
+    }
+
+    fn write_kubeconfig(dir: &Path, user_block: &str) -> PathBuf {
+        let path = dir.join("config.yaml");
+        let content = format!(
+            "apiVersion: v1
+kind: Config
+current-context: ctx
+clusters:
+- name: c
+  cluster:
+    server: https://example.com
+users:
+- name: u
+  user:
+{}
+contexts:
+- name: ctx
+  context:
+    cluster: c
+    user: u
+",
+            user_block
+        );
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn token_expiry_reads_jwt_exp_claim() {
+        assert_eq!(
+            token_expiry(&jwt_with_exp(1_900_000_000)),
+            Some(1_900_000_000)
+        );
+    }
+
+    #[test]
+    fn token_expiry_ignores_opaque_tokens() {
+        assert_eq!(token_expiry("test-token"), None);
+        assert_eq!(token_expiry("a.not-base64!.c"), None);
+    }
+
+    // Self-signed test certificate, notAfter 2036-10-16T01:36:57Z
+    const TEST_CERT_PEM: &str = concat!(
+        "-----BEGIN CERTIFICATE-----\n",
+        "MIIBfTCCASOgAwIBAgIUUnu9txZ9sQcsi2hcgtwNNWEaBEswCgYIKoZIzj0EAwIw\n",
+        "FDESMBAGA1UEAwwJa29yZS10ZXN0MB4XDTI2MTAxOTAxMzY1N1oXDTM2MTAxNjAx\n",
+        "MzY1N1owFDESMBAGA1UEAwwJa29yZS10ZXN0MFkwEwYHKoZIzj0CAQYIKoZIzj0D\n",
+        "AQcDQgAEcjHfjONK2HpcwRazfKpTuSVhvoLCJ2tebvAb6KdoSgO80kW3WI7AVxMH\n",
+        "v9ehmqZ4wteFzrOmmUuaPD+Kp4uFxaNTMFEwHQYDVR0OBBYEFG0Wa1UcFc7YEkRC\n",
+        "yF5vk4RDvdljMB8GA1UdIwQYMBaAFG0Wa1UcFc7YEkRCyF5vk4RDvdljMA8GA1Ud\n",
+        "EwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIhAI+PH0eg2kYvB/ETiwXmkQvr\n",
+        "BrU6tySKOXL82IWEShiJAiAPds6xyBq6ZOmhHD+4ZVnUuYQxdiW7xKS483jF3Hil\n",
+        "vw==\n",
+        "-----END CERTIFICATE-----\n",
+    );
+    const TEST_CERT_NOT_AFTER: i64 = 2_107_733_817;
+
+    #[test]
+    fn kubeconfig_expiry_reads_embedded_client_certificate() {
+        let temp = TempDir::new().unwrap();
+        let data = base64::engine::general_purpose::STANDARD.encode(TEST_CERT_PEM);
+        let path = write_kubeconfig(
+            temp.path(),
+            &format!("    client-certificate-data: {}", data),
+        );
+        assert_eq!(
+            credential_expiry_for_kubeconfig(&path).unwrap(),
+            Some(TEST_CERT_NOT_AFTER)
+        );
+    }
+
+    #[test]
+    fn certificate_expiry_rejects_garbage() {
+        assert_eq!(certificate_expiry(b"not a certificate"), None);
+    }
+
+    #[test]
+    fn kubeconfig_expiry_uses_token_exp() {
+        let temp = TempDir::new().unwrap();
+        let path = write_kubeconfig(
+            temp.path(),
+            &format!("    token: {}", jwt_with_exp(1_800_000_000)),
+        );
+        assert_eq!(
+            credential_expiry_for_kubeconfig(&path).unwrap(),
+            Some(1_800_000_000)
+        );
+    }
+
+    #[test]
+    fn kubeconfig_without_expiring_credentials_returns_none() {
+        let temp = TempDir::new().unwrap();
+        let path = write_kubeconfig(temp.path(), "    token: test-token");
+        assert_eq!(credential_expiry_for_kubeconfig(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn expiring_clusters_respects_window() {
+        let temp = TempDir::new().unwrap();
+        let manager = ClusterManager::new(temp.path().join("clusters.db")).unwrap();
+        let soon = manager
+            .add_cluster(
+                "soon".to_string(),
+                "ctx".to_string(),
+                PathBuf::from("/tmp/soon.yaml"),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap();
+        let later = manager
+            .add_cluster(
+                "later".to_string(),
+                "ctx".to_string(),
+                PathBuf::from("/tmp/later.yaml"),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap();
+        let now = 1_000_000;
+        manager
+            .set_credentials_expiry(&soon.id, Some(now + SECONDS_PER_DAY))
+            .unwrap();
+        manager
+            .set_credentials_expiry(&later.id, Some(now + 30 * SECONDS_PER_DAY))
+            .unwrap();
+
+        let warnings = expiring_clusters(&manager, now, 7 * SECONDS_PER_DAY).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].cluster_name, "soon");
+        assert!(!warnings[0].expired);
+        assert_eq!(
+            describe_expiry(&warnings[0], now),
+            "Credentials for cluster 'soon' expire in 1d"
+        );
+    }
+}
@@ -202,7 +202,10 @@ pub async fn import_add_cluster(
         .0
         .lock()
         .map_err(|e| format!("Failed to acquire lock: {}", e))?;
+    let credentials_expires_at =
+        crate::credentials::credential_expiry_for_kubeconfig(&config_path).unwrap_or(None);
     let cluster = manager.add_cluster(name, context_name, config_path, icon, description, tags)?;
+    manager.set_credentials_expiry(&cluster.id, credentials_expires_at)?;
 
     Ok(cluster.id)
 }
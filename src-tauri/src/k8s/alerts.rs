@@ -0,0 +1,728 @@
+use crate::alert_rules::{AlertCondition, AlertRule, AlertRuleState};
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::tasks::{BackgroundTaskState, TaskInfo};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{Event as CoreEvent, Node, Pod};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::{Api, Resource, ResourceExt};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State, Window};
+use tauri_plugin_notification::NotificationExt;
+
+/// A condition that flaps (e.g. CrashLoopBackOff between restarts) notifies at most this often
+const REFIRE_COOLDOWN_SECS: i64 = 30 * 60;
+/// How often duration-based rules ("unavailable for 5 min") are re-checked
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Waiting reasons that mean a container is not coming up on its own
+const POD_FAILURE_REASONS: &[&str] = &[
+    "CrashLoopBackOff",
+    "ImagePullBackOff",
+    "ErrImagePull",
+    "CreateContainerConfigError",
+    "CreateContainerError",
+    "InvalidImageName",
+    "RunContainerError",
+];
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct AlertObject {
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+impl AlertObject {
+    fn of<K: Resource<DynamicType = ()>>(obj: &K) -> Self {
+        AlertObject {
+            kind: K::kind(&()).to_string(),
+            namespace: obj.meta().namespace.clone(),
+            name: obj.name_any(),
+        }
+    }
+
+    fn key(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.kind,
+            self.namespace.as_deref().unwrap_or_default(),
+            self.name
+        )
+    }
+
+    fn display(&self) -> String {
+        match &self.namespace {
+            Some(ns) => format!("{} {}/{}", self.kind, ns, self.name),
+            None => format!("{} {}", self.kind, self.name),
+        }
+    }
+}
+
+/// Something wrong with an object that a rule may care about
+#[derive(Debug, Clone, PartialEq)]
+pub enum Signal {
+    PodFailure {
+        container: String,
+        reason: String,
+        restarts: i32,
+    },
+    NodeNotReady {
+        message: String,
+    },
+    DeploymentUnavailable {
+        since: i64,
+        message: String,
+    },
+    WarningEvent {
+        reason: String,
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FiredAlert {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub cluster_id: String,
+    pub object: AlertObject,
+    pub title: String,
+    pub body: String,
+    pub link: String, // App route that shows the object
+    pub fired_at: i64,
+}
+
+fn timestamp(time: Option<&Time>) -> Option<i64> {
+    time.and_then(|t| chrono::DateTime::parse_from_rfc3339(&t.0.to_string()).ok())
+        .map(|dt| dt.timestamp())
+}
+
+/// Failure signals of a pod's containers. OOM kills only count when they happened
+/// at or after `since` (monitoring start), so an old `lastState` is not reported.
+pub fn pod_signals(pod: &Pod, since: i64) -> Vec<Signal> {
+    let Some(status) = &pod.status else {
+        return vec![];
+    };
+    let statuses = status
+        .init_container_statuses
+        .iter()
+        .flatten()
+        .chain(status.container_statuses.iter().flatten());
+
+    let mut signals = Vec::new();
+    for cs in statuses {
+        let waiting = cs
+            .state
+            .as_ref()
+            .and_then(|s| s.waiting.as_ref())
+            .and_then(|w| w.reason.as_deref())
+            .filter(|reason| POD_FAILURE_REASONS.contains(reason));
+        let oom_killed = cs
+            .last_state
+            .as_ref()
+            .and_then(|s| s.terminated.as_ref())
+            .into_iter()
+            .chain(cs.state.as_ref().and_then(|s| s.terminated.as_ref()))
+            .any(|t| {
+                t.reason.as_deref() == Some("OOMKilled")
+                    && timestamp(t.finished_at.as_ref()).is_some_and(|at| at >= since)
+            });
+
+        let mut push = |reason: &str| {
+            signals.push(Signal::PodFailure {
+                container: cs.name.clone(),
+                reason: reason.to_string(),
+                restarts: cs.restart_count,
+            })
+        };
+        if let Some(reason) = waiting {
+            push(reason);
+        }
+        if oom_killed {
+            push("OOMKilled");
+        }
+    }
+    signals
+}
+
+pub fn node_signals(node: &Node) -> Vec<Signal> {
+    let ready = node
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .and_then(|c| c.iter().find(|c| c.type_ == "Ready"));
+    match ready {
+        Some(c) if c.status != "True" => vec![Signal::NodeNotReady {
+            message: c
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("Ready is {}", c.status)),
+        }],
+        _ => vec![],
+    }
+}
+
+pub fn deployment_signals(deployment: &Deployment) -> Vec<Signal> {
+    let available = deployment
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .and_then(|c| c.iter().find(|c| c.type_ == "Available"));
+    match available {
+        Some(c) if c.status == "False" => vec![Signal::DeploymentUnavailable {
+            since: timestamp(c.last_transition_time.as_ref())
+                .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            message: c.message.clone().unwrap_or_default(),
+        }],
+        _ => vec![],
+    }
+}
+
+/// The object a Warning event is about, and the event as a signal
+pub fn event_signal(event: &CoreEvent) -> Option<(AlertObject, Signal)> {
+    if event.type_.as_deref() != Some("Warning") {
+        return None;
+    }
+    let involved = &event.involved_object;
+    let object = AlertObject {
+        kind: involved.kind.clone()?,
+        namespace: involved
+            .namespace
+            .clone()
+            .or_else(|| event.metadata.namespace.clone()),
+        name: involved.name.clone()?,
+    };
+    Some((
+        object,
+        Signal::WarningEvent {
+            reason: event.reason.clone().unwrap_or_default(),
+            message: event.message.clone().unwrap_or_default(),
+        },
+    ))
+}
+
+fn signal_matches(condition: &AlertCondition, signal: &Signal, now: i64) -> bool {
+    match (condition, signal) {
+        (AlertCondition::PodFailure { reasons }, Signal::PodFailure { reason, .. }) => {
+            reasons.iter().any(|r| r.eq_ignore_ascii_case(reason))
+        }
+        (AlertCondition::NodeNotReady, Signal::NodeNotReady { .. }) => true,
+        (
+            AlertCondition::DeploymentUnavailable { for_seconds },
+            Signal::DeploymentUnavailable { since, .. },
+        ) => now - since >= *for_seconds,
+        (AlertCondition::WarningEvent { reason: wanted }, Signal::WarningEvent { reason, .. }) => {
+            wanted.eq_ignore_ascii_case(reason)
+        }
+        _ => false,
+    }
+}
+
+/// Identity of one notification. OOM kills include the restart count so that
+/// each new kill notifies again.
+fn alert_key(rule: &AlertRule, object: &AlertObject, signal: &Signal) -> String {
+    let detail = match signal {
+        Signal::PodFailure {
+            container,
+            reason,
+            restarts,
+        } if reason == "OOMKilled" => format!("{}:{}:{}", container, reason, restarts),
+        Signal::PodFailure {
+            container, reason, ..
+        } => format!("{}:{}", container, reason),
+        Signal::WarningEvent { reason, .. } => reason.clone(),
+        Signal::NodeNotReady { .. } | Signal::DeploymentUnavailable { .. } => String::new(),
+    };
+    format!("{}|{}|{}", rule.id, object.key(), detail)
+}
+
+fn object_link(cluster_id: &str, object: &AlertObject) -> String {
+    match object.kind.as_str() {
+        "Pod" => format!(
+            "/cluster/{}/pods?pod={}&namespace={}",
+            cluster_id,
+            object.name,
+            object.namespace.as_deref().unwrap_or_default()
+        ),
+        "Node" => format!("/cluster/{}/nodes", cluster_id),
+        "Deployment" => format!("/cluster/{}/deployments", cluster_id),
+        _ => format!("/cluster/{}/events", cluster_id),
+    }
+}
+
+fn describe(signal: &Signal, now: i64) -> String {
+    match signal {
+        Signal::PodFailure {
+            container,
+            reason,
+            restarts,
+        } => format!(
+            "Container {} is {} ({} restarts)",
+            container, reason, restarts
+        ),
+        Signal::NodeNotReady { message } => format!("Not ready: {}", message),
+        Signal::DeploymentUnavailable { since, message } => format!(
+            "Unavailable for {}m: {}",
+            (now - since).max(0) / 60,
+            message
+        ),
+        Signal::WarningEvent { reason, message } => format!("{}: {}", reason, message),
+    }
+}
+
+/// What a cluster's watches observed, ready for the engine
+pub enum Observation {
+    Reset(&'static str), // A kind is being re-listed; forget what we knew about it
+    Object(AlertObject, Vec<Signal>),
+    Gone(AlertObject),
+    Event(AlertObject, Signal),
+}
+
+/// Per-cluster rule evaluation with de-duplication: a notification fires when a
+/// rule starts matching an object, not again while it keeps matching, and not
+/// more than once per cooldown if the condition flaps.
+pub struct AlertEngine {
+    cluster_id: String,
+    cluster_name: String,
+    signals: HashMap<String, (AlertObject, Vec<Signal>)>, // Only objects with something wrong
+    active: HashMap<String, HashSet<String>>, // Alert keys currently matching, per object
+    last_fired: HashMap<String, i64>,
+}
+
+impl AlertEngine {
+    pub fn new(cluster_id: &str, cluster_name: &str) -> Self {
+        AlertEngine {
+            cluster_id: cluster_id.to_string(),
+            cluster_name: cluster_name.to_string(),
+            signals: HashMap::new(),
+            active: HashMap::new(),
+            last_fired: HashMap::new(),
+        }
+    }
+
+    pub fn handle(
+        &mut self,
+        rules: &[AlertRule],
+        observation: Observation,
+        now: i64,
+    ) -> Vec<FiredAlert> {
+        match observation {
+            Observation::Reset(kind) => {
+                let prefix = format!("{}/", kind);
+                self.signals.retain(|key, _| !key.starts_with(&prefix));
+                self.active.retain(|key, _| !key.starts_with(&prefix));
+                vec![]
+            }
+            Observation::Object(object, signals) => {
+                let key = object.key();
+                if signals.is_empty() {
+                    self.signals.remove(&key);
+                    self.active.remove(&key);
+                    return vec![];
+                }
+                self.signals.insert(key.clone(), (object, signals));
+                self.evaluate(rules, &key, now)
+            }
+            Observation::Gone(object) => {
+                let key = object.key();
+                self.signals.remove(&key);
+                self.active.remove(&key);
+                vec![]
+            }
+            Observation::Event(object, signal) => {
+                // Events are one-off occurrences, so only the cooldown de-duplicates them
+                let mut fired = Vec::new();
+                for rule in rules {
+                    if rule.applies_to(&self.cluster_id, object.namespace.as_deref())
+                        && signal_matches(&rule.condition, &signal, now)
+                    {
+                        let key = alert_key(rule, &object, &signal);
+                        fired.extend(self.fire(key, rule, &object, &signal, now));
+                    }
+                }
+                fired
+            }
+        }
+    }
+
+    /// Re-check every tracked object, e.g. for rules that need a condition to persist
+    pub fn tick(&mut self, rules: &[AlertRule], now: i64) -> Vec<FiredAlert> {
+        self.last_fired
+            .retain(|_, fired_at| now - *fired_at < REFIRE_COOLDOWN_SECS);
+        let keys: Vec<String> = self.signals.keys().cloned().collect();
+        keys.iter()
+            .flat_map(|key| self.evaluate(rules, key, now))
+            .collect()
+    }
+
+    fn evaluate(&mut self, rules: &[AlertRule], key: &str, now: i64) -> Vec<FiredAlert> {
+        let Some((object, signals)) = self.signals.get(key).cloned() else {
+            return vec![];
+        };
+        let previous = self.active.remove(key).unwrap_or_default();
+        let mut matching = HashSet::new();
+        let mut fired = Vec::new();
+
+        for rule in rules {
+            if !rule.applies_to(&self.cluster_id, object.namespace.as_deref()) {
+                continue;
+            }
+            for signal in signals
+                .iter()
+                .filter(|s| signal_matches(&rule.condition, s, now))
+            {
+                let alert = alert_key(rule, &object, signal);
+                if !previous.contains(&alert) {
+                    fired.extend(self.fire(alert.clone(), rule, &object, signal, now));
+                }
+                matching.insert(alert);
+            }
+        }
+        self.active.insert(key.to_string(), matching);
+        fired
+    }
+
+    fn fire(
+        &mut self,
+        key: String,
+        rule: &AlertRule,
+        object: &AlertObject,
+        signal: &Signal,
+        now: i64,
+    ) -> Option<FiredAlert> {
+        if self
+            .last_fired
+            .get(&key)
+            .is_some_and(|fired_at| now - fired_at < REFIRE_COOLDOWN_SECS)
+        {
+            return None;
+        }
+        self.last_fired.insert(key, now);
+        Some(FiredAlert {
+            rule_id: rule.id.clone(),
+            rule_name: rule.name.clone(),
+            cluster_id: self.cluster_id.clone(),
+            object: object.clone(),
+            title: format!("{} · {}", rule.name, self.cluster_name),
+            body: format!("{}\n{}", object.display(), describe(signal, now)),
+            link: object_link(&self.cluster_id, object),
+            fired_at: now,
+        })
+    }
+}
+
+type ObservationStream = BoxStream<'static, Observation>;
+
+fn object_observations<K>(
+    api: Api<K>,
+    kind: &'static str,
+    signals: impl Fn(&K) -> Vec<Signal> + Copy + Send + 'static,
+) -> ObservationStream
+where
+    K: Resource<DynamicType = ()>
+        + Clone
+        + serde::de::DeserializeOwned
+        + Debug
+        + Send
+        + Sync
+        + 'static,
+{
+    watcher(api, watcher::Config::default())
+        .default_backoff()
+        .filter_map(move |result| async move {
+            match result {
+                Ok(watcher::Event::Init) => Some(Observation::Reset(kind)),
+                Ok(watcher::Event::InitApply(obj) | watcher::Event::Apply(obj)) => {
+                    Some(Observation::Object(AlertObject::of(&obj), signals(&obj)))
+                }
+                Ok(watcher::Event::Delete(obj)) => Some(Observation::Gone(AlertObject::of(&obj))),
+                Ok(watcher::Event::InitDone) => None,
+                Err(e) => {
+                    println!("Alert watch error ({}): {}", kind, e);
+                    None
+                }
+            }
+        })
+        .boxed()
+}
+
+fn event_observations(api: Api<CoreEvent>) -> ObservationStream {
+    let config = watcher::Config::default().fields("type=Warning");
+    watcher(api, config)
+        .default_backoff()
+        .filter_map(|result| async move {
+            match result {
+                // Warnings that already existed when monitoring started are not news
+                Ok(watcher::Event::Apply(event)) => {
+                    event_signal(&event).map(|(object, signal)| Observation::Event(object, signal))
+                }
+                Ok(_) => None,
+                Err(e) => {
+                    println!("Alert watch error (Event): {}", e);
+                    None
+                }
+            }
+        })
+        .boxed()
+}
+
+fn notify(app: &AppHandle, alert: &FiredAlert) {
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(&alert.title)
+        .body(&alert.body)
+        .show()
+    {
+        eprintln!("Failed to show notification: {}", e);
+    }
+    // The UI uses the link to take the user to the object
+    if let Err(e) = app.emit("alert_fired", alert) {
+        eprintln!("Failed to emit alert: {}", e);
+    }
+}
+
+/// Start background watches that evaluate the alert rules for a cluster.
+/// Calling it again restarts monitoring for that cluster.
+#[tauri::command]
+pub async fn alerts_start_monitoring(
+    cluster_id: String,
+    app: AppHandle,
+    window: Window,
+    state: State<'_, ClusterManagerState>,
+    rules: State<'_, AlertRuleState>,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    let cluster_name = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock: {}", e))?
+        .get_cluster(&cluster_id)?
+        .map(|c| c.name)
+        .unwrap_or_else(|| cluster_id.clone());
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let started = chrono::Utc::now().timestamp();
+
+    let mut observations = futures::stream::select_all([
+        object_observations(Api::<Pod>::all(client.clone()), "Pod", move |pod| {
+            pod_signals(pod, started)
+        }),
+        object_observations(Api::<Node>::all(client.clone()), "Node", node_signals),
+        object_observations(
+            Api::<Deployment>::all(client.clone()),
+            "Deployment",
+            deployment_signals,
+        ),
+        event_observations(Api::all(client)),
+    ]);
+
+    let info = TaskInfo::new(
+        format!("alerts:{}", cluster_id),
+        "alerts",
+        Some(&cluster_id),
+        &window,
+        format!("Alert rules for {}", cluster_name),
+    );
+    let store = rules.0.clone();
+
+    tasks.spawn(info, async move {
+        let mut engine = AlertEngine::new(&cluster_id, &cluster_name);
+        let mut interval = tokio::time::interval(EVALUATION_INTERVAL);
+
+        loop {
+            let fired = tokio::select! {
+                next = observations.next() => {
+                    let Some(observation) = next else { break };
+                    let rules = store.list().unwrap_or_default();
+                    engine.handle(&rules, observation, chrono::Utc::now().timestamp())
+                }
+                _ = interval.tick() => {
+                    let rules = store.list().unwrap_or_default();
+                    engine.tick(&rules, chrono::Utc::now().timestamp())
+                }
+            };
+            for alert in &fired {
+                notify(&app, alert);
+            }
+        }
+    })
+}
+
+#[tauri::command]
+pub async fn alerts_stop_monitoring(
+    cluster_id: String,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    tasks.stop(&format!("alerts:{}", cluster_id))?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(id: &str, condition: AlertCondition) -> AlertRule {
+        AlertRule {
+            id: id.to_string(),
+            name: format!("rule {}", id),
+            enabled: true,
+            cluster_ids: vec![],
+            namespace: Some("prod".to_string()),
+            condition,
+            created_at: 0,
+        }
+    }
+
+    fn crash_looping_pod(restarts: i32) -> Pod {
+        serde_json::from_value(json!({
+            "metadata": {"name": "api-0", "namespace": "prod"},
+            "status": {"containerStatuses": [{
+                "name": "api", "image": "api:1", "imageID": "", "ready": false,
+                "restartCount": restarts,
+                "state": {"waiting": {"reason": "CrashLoopBackOff"}},
+                "lastState": {"terminated": {
+                    "exitCode": 137, "reason": "OOMKilled", "finishedAt": "2024-05-01T10:00:00Z"
+                }}
+            }]}
+        }))
+        .unwrap()
+    }
+
+    /// 2024-05-01T09:00:00Z, before the fixture's OOM kill
+    const MONITORING_START: i64 = 1_714_554_000;
+
+    #[test]
+    fn test_pod_signals() {
+        let signals = pod_signals(&crash_looping_pod(4), MONITORING_START);
+        assert_eq!(signals.len(), 2);
+        assert!(
+            matches!(&signals[0], Signal::PodFailure { reason, restarts: 4, .. } if reason == "CrashLoopBackOff")
+        );
+        assert!(matches!(&signals[1], Signal::PodFailure { reason, .. } if reason == "OOMKilled"));
+
+        let healthy: Pod = serde_json::from_value(json!({
+            "metadata": {"name": "ok"},
+            "status": {"containerStatuses": [{
+                "name": "c", "image": "", "imageID": "", "ready": true, "restartCount": 0,
+                "state": {"waiting": {"reason": "ContainerCreating"}}
+            }]}
+        }))
+        .unwrap();
+        assert!(pod_signals(&healthy, MONITORING_START).is_empty());
+    }
+
+    #[test]
+    fn test_pod_signals_ignore_oom_kills_before_monitoring_started() {
+        let signals = pod_signals(&crash_looping_pod(4), MONITORING_START + 7200);
+        assert_eq!(signals.len(), 1);
+        assert!(
+            matches!(&signals[0], Signal::PodFailure { reason, .. } if reason == "CrashLoopBackOff")
+        );
+    }
+
+    #[test]
+    fn test_engine_fires_once_while_condition_persists() {
+        let rules = vec![rule(
+            "crash",
+            AlertCondition::PodFailure {
+                reasons: vec!["crashloopbackoff".to_string()],
+            },
+        )];
+        let mut engine = AlertEngine::new("c1", "Prod");
+        let pod = crash_looping_pod(1);
+        let observe = |pod: &Pod| {
+            Observation::Object(AlertObject::of(pod), pod_signals(pod, MONITORING_START))
+        };
+
+        let fired = engine.handle(&rules, observe(&pod), 1000);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].link, "/cluster/c1/pods?pod=api-0&namespace=prod");
+        assert!(engine.handle(&rules, observe(&pod), 1010).is_empty());
+        assert!(engine.tick(&rules, 1020).is_empty());
+
+        // Recovering and crashing again within the cooldown stays quiet
+        engine.handle(
+            &rules,
+            Observation::Object(AlertObject::of(&pod), vec![]),
+            1030,
+        );
+        assert!(engine.handle(&rules, observe(&pod), 1040).is_empty());
+        engine.handle(
+            &rules,
+            Observation::Object(AlertObject::of(&pod), vec![]),
+            1050,
+        );
+        assert_eq!(
+            engine
+                .handle(&rules, observe(&pod), 1000 + REFIRE_COOLDOWN_SECS)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_engine_waits_for_deployment_unavailable_duration() {
+        let rules = vec![rule(
+            "unavailable",
+            AlertCondition::DeploymentUnavailable { for_seconds: 300 },
+        )];
+        let mut engine = AlertEngine::new("c1", "Prod");
+        let object = AlertObject {
+            kind: "Deployment".to_string(),
+            namespace: Some("prod".to_string()),
+            name: "web".to_string(),
+        };
+        let signal = Signal::DeploymentUnavailable {
+            since: 1000,
+            message: "MinimumReplicasUnavailable".to_string(),
+        };
+
+        assert!(engine
+            .handle(&rules, Observation::Object(object, vec![signal]), 1100)
+            .is_empty());
+        assert!(engine.tick(&rules, 1200).is_empty());
+        let fired = engine.tick(&rules, 1300);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].body.contains("Unavailable for 5m"));
+        assert!(engine.tick(&rules, 1330).is_empty());
+    }
+
+    #[test]
+    fn test_engine_scopes_warning_events_by_namespace_and_reason() {
+        let rules = vec![rule(
+            "evicted",
+            AlertCondition::WarningEvent {
+                reason: "Evicted".to_string(),
+            },
+        )];
+        let mut engine = AlertEngine::new("c1", "Prod");
+        let event = |namespace: &str, reason: &str| {
+            let event: CoreEvent = serde_json::from_value(json!({
+                "metadata": {"name": "e", "namespace": namespace},
+                "involvedObject": {"kind": "Pod", "name": "api-0", "namespace": namespace},
+                "type": "Warning",
+                "reason": reason,
+                "message": "The node was low on resource: memory."
+            }))
+            .unwrap();
+            let (object, signal) = event_signal(&event).unwrap();
+            Observation::Event(object, signal)
+        };
+
+        assert!(engine.handle(&rules, event("dev", "Evicted"), 0).is_empty());
+        assert!(engine
+            .handle(&rules, event("prod", "BackOff"), 0)
+            .is_empty());
+        assert_eq!(engine.handle(&rules, event("prod", "Evicted"), 0).len(), 1);
+        assert!(engine
+            .handle(&rules, event("prod", "Evicted"), 60)
+            .is_empty());
+    }
+}
@@ -0,0 +1,615 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::{
+    label_selector_matches, label_selector_to_string, list_namespaced_or_all,
+};
+use crate::k8s::relationships::pod_spec_references;
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{
+    ConfigMap, PersistentVolumeClaim, Pod, PodSpec, Secret, Service, ServiceAccount,
+};
+use k8s_openapi::api::networking::v1::{Ingress, IngressServiceBackend};
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::api::rbac::v1::{ClusterRole, ClusterRoleBinding, Role, RoleBinding, RoleRef};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::Api;
+use std::collections::HashSet;
+use tauri::State;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+pub struct ObjectRef {
+    pub kind: String,
+    pub namespace: Option<String>, // None for cluster-scoped objects
+    pub name: String,
+}
+
+impl ObjectRef {
+    fn new(kind: &str, namespace: Option<&str>, name: &str) -> Self {
+        ObjectRef {
+            kind: kind.to_string(),
+            namespace: namespace.map(str::to_string),
+            name: name.to_string(),
+        }
+    }
+
+    fn of(kind: &str, metadata: &ObjectMeta) -> Self {
+        Self::new(
+            kind,
+            metadata.namespace.as_deref(),
+            metadata.name.as_deref().unwrap_or_default(),
+        )
+    }
+}
+
+/// A field on `source` that points at something that does not exist
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct BrokenReference {
+    pub source: ObjectRef,
+    pub field_path: String, // e.g. spec.template.spec.volumes[0].configMap.name
+    pub target: ObjectRef,
+    pub reason: String,
+}
+
+/// Objects the detector cross-checks
+#[derive(Default)]
+pub struct ReferenceInput {
+    pub pods: Vec<Pod>,
+    pub deployments: Vec<Deployment>,
+    pub stateful_sets: Vec<StatefulSet>,
+    pub daemon_sets: Vec<DaemonSet>,
+    pub replica_sets: Vec<ReplicaSet>,
+    pub jobs: Vec<Job>,
+    pub cron_jobs: Vec<CronJob>,
+    pub config_maps: Vec<ConfigMap>,
+    pub secrets: Vec<Secret>,
+    pub claims: Vec<PersistentVolumeClaim>,
+    pub service_accounts: Vec<ServiceAccount>,
+    pub services: Vec<Service>,
+    pub ingresses: Vec<Ingress>,
+    pub roles: Vec<Role>,
+    pub cluster_roles: Vec<ClusterRole>,
+    pub role_bindings: Vec<RoleBinding>,
+    pub cluster_role_bindings: Vec<ClusterRoleBinding>,
+    pub hpas: Vec<HorizontalPodAutoscaler>,
+    pub pdbs: Vec<PodDisruptionBudget>,
+}
+
+/// (kind, namespace, name) of every object that exists
+struct Existing(HashSet<(String, String, String)>);
+
+impl Existing {
+    fn new(input: &ReferenceInput) -> Self {
+        let mut existing = Existing(HashSet::new());
+        existing.add(&input.config_maps);
+        existing.add(&input.secrets);
+        existing.add(&input.claims);
+        existing.add(&input.service_accounts);
+        existing.add(&input.services);
+        existing.add(&input.roles);
+        existing.add(&input.cluster_roles);
+        existing.add(&input.deployments);
+        existing.add(&input.stateful_sets);
+        existing.add(&input.replica_sets);
+        existing
+    }
+
+    fn add<K>(&mut self, objects: &[K])
+    where
+        K: k8s_openapi::Resource + k8s_openapi::Metadata<Ty = ObjectMeta>,
+    {
+        for object in objects {
+            let metadata = object.metadata();
+            self.0.insert((
+                K::KIND.to_string(),
+                metadata.namespace.clone().unwrap_or_default(),
+                metadata.name.clone().unwrap_or_default(),
+            ));
+        }
+    }
+
+    fn contains(&self, target: &ObjectRef) -> bool {
+        self.0.contains(&(
+            target.kind.clone(),
+            target.namespace.clone().unwrap_or_default(),
+            target.name.clone(),
+        ))
+    }
+}
+
+fn is_unowned(metadata: &ObjectMeta) -> bool {
+    metadata
+        .owner_references
+        .as_ref()
+        .is_none_or(|o| o.is_empty())
+}
+
+/// Pod specs to check, with the field prefix leading to each. Pods and Jobs created by a
+/// controller are covered by the controller's template.
+fn pod_spec_sources(input: &ReferenceInput) -> Vec<(ObjectRef, &'static str, &PodSpec)> {
+    let mut sources = Vec::new();
+    let template = "spec.template.spec.";
+    for d in &input.deployments {
+        if let Some(spec) = d.spec.as_ref().and_then(|s| s.template.spec.as_ref()) {
+            sources.push((ObjectRef::of("Deployment", &d.metadata), template, spec));
+        }
+    }
+    for s in &input.stateful_sets {
+        if let Some(spec) = s.spec.as_ref().and_then(|s| s.template.spec.as_ref()) {
+            sources.push((ObjectRef::of("StatefulSet", &s.metadata), template, spec));
+        }
+    }
+    for d in &input.daemon_sets {
+        if let Some(spec) = d.spec.as_ref().and_then(|s| s.template.spec.as_ref()) {
+            sources.push((ObjectRef::of("DaemonSet", &d.metadata), template, spec));
+        }
+    }
+    for j in input.jobs.iter().filter(|j| is_unowned(&j.metadata)) {
+        if let Some(spec) = j.spec.as_ref().and_then(|s| s.template.spec.as_ref()) {
+            sources.push((ObjectRef::of("Job", &j.metadata), template, spec));
+        }
+    }
+    for c in &input.cron_jobs {
+        let spec = c
+            .spec
+            .as_ref()
+            .and_then(|s| s.job_template.spec.as_ref())
+            .and_then(|s| s.template.spec.as_ref());
+        if let Some(spec) = spec {
+            sources.push((
+                ObjectRef::of("CronJob", &c.metadata),
+                "spec.jobTemplate.spec.template.spec.",
+                spec,
+            ));
+        }
+    }
+    for p in input.pods.iter().filter(|p| is_unowned(&p.metadata)) {
+        if let Some(spec) = &p.spec {
+            sources.push((ObjectRef::of("Pod", &p.metadata), "spec.", spec));
+        }
+    }
+    sources
+}
+
+fn check_ingress_backend(
+    source: &ObjectRef,
+    field_path: String,
+    backend: &IngressServiceBackend,
+    input: &ReferenceInput,
+    broken: &mut Vec<BrokenReference>,
+) {
+    let target = ObjectRef::new("Service", source.namespace.as_deref(), &backend.name);
+    let Some(service) = input.services.iter().find(|s| {
+        s.metadata.namespace == target.namespace && s.metadata.name.as_ref() == Some(&target.name)
+    }) else {
+        broken.push(BrokenReference {
+            source: source.clone(),
+            field_path: format!("{}.name", field_path),
+            target,
+            reason: "Service not found".to_string(),
+        });
+        return;
+    };
+
+    let Some(port) = &backend.port else {
+        return;
+    };
+    let ports = service
+        .spec
+        .as_ref()
+        .and_then(|s| s.ports.as_deref())
+        .unwrap_or_default();
+    let (field, wanted, found) = match (&port.name, port.number) {
+        (Some(name), _) if !name.is_empty() => (
+            "port.name",
+            name.clone(),
+            ports.iter().any(|p| p.name.as_ref() == Some(name)),
+        ),
+        (_, Some(number)) => (
+            "port.number",
+            number.to_string(),
+            ports.iter().any(|p| p.port == number),
+        ),
+        _ => return,
+    };
+    if !found {
+        broken.push(BrokenReference {
+            source: source.clone(),
+            field_path: format!("{}.{}", field_path, field),
+            target,
+            reason: format!("Service has no port {}", wanted),
+        });
+    }
+}
+
+fn check_role_ref(
+    source: ObjectRef,
+    role_ref: &RoleRef,
+    existing: &Existing,
+    broken: &mut Vec<BrokenReference>,
+) {
+    // A RoleBinding may reference a namespaced Role or a ClusterRole
+    let namespace = match role_ref.kind.as_str() {
+        "Role" => source.namespace.as_deref(),
+        _ => None,
+    };
+    let target = ObjectRef::new(&role_ref.kind, namespace, &role_ref.name);
+    if !existing.contains(&target) {
+        broken.push(BrokenReference {
+            source,
+            field_path: "roleRef.name".to_string(),
+            target,
+            reason: format!("{} not found", role_ref.kind),
+        });
+    }
+}
+
+/// Find every reference in the input that points at a missing object
+pub fn find_broken_references(input: &ReferenceInput) -> Vec<BrokenReference> {
+    let existing = Existing::new(input);
+    let mut broken = Vec::new();
+
+    for (source, prefix, spec) in pod_spec_sources(input) {
+        for reference in pod_spec_references(spec)
+            .into_iter()
+            .filter(|r| !r.optional)
+        {
+            let target = ObjectRef::new(
+                &reference.kind,
+                source.namespace.as_deref(),
+                &reference.name,
+            );
+            if !existing.contains(&target) {
+                broken.push(BrokenReference {
+                    source: source.clone(),
+                    field_path: format!("{}{}", prefix, reference.field_path),
+                    reason: format!("{} not found", target.kind),
+                    target,
+                });
+            }
+        }
+    }
+
+    for ingress in &input.ingresses {
+        let source = ObjectRef::of("Ingress", &ingress.metadata);
+        let Some(spec) = &ingress.spec else {
+            continue;
+        };
+        if let Some(backend) = spec
+            .default_backend
+            .as_ref()
+            .and_then(|b| b.service.as_ref())
+        {
+            check_ingress_backend(
+                &source,
+                "spec.defaultBackend.service".to_string(),
+                backend,
+                input,
+                &mut broken,
+            );
+        }
+        for (i, rule) in spec.rules.iter().flatten().enumerate() {
+            let paths = rule.http.iter().flat_map(|h| h.paths.iter()).enumerate();
+            for (j, path) in paths {
+                if let Some(backend) = &path.backend.service {
+                    check_ingress_backend(
+                        &source,
+                        format!("spec.rules[{}].http.paths[{}].backend.service", i, j),
+                        backend,
+                        input,
+                        &mut broken,
+                    );
+                }
+            }
+        }
+    }
+
+    for binding in &input.role_bindings {
+        let source = ObjectRef::of("RoleBinding", &binding.metadata);
+        check_role_ref(source, &binding.role_ref, &existing, &mut broken);
+    }
+    for binding in &input.cluster_role_bindings {
+        let source = ObjectRef::of("ClusterRoleBinding", &binding.metadata);
+        check_role_ref(source, &binding.role_ref, &existing, &mut broken);
+    }
+
+    for hpa in &input.hpas {
+        let Some(target_ref) = hpa.spec.as_ref().map(|s| &s.scale_target_ref) else {
+            continue;
+        };
+        // Only built-in scale targets are fetched
+        if !matches!(
+            target_ref.kind.as_str(),
+            "Deployment" | "StatefulSet" | "ReplicaSet"
+        ) {
+            continue;
+        }
+        let target = ObjectRef::new(
+            &target_ref.kind,
+            hpa.metadata.namespace.as_deref(),
+            &target_ref.name,
+        );
+        if !existing.contains(&target) {
+            broken.push(BrokenReference {
+                source: ObjectRef::of("HorizontalPodAutoscaler", &hpa.metadata),
+                field_path: "spec.scaleTargetRef.name".to_string(),
+                reason: format!("{} not found", target.kind),
+                target,
+            });
+        }
+    }
+
+    for pdb in &input.pdbs {
+        let Some(selector) = pdb.spec.as_ref().and_then(|s| s.selector.as_ref()) else {
+            continue;
+        };
+        let matches_any = input.pods.iter().any(|p| {
+            p.metadata.namespace == pdb.metadata.namespace
+                && label_selector_matches(selector, &p.metadata.labels.clone().unwrap_or_default())
+        });
+        if !matches_any {
+            broken.push(BrokenReference {
+                source: ObjectRef::of("PodDisruptionBudget", &pdb.metadata),
+                field_path: "spec.selector".to_string(),
+                target: ObjectRef::new(
+                    "Pod",
+                    pdb.metadata.namespace.as_deref(),
+                    &label_selector_to_string(selector),
+                ),
+                reason: "Selector matches no pods".to_string(),
+            });
+        }
+    }
+
+    broken.sort_by(|a, b| (&a.source, &a.field_path).cmp(&(&b.source, &b.field_path)));
+    broken
+}
+
+/// List objects with metadata only. ConfigMaps and Secrets are referenced by
+/// name, so their data is never fetched.
+async fn list_names_only<K>(client: &kube::Client, ns: Option<&str>) -> Result<Vec<K>, String>
+where
+    K: kube::Resource<Scope = k8s_openapi::NamespaceResourceScope>
+        + Default
+        + Clone
+        + serde::de::DeserializeOwned
+        + std::fmt::Debug,
+    K::DynamicType: Default,
+{
+    let api: Api<K> = match ns {
+        Some(ns) => Api::namespaced(client.clone(), ns),
+        None => Api::all(client.clone()),
+    };
+    let list = api
+        .list_metadata(&Default::default())
+        .await
+        .map_err(|e| format!("Failed to list {}: {}", K::plural(&Default::default()), e))?;
+    Ok(list
+        .items
+        .into_iter()
+        .map(|m| {
+            let mut object = K::default();
+            *object.meta_mut() = m.metadata;
+            object
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn cluster_find_broken_references(
+    cluster_id: String,
+    namespace: Option<String>,
+    state: State<'_, ClusterManagerState>,
+) -> Result<Vec<BrokenReference>, String> {
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let ns = namespace.as_deref().filter(|ns| !ns.is_empty());
+
+    // Bindings to ClusterRoles are checked in every scope; ClusterRoleBindings only cluster-wide
+    let cluster_roles = Api::<ClusterRole>::all(client.clone())
+        .list_metadata(&Default::default())
+        .await
+        .map_err(|e| format!("Failed to list clusterroles: {}", e))?
+        .items
+        .into_iter()
+        .map(|m| ClusterRole {
+            metadata: m.metadata,
+            ..Default::default()
+        })
+        .collect();
+    let cluster_role_bindings = match ns {
+        Some(_) => vec![],
+        None => {
+            Api::<ClusterRoleBinding>::all(client.clone())
+                .list(&Default::default())
+                .await
+                .map_err(|e| format!("Failed to list clusterrolebindings: {}", e))?
+                .items
+        }
+    };
+    let input = ReferenceInput {
+        pods: list_namespaced_or_all(&client, ns).await?,
+        deployments: list_namespaced_or_all(&client, ns).await?,
+        stateful_sets: list_namespaced_or_all(&client, ns).await?,
+        daemon_sets: list_namespaced_or_all(&client, ns).await?,
+        replica_sets: list_namespaced_or_all(&client, ns).await?,
+        jobs: list_namespaced_or_all(&client, ns).await?,
+        cron_jobs: list_namespaced_or_all(&client, ns).await?,
+        config_maps: list_names_only(&client, ns).await?,
+        secrets: list_names_only(&client, ns).await?,
+        claims: list_namespaced_or_all(&client, ns).await?,
+        service_accounts: list_namespaced_or_all(&client, ns).await?,
+        services: list_namespaced_or_all(&client, ns).await?,
+        ingresses: list_namespaced_or_all(&client, ns).await?,
+        roles: list_namespaced_or_all(&client, ns).await?,
+        cluster_roles,
+        role_bindings: list_namespaced_or_all(&client, ns).await?,
+        cluster_role_bindings,
+        hpas: list_namespaced_or_all(&client, ns).await?,
+        pdbs: list_namespaced_or_all(&client, ns).await?,
+    };
+    Ok(find_broken_references(&input))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn from_json<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> T {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn named<T: serde::de::DeserializeOwned>(name: &str) -> T {
+        from_json(json!({"metadata": {"name": name, "namespace": "prod"}}))
+    }
+
+    fn summary(broken: &[BrokenReference]) -> Vec<(String, String, String)> {
+        broken
+            .iter()
+            .map(|b| {
+                (
+                    format!("{}/{}", b.source.kind, b.source.name),
+                    b.field_path.clone(),
+                    format!("{}/{}", b.target.kind, b.target.name),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_workload_template_references() {
+        let deployment: Deployment = from_json(json!({
+            "metadata": {"name": "api", "namespace": "prod"},
+            "spec": {
+                "selector": {"matchLabels": {"app": "api"}},
+                "template": {"spec": {
+                    "serviceAccountName": "api",
+                    "containers": [{"name": "api", "envFrom": [
+                        {"configMapRef": {"name": "api-config"}},
+                        {"secretRef": {"name": "api-extra", "optional": true}}
+                    ]}],
+                    "volumes": [{"name": "data", "persistentVolumeClaim": {"claimName": "api-data"}}],
+                    "imagePullSecrets": [{"name": "registry"}]
+                }}
+            }
+        }));
+        // Pods created by the Deployment are covered by its template
+        let owned_pod: Pod = from_json(json!({
+            "metadata": {"name": "api-1", "namespace": "prod", "ownerReferences": [
+                {"apiVersion": "apps/v1", "kind": "ReplicaSet", "name": "api-abc", "uid": "1"}
+            ]},
+            "spec": {"containers": [{"name": "api", "envFrom": [{"configMapRef": {"name": "api-config"}}]}]}
+        }));
+        let input = ReferenceInput {
+            deployments: vec![deployment],
+            pods: vec![owned_pod],
+            claims: vec![named("api-data")],
+            service_accounts: vec![named("api")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            summary(&find_broken_references(&input)),
+            vec![
+                (
+                    "Deployment/api".to_string(),
+                    "spec.template.spec.containers[0].envFrom[0].configMapRef.name".to_string(),
+                    "ConfigMap/api-config".to_string()
+                ),
+                (
+                    "Deployment/api".to_string(),
+                    "spec.template.spec.imagePullSecrets[0].name".to_string(),
+                    "Secret/registry".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ingress_backends() {
+        let service: Service = from_json(json!({
+            "metadata": {"name": "web", "namespace": "prod"},
+            "spec": {"ports": [{"name": "http", "port": 80}]}
+        }));
+        let ingress: Ingress = from_json(json!({
+            "metadata": {"name": "web", "namespace": "prod"},
+            "spec": {"rules": [{"http": {"paths": [
+                {"path": "/", "pathType": "Prefix", "backend": {"service": {"name": "web", "port": {"name": "http"}}}},
+                {"path": "/admin", "pathType": "Prefix", "backend": {"service": {"name": "web", "port": {"number": 8080}}}},
+                {"path": "/old", "pathType": "Prefix", "backend": {"service": {"name": "legacy", "port": {"number": 80}}}}
+            ]}}]}
+        }));
+        let input = ReferenceInput {
+            services: vec![service],
+            ingresses: vec![ingress],
+            ..Default::default()
+        };
+        let broken = find_broken_references(&input);
+
+        assert_eq!(broken.len(), 2);
+        assert_eq!(
+            broken[0].field_path,
+            "spec.rules[0].http.paths[1].backend.service.port.number"
+        );
+        assert_eq!(broken[0].reason, "Service has no port 8080");
+        assert_eq!(
+            broken[1].field_path,
+            "spec.rules[0].http.paths[2].backend.service.name"
+        );
+        assert_eq!(broken[1].target.name, "legacy");
+    }
+
+    #[test]
+    fn test_role_bindings_hpas_and_pdbs() {
+        let binding = |name: &str, kind: &str, role: &str| -> RoleBinding {
+            from_json(json!({
+                "metadata": {"name": name, "namespace": "prod"},
+                "roleRef": {"apiGroup": "rbac.authorization.k8s.io", "kind": kind, "name": role}
+            }))
+        };
+        let hpa: HorizontalPodAutoscaler = from_json(json!({
+            "metadata": {"name": "worker", "namespace": "prod"},
+            "spec": {"maxReplicas": 3, "scaleTargetRef": {"kind": "Deployment", "name": "worker"}}
+        }));
+        let pdb: PodDisruptionBudget = from_json(json!({
+            "metadata": {"name": "db", "namespace": "prod"},
+            "spec": {"selector": {"matchLabels": {"app": "db"}}}
+        }));
+        let cluster_role: ClusterRole = from_json(json!({"metadata": {"name": "view"}}));
+        let input = ReferenceInput {
+            roles: vec![named("reader")],
+            cluster_roles: vec![cluster_role],
+            role_bindings: vec![
+                binding("readers", "Role", "reader"),
+                binding("viewers", "ClusterRole", "view"),
+                binding("writers", "Role", "writer"),
+            ],
+            hpas: vec![hpa],
+            pdbs: vec![pdb],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            summary(&find_broken_references(&input)),
+            vec![
+                (
+                    "HorizontalPodAutoscaler/worker".to_string(),
+                    "spec.scaleTargetRef.name".to_string(),
+                    "Deployment/worker".to_string()
+                ),
+                (
+                    "PodDisruptionBudget/db".to_string(),
+                    "spec.selector".to_string(),
+                    "Pod/app=db".to_string()
+                ),
+                (
+                    "RoleBinding/writers".to_string(),
+                    "roleRef.name".to_string(),
+                    "Role/writer".to_string()
+                ),
+            ]
+        );
+    }
+}
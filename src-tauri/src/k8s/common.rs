@@ -24,6 +24,15 @@ pub struct K8sEventInfo {
     pub source: String,
 }
 
+/// How urgent a diagnostic or lint finding is; orders most severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Critical,
+    Warning,
+    Info,
+}
+
 pub fn calculate_age(
     timestamp: Option<&k8s_openapi::apimachinery::pkg::apis::meta::v1::Time>,
 ) -> String {
@@ -60,3 +69,82 @@ pub fn get_created_at(
     }
     0
 }
+
+/// List a namespaced resource in one namespace, or across all namespaces when none is given
+pub(crate) async fn list_namespaced_or_all<K>(
+    client: &kube::Client,
+    namespace: Option<&str>,
+) -> Result<Vec<K>, String>
+where
+    K: kube::Resource<Scope = k8s_openapi::NamespaceResourceScope>
+        + Clone
+        + serde::de::DeserializeOwned
+        + std::fmt::Debug,
+    K::DynamicType: Default,
+{
+    let api: kube::Api<K> = match namespace {
+        Some(ns) => kube::Api::namespaced(client.clone(), ns),
+        None => kube::Api::all(client.clone()),
+    };
+    api.list(&Default::default())
+        .await
+        .map(|list| list.items)
+        .map_err(|e| format!("Failed to list {}: {}", K::plural(&Default::default()), e))
+}
+
+/// Evaluate a LabelSelector against a set of labels. An empty selector matches everything.
+pub fn label_selector_matches(
+    selector: &k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector,
+    labels: &std::collections::BTreeMap<String, String>,
+) -> bool {
+    let labels_match = selector
+        .match_labels
+        .as_ref()
+        .map(|wanted| wanted.iter().all(|(k, v)| labels.get(k) == Some(v)))
+        .unwrap_or(true);
+
+    let expressions_match = selector
+        .match_expressions
+        .as_ref()
+        .map(|exprs| {
+            exprs.iter().all(|expr| {
+                let values = expr.values.as_deref().unwrap_or_default();
+                let current = labels.get(&expr.key);
+                match expr.operator.as_str() {
+                    "In" => current.map(|v| values.contains(v)).unwrap_or(false),
+                    "NotIn" => current.map(|v| !values.contains(v)).unwrap_or(true),
+                    "Exists" => current.is_some(),
+                    "DoesNotExist" => current.is_none(),
+                    _ => false,
+                }
+            })
+        })
+        .unwrap_or(true);
+
+    labels_match && expressions_match
+}
+
+/// Render a LabelSelector in the `labelSelector` query syntax (e.g. "app=web,tier in (a,b)")
+pub fn label_selector_to_string(
+    selector: &k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector,
+) -> String {
+    let mut parts: Vec<String> = selector
+        .match_labels
+        .iter()
+        .flatten()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+
+    for expr in selector.match_expressions.iter().flatten() {
+        let values = expr.values.clone().unwrap_or_default().join(",");
+        parts.push(match expr.operator.as_str() {
+            "In" => format!("{} in ({})", expr.key, values),
+            "NotIn" => format!("{} notin ({})", expr.key, values),
+            "Exists" => expr.key.clone(),
+            "DoesNotExist" => format!("!{}", expr.key),
+            other => format!("{} {} ({})", expr.key, other, values),
+        });
+    }
+
+    parts.join(",")
+}
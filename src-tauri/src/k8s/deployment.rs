@@ -1,6 +1,7 @@
 use crate::cluster_manager::ClusterManagerState;
 use crate::k8s::client::create_client_for_cluster;
 use crate::k8s::common::{calculate_age, K8sEventInfo};
+use crate::k8s::event_watch::summarize_core_event;
 use k8s_openapi::api::apps::v1::{Deployment, ReplicaSet};
 use k8s_openapi::api::core::v1::{Event, Pod};
 use kube::api::{Api, ListParams};
@@ -398,23 +399,7 @@ pub fn filter_deployment_events(
 
             name_matches && kind_matches && uid_matches
         })
-        .map(|event| {
-            let source = event
-                .source
-                .as_ref()
-                .and_then(|s| s.component.clone())
-                .unwrap_or_else(|| "unknown".to_string());
-
-            K8sEventInfo {
-                event_type: event.type_.unwrap_or_else(|| "Normal".to_string()),
-                reason: event.reason.unwrap_or_default(),
-                message: event.message.unwrap_or_default(),
-                count: event.count.unwrap_or(1),
-                first_timestamp: event.first_timestamp.as_ref().map(|t| t.0.to_string()),
-                last_timestamp: event.last_timestamp.as_ref().map(|t| t.0.to_string()),
-                source,
-            }
-        })
+        .map(|event| K8sEventInfo::from(&summarize_core_event(event)))
         .collect();
 
     // Sort by last_timestamp descending (most recent first)
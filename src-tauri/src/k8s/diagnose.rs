@@ -0,0 +1,713 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::Severity;
+use crate::k8s::relationships::{pod_spec_references, SpecReference};
+use k8s_openapi::api::core::v1::{ConfigMap, ContainerStatus, Event, Node, Pod, PodSpec, Secret};
+use kube::api::ListParams;
+use kube::Api;
+use std::collections::HashSet;
+use tauri::State;
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub title: String,
+    pub container: Option<String>,
+    pub evidence: Vec<String>,
+    pub next_steps: Vec<String>,
+}
+
+impl Finding {
+    fn new(severity: Severity, title: impl Into<String>) -> Self {
+        Finding {
+            severity,
+            title: title.into(),
+            container: None,
+            evidence: vec![],
+            next_steps: vec![],
+        }
+    }
+
+    fn container(mut self, name: &str) -> Self {
+        self.container = Some(name.to_string());
+        self
+    }
+
+    fn evidence(mut self, line: impl Into<String>) -> Self {
+        let line = line.into();
+        if !line.is_empty() {
+            self.evidence.push(line);
+        }
+        self
+    }
+
+    fn step(mut self, step: impl Into<String>) -> Self {
+        self.next_steps.push(step.into());
+        self
+    }
+}
+
+/// What was fetched alongside the pod
+pub struct DiagnosisInput<'a> {
+    pub pod: &'a Pod,
+    pub events: &'a [Event],
+    pub node: Option<&'a Node>,
+    pub missing_refs: &'a [SpecReference],
+    /// References whose existence could not be checked, with the lookup error
+    pub unverified_refs: &'a [(SpecReference, String)],
+}
+
+fn events_with_reason<'a>(
+    events: &'a [Event],
+    reason: &'a str,
+) -> impl Iterator<Item = &'a Event> + 'a {
+    events
+        .iter()
+        .filter(move |e| e.reason.as_deref() == Some(reason))
+}
+
+fn event_line(event: &Event) -> String {
+    let count = event.count.unwrap_or(1);
+    let message = event.message.as_deref().unwrap_or_default();
+    if count > 1 {
+        format!("{} (x{})", message, count)
+    } else {
+        message.to_string()
+    }
+}
+
+fn check_scheduling(input: &DiagnosisInput, findings: &mut Vec<Finding>) {
+    let status = input.pod.status.as_ref();
+    let unscheduled = status.and_then(|s| s.conditions.as_ref()).and_then(|c| {
+        c.iter()
+            .find(|c| c.type_ == "PodScheduled" && c.status == "False")
+    });
+    let Some(condition) = unscheduled else {
+        return;
+    };
+
+    let mut finding = Finding::new(Severity::Critical, "Pod cannot be scheduled")
+        .evidence(condition.message.clone().unwrap_or_default());
+    for event in events_with_reason(input.events, "FailedScheduling").take(3) {
+        finding = finding.evidence(event_line(event));
+    }
+    findings.push(
+        finding
+            .step("Compare the pod's CPU/memory requests with free allocatable capacity on the nodes")
+            .step("Check node taints against the pod's tolerations, and its nodeSelector/affinity rules")
+            .step("For unbound PersistentVolumeClaims, check the StorageClass and volume zone"),
+    );
+}
+
+fn check_missing_refs(input: &DiagnosisInput, findings: &mut Vec<Finding>) {
+    let namespace = input.pod.metadata.namespace.as_deref().unwrap_or("default");
+    for r in input.missing_refs {
+        let severity = if r.optional {
+            Severity::Info
+        } else {
+            Severity::Critical
+        };
+        findings.push(
+            Finding::new(severity, format!("{} '{}' does not exist", r.kind, r.name))
+                .evidence(format!("Referenced by spec.{}", r.field_path))
+                .evidence(if r.optional {
+                    "The reference is optional, so the pod can still start".to_string()
+                } else {
+                    String::new()
+                })
+                .step(format!(
+                    "Create {} '{}' in namespace '{}', or fix the reference",
+                    r.kind, r.name, namespace
+                )),
+        );
+    }
+    for (r, error) in input.unverified_refs {
+        findings.push(
+            Finding::new(
+                Severity::Info,
+                format!("Could not verify {} '{}'", r.kind, r.name),
+            )
+            .evidence(format!("Referenced by spec.{}", r.field_path))
+            .evidence(error.clone())
+            .step(format!(
+                "Check that you can read {}s in namespace '{}'",
+                r.kind, namespace
+            )),
+        );
+    }
+}
+
+fn image_of(spec: Option<&PodSpec>, container: &str) -> Option<String> {
+    let spec = spec?;
+    spec.init_containers
+        .iter()
+        .flatten()
+        .chain(&spec.containers)
+        .find(|c| c.name == container)
+        .and_then(|c| c.image.clone())
+}
+
+fn memory_limit_of(spec: Option<&PodSpec>, container: &str) -> Option<String> {
+    spec?
+        .init_containers
+        .iter()
+        .flatten()
+        .chain(&spec?.containers)
+        .find(|c| c.name == container)?
+        .resources
+        .as_ref()?
+        .limits
+        .as_ref()?
+        .get("memory")
+        .map(|q| q.0.clone())
+}
+
+fn check_waiting(input: &DiagnosisInput, cs: &ContainerStatus, findings: &mut Vec<Finding>) {
+    let Some(waiting) = cs.state.as_ref().and_then(|s| s.waiting.as_ref()) else {
+        return;
+    };
+    let reason = waiting.reason.as_deref().unwrap_or_default();
+    let message = waiting.message.clone().unwrap_or_default();
+    let spec = input.pod.spec.as_ref();
+
+    let finding = match reason {
+        "ImagePullBackOff" | "ErrImagePull" | "InvalidImageName" | "ErrImageNeverPull" => {
+            let mut finding = Finding::new(
+                Severity::Critical,
+                format!("Image cannot be pulled ({})", reason),
+            )
+            .evidence(message)
+            .evidence(format!(
+                "Image: {}",
+                image_of(spec, &cs.name).unwrap_or_default()
+            ));
+            for event in events_with_reason(input.events, "Failed").take(2) {
+                finding = finding.evidence(event_line(event));
+            }
+            finding
+                .step("Check that the image name and tag exist in the registry")
+                .step("For private registries, check the pod's imagePullSecrets and their credentials")
+                .step("Check that the node can reach the registry")
+        }
+        "CreateContainerConfigError" | "CreateContainerError" => Finding::new(
+            Severity::Critical,
+            format!("Container cannot be created ({})", reason),
+        )
+        .evidence(message)
+        .step("Check that the referenced ConfigMaps, Secrets and keys exist")
+        .step("Check the container's command, volume mounts and securityContext"),
+        "CrashLoopBackOff" => {
+            let mut finding = Finding::new(
+                Severity::Critical,
+                "Container keeps crashing (CrashLoopBackOff)",
+            )
+            .evidence(format!("{} restarts", cs.restart_count))
+            .evidence(message);
+            if let Some(t) = cs.last_state.as_ref().and_then(|s| s.terminated.as_ref()) {
+                finding = finding.evidence(format!(
+                    "Last exit: code {} ({})",
+                    t.exit_code,
+                    t.reason.as_deref().unwrap_or("unknown reason")
+                ));
+            }
+            finding
+                .step(format!(
+                    "Read the previous logs: kubectl logs {} -c {} --previous",
+                    input.pod.metadata.name.as_deref().unwrap_or_default(),
+                    cs.name
+                ))
+                .step("Check the container's command, arguments and required environment")
+        }
+        "ContainerCreating" | "PodInitializing" => return,
+        _ => Finding::new(
+            Severity::Warning,
+            format!("Container is waiting ({})", reason),
+        )
+        .evidence(message)
+        .step("Check the pod's events for details"),
+    };
+    findings.push(finding.container(&cs.name));
+}
+
+fn exit_code_hint(code: i32) -> &'static str {
+    match code {
+        0 => "The process exited successfully; check whether it is meant to run continuously",
+        1 => "The application exited with an error; check its logs",
+        126 => "The command is not executable; check file permissions and the entrypoint",
+        127 => "The command was not found; check the image's entrypoint and the container command",
+        137 => {
+            "The process was killed (SIGKILL), often by a failing liveness probe or the OOM killer"
+        }
+        139 => "The process crashed with a segmentation fault",
+        143 => "The process was terminated (SIGTERM); check graceful shutdown and preStop hooks",
+        _ => "Check the container's logs for the cause of the exit",
+    }
+}
+
+fn check_last_termination(
+    input: &DiagnosisInput,
+    cs: &ContainerStatus,
+    findings: &mut Vec<Finding>,
+) {
+    let Some(t) = cs.last_state.as_ref().and_then(|s| s.terminated.as_ref()) else {
+        return;
+    };
+    let reason = t.reason.as_deref().unwrap_or("Unknown");
+    if reason == "Completed" && t.exit_code == 0 {
+        return;
+    }
+    let spec = input.pod.spec.as_ref();
+
+    let finding = if reason == "OOMKilled" {
+        Finding::new(
+            Severity::Critical,
+            "Container was killed for exceeding its memory limit (OOMKilled)",
+        )
+        .evidence(format!(
+            "Memory limit: {}",
+            memory_limit_of(spec, &cs.name)
+                .unwrap_or_else(|| "none (node memory ran out)".to_string())
+        ))
+        .evidence(format!("{} restarts", cs.restart_count))
+        .step("Raise the container's memory limit, or reduce the application's memory usage")
+        .step("Compare actual usage with the limit, e.g. with kubectl top pod")
+    } else {
+        Finding::new(
+            Severity::Warning,
+            format!(
+                "Container last exited with code {} ({})",
+                t.exit_code, reason
+            ),
+        )
+        .evidence(t.message.clone().unwrap_or_default())
+        .evidence(format!("{} restarts", cs.restart_count))
+        .step(exit_code_hint(t.exit_code))
+    };
+    findings.push(finding.container(&cs.name));
+}
+
+fn check_probes(input: &DiagnosisInput, findings: &mut Vec<Finding>) {
+    for (kind, severity) in [
+        ("Liveness", Severity::Critical),
+        ("Startup", Severity::Critical),
+        ("Readiness", Severity::Warning),
+    ] {
+        let prefix = format!("{} probe failed", kind);
+        let failures: Vec<&Event> = events_with_reason(input.events, "Unhealthy")
+            .filter(|e| e.message.as_deref().is_some_and(|m| m.starts_with(&prefix)))
+            .collect();
+        if failures.is_empty() {
+            continue;
+        }
+        let mut finding = Finding::new(severity, format!("{} probe is failing", kind));
+        for event in failures.iter().take(3) {
+            finding = finding.evidence(event_line(event));
+        }
+        let finding = if kind == "Readiness" {
+            finding
+                .step("The pod is removed from Service endpoints while the probe fails")
+                .step("Check the probe's path/port and that dependencies the endpoint checks are reachable")
+        } else {
+            finding
+                .step("A failing liveness or startup probe restarts the container")
+                .step("Check the probe's path/port, and raise initialDelaySeconds/failureThreshold for slow starts")
+        };
+        findings.push(finding);
+    }
+}
+
+fn check_node(input: &DiagnosisInput, findings: &mut Vec<Finding>) {
+    let Some(node) = input.node else {
+        return;
+    };
+    let node_name = node.metadata.name.clone().unwrap_or_default();
+    let conditions = node
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .into_iter()
+        .flatten();
+
+    for condition in conditions {
+        let message = condition.message.clone().unwrap_or_default();
+        match (condition.type_.as_str(), condition.status.as_str()) {
+            ("Ready", status) if status != "True" => findings.push(
+                Finding::new(
+                    Severity::Critical,
+                    format!("Node {} is not ready", node_name),
+                )
+                .evidence(message)
+                .step("Check the node's kubelet and container runtime")
+                .step("Pods on a NotReady node are evicted after the toleration timeout"),
+            ),
+            ("MemoryPressure" | "DiskPressure" | "PIDPressure", "True") => findings.push(
+                Finding::new(
+                    Severity::Warning,
+                    format!("Node {} has {}", node_name, condition.type_),
+                )
+                .evidence(message)
+                .step("The kubelet may evict pods from this node; check its resource usage"),
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn check_pod_status(input: &DiagnosisInput, findings: &mut Vec<Finding>) {
+    let pod = input.pod;
+    if let Some(status) = &pod.status {
+        if status.reason.as_deref() == Some("Evicted") {
+            findings.push(
+                Finding::new(Severity::Critical, "Pod was evicted")
+                    .evidence(status.message.clone().unwrap_or_default())
+                    .step("Check the node's pressure conditions and the pod's resource requests"),
+            );
+        }
+    }
+    if pod.metadata.deletion_timestamp.is_some() {
+        let finalizers = pod.metadata.finalizers.clone().unwrap_or_default();
+        findings.push(
+            Finding::new(Severity::Warning, "Pod is terminating")
+                .evidence(if finalizers.is_empty() {
+                    String::new()
+                } else {
+                    format!("Finalizers: {}", finalizers.join(", "))
+                })
+                .step("If it stays terminating, check its finalizers and whether the node is reachable"),
+        );
+    }
+}
+
+/// Analyse a pod and return findings, most severe first
+pub fn diagnose(input: &DiagnosisInput) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    check_pod_status(input, &mut findings);
+    check_scheduling(input, &mut findings);
+    check_missing_refs(input, &mut findings);
+
+    if let Some(status) = &input.pod.status {
+        let statuses = status
+            .init_container_statuses
+            .iter()
+            .flatten()
+            .chain(status.container_statuses.iter().flatten());
+        for cs in statuses {
+            check_waiting(input, cs, &mut findings);
+            check_last_termination(input, cs, &mut findings);
+        }
+    }
+    check_probes(input, &mut findings);
+    check_node(input, &mut findings);
+
+    if findings.is_empty() {
+        findings.push(Finding::new(Severity::Info, "No problems detected"));
+    }
+    // Stable, so checks keep their root-cause-first order within a severity
+    findings.sort_by_key(|f| f.severity);
+    findings
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PodDiagnosis {
+    pub name: String,
+    pub namespace: String,
+    pub phase: String,
+    pub node: Option<String>,
+    pub findings: Vec<Finding>,
+}
+
+/// References a pod has to ConfigMaps and Secrets, split by lookup outcome
+#[derive(Default)]
+struct ConfigRefCheck {
+    missing: Vec<SpecReference>,
+    /// Lookups that failed (e.g. no permission to read Secrets), with the error
+    unverified: Vec<(SpecReference, String)>,
+}
+
+/// Find the ConfigMaps and Secrets a pod references that do not exist.
+/// A failed lookup does not fail the diagnosis; the reference is reported as unverified.
+async fn check_config_refs(
+    client: kube::Client,
+    namespace: &str,
+    refs: Vec<SpecReference>,
+) -> ConfigRefCheck {
+    let config_maps: Api<ConfigMap> = Api::namespaced(client.clone(), namespace);
+    let secrets: Api<Secret> = Api::namespaced(client, namespace);
+    let mut check = ConfigRefCheck::default();
+    let mut checked = HashSet::new();
+
+    let config_refs = refs
+        .into_iter()
+        .filter(|r| r.kind == "ConfigMap" || r.kind == "Secret");
+    for r in config_refs {
+        // A Secret shared by several containers or imagePullSecrets is looked up once
+        if !checked.insert((r.kind.clone(), r.name.clone())) {
+            continue;
+        }
+        let exists = if r.kind == "ConfigMap" {
+            config_maps
+                .get_metadata_opt(&r.name)
+                .await
+                .map(|o| o.is_some())
+        } else {
+            secrets.get_metadata_opt(&r.name).await.map(|o| o.is_some())
+        };
+        match exists {
+            Ok(true) => {}
+            Ok(false) => check.missing.push(r),
+            Err(e) => {
+                let error = format!("Failed to get {} {}: {}", r.kind, r.name, e);
+                check.unverified.push((r, error));
+            }
+        }
+    }
+    check
+}
+
+#[tauri::command]
+pub async fn cluster_diagnose_pod(
+    cluster_id: String,
+    namespace: String,
+    pod_name: String,
+    state: State<'_, ClusterManagerState>,
+) -> Result<PodDiagnosis, String> {
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let pods: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+    let pod = pods
+        .get(&pod_name)
+        .await
+        .map_err(|e| format!("Failed to get pod: {}", e))?;
+
+    let events_api: Api<Event> = Api::namespaced(client.clone(), &namespace);
+    let lp = ListParams::default().fields(&format!(
+        "involvedObject.kind=Pod,involvedObject.name={}",
+        pod_name
+    ));
+    let mut events = events_api
+        .list(&lp)
+        .await
+        .map_err(|e| format!("Failed to list events: {}", e))?
+        .items;
+    // Most recent first so evidence shows the latest occurrences
+    events.sort_by(|a, b| b.last_timestamp.cmp(&a.last_timestamp));
+
+    let node_name = pod.spec.as_ref().and_then(|s| s.node_name.clone());
+    let node = match &node_name {
+        Some(name) => Api::<Node>::all(client.clone())
+            .get_opt(name)
+            .await
+            .map_err(|e| format!("Failed to get node: {}", e))?,
+        None => None,
+    };
+
+    let refs = pod
+        .spec
+        .as_ref()
+        .map(pod_spec_references)
+        .unwrap_or_default();
+    let ref_check = check_config_refs(client, &namespace, refs).await;
+
+    let findings = diagnose(&DiagnosisInput {
+        pod: &pod,
+        events: &events,
+        node: node.as_ref(),
+        missing_refs: &ref_check.missing,
+        unverified_refs: &ref_check.unverified,
+    });
+
+    Ok(PodDiagnosis {
+        name: pod_name,
+        namespace,
+        phase: pod
+            .status
+            .as_ref()
+            .and_then(|s| s.phase.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        node: node_name,
+        findings,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pod(status: serde_json::Value) -> Pod {
+        serde_json::from_value(json!({
+            "metadata": {"name": "api-0", "namespace": "prod"},
+            "spec": {
+                "containers": [{
+                    "name": "api",
+                    "image": "registry.example.com/api:1.2",
+                    "resources": {"limits": {"memory": "256Mi"}},
+                    "envFrom": [{"secretRef": {"name": "api-env"}}],
+                    "env": [{"name": "FLAG", "valueFrom": {"configMapKeyRef": {"name": "flags", "key": "x", "optional": true}}}]
+                }],
+                "volumes": [{"name": "cfg", "configMap": {"name": "api-config"}}],
+                "imagePullSecrets": [{"name": "registry"}]
+            },
+            "status": status
+        }))
+        .unwrap()
+    }
+
+    fn event(reason: &str, message: &str) -> Event {
+        serde_json::from_value(json!({
+            "metadata": {"name": "e"},
+            "involvedObject": {"kind": "Pod", "name": "api-0"},
+            "type": "Warning",
+            "reason": reason,
+            "message": message,
+            "count": 5
+        }))
+        .unwrap()
+    }
+
+    fn diagnose_pod(
+        pod: &Pod,
+        events: &[Event],
+        node: Option<&Node>,
+        missing: &[SpecReference],
+    ) -> Vec<Finding> {
+        diagnose(&DiagnosisInput {
+            pod,
+            events,
+            node,
+            missing_refs: missing,
+            unverified_refs: &[],
+        })
+    }
+
+    #[test]
+    fn test_oom_crash_loop_ranks_critical_findings_first() {
+        let pod = pod(json!({
+            "phase": "Running",
+            "containerStatuses": [{
+                "name": "api", "image": "", "imageID": "", "ready": false, "restartCount": 6,
+                "state": {"waiting": {"reason": "CrashLoopBackOff", "message": "back-off 5m0s restarting failed container"}},
+                "lastState": {"terminated": {"exitCode": 137, "reason": "OOMKilled"}}
+            }]
+        }));
+        let events = [event(
+            "Unhealthy",
+            "Readiness probe failed: HTTP probe failed with statuscode: 503",
+        )];
+        let findings = diagnose_pod(&pod, &events, None, &[]);
+
+        let titles: Vec<&str> = findings.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Container keeps crashing (CrashLoopBackOff)",
+                "Container was killed for exceeding its memory limit (OOMKilled)",
+                "Readiness probe is failing",
+            ]
+        );
+        assert!(findings[1]
+            .evidence
+            .contains(&"Memory limit: 256Mi".to_string()));
+        assert!(findings[2].evidence[0].ends_with("(x5)"));
+    }
+
+    #[test]
+    fn test_unschedulable_pod_and_missing_refs() {
+        let pod = pod(json!({
+            "phase": "Pending",
+            "conditions": [{"type": "PodScheduled", "status": "False", "reason": "Unschedulable",
+                "message": "0/3 nodes are available: 3 Insufficient memory."}]
+        }));
+        let missing = [
+            SpecReference {
+                kind: "ConfigMap".to_string(),
+                name: "flags".to_string(),
+                optional: true,
+                field_path: "containers[0].env[0].valueFrom.configMapKeyRef.name".to_string(),
+            },
+            SpecReference {
+                kind: "Secret".to_string(),
+                name: "api-env".to_string(),
+                optional: false,
+                field_path: "containers[0].envFrom[0].secretRef.name".to_string(),
+            },
+        ];
+        let findings = diagnose_pod(&pod, &[], None, &missing);
+
+        assert_eq!(findings[0].title, "Pod cannot be scheduled");
+        assert_eq!(
+            findings[0].evidence[0],
+            "0/3 nodes are available: 3 Insufficient memory."
+        );
+        assert_eq!(findings[1].title, "Secret 'api-env' does not exist");
+        assert_eq!(findings[1].severity, Severity::Critical);
+        assert_eq!(
+            findings[1].evidence[0],
+            "Referenced by spec.containers[0].envFrom[0].secretRef.name"
+        );
+        assert_eq!(findings[2].title, "ConfigMap 'flags' does not exist");
+        assert_eq!(findings[2].severity, Severity::Info);
+    }
+
+    #[test]
+    fn test_unverifiable_ref_is_info_finding() {
+        let pod = pod(json!({"phase": "Running"}));
+        let unverified = [(
+            SpecReference {
+                kind: "Secret".to_string(),
+                name: "api-env".to_string(),
+                optional: false,
+                field_path: "containers[0].envFrom[0].secretRef.name".to_string(),
+            },
+            "Failed to get Secret api-env: forbidden".to_string(),
+        )];
+        let findings = diagnose(&DiagnosisInput {
+            pod: &pod,
+            events: &[],
+            node: None,
+            missing_refs: &[],
+            unverified_refs: &unverified,
+        });
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].title, "Could not verify Secret 'api-env'");
+        assert_eq!(findings[0].severity, Severity::Info);
+        assert_eq!(
+            findings[0].evidence[1],
+            "Failed to get Secret api-env: forbidden"
+        );
+    }
+
+    #[test]
+    fn test_image_pull_and_node_pressure() {
+        let pod = pod(json!({
+            "phase": "Pending",
+            "containerStatuses": [{
+                "name": "api", "image": "", "imageID": "", "ready": false, "restartCount": 0,
+                "state": {"waiting": {"reason": "ImagePullBackOff", "message": "Back-off pulling image"}}
+            }]
+        }));
+        let node: Node = serde_json::from_value(json!({
+            "metadata": {"name": "node-1"},
+            "status": {"conditions": [
+                {"type": "Ready", "status": "True"},
+                {"type": "DiskPressure", "status": "True", "message": "ephemeral storage low"}
+            ]}
+        }))
+        .unwrap();
+        let findings = diagnose_pod(&pod, &[], Some(&node), &[]);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            findings[0].title,
+            "Image cannot be pulled (ImagePullBackOff)"
+        );
+        assert!(findings[0]
+            .evidence
+            .contains(&"Image: registry.example.com/api:1.2".to_string()));
+        assert_eq!(findings[1].title, "Node node-1 has DiskPressure");
+
+        let healthy = diagnose_pod(&self::pod(json!({"phase": "Running"})), &[], None, &[]);
+        assert_eq!(healthy[0].title, "No problems detected");
+    }
+}
@@ -0,0 +1,110 @@
+use crate::k8s::common::{calculate_age, get_created_at, WorkloadSummary};
+use kube::api::{Api, DynamicObject};
+use kube::core::GroupVersionKind;
+use kube::discovery::{self, ApiCapabilities, ApiResource, Discovery, Scope};
+use kube::Client;
+
+/// Split an apiVersion ("apps/v1", "v1") into (group, version)
+pub fn split_api_version(api_version: &str) -> (String, String) {
+    match api_version.split_once('/') {
+        Some((group, version)) => (group.to_string(), version.to_string()),
+        None => (String::new(), api_version.to_string()),
+    }
+}
+
+/// Resolve a kind to its API resource. With an apiVersion the lookup is pinned;
+/// without one, discovery is run and the kind is matched by kind or plural name
+/// (case-insensitive), preferring the core group.
+pub async fn resolve_api_resource(
+    client: &Client,
+    api_version: Option<&str>,
+    kind: &str,
+) -> Result<(ApiResource, ApiCapabilities), String> {
+    if let Some(api_version) = api_version.filter(|v| !v.is_empty()) {
+        let (group, version) = split_api_version(api_version);
+        let gvk = GroupVersionKind::gvk(&group, &version, kind);
+        return discovery::pinned_kind(client, &gvk)
+            .await
+            .map_err(|e| format!("Failed to resolve {} {}: {}", api_version, kind, e));
+    }
+
+    let discovery = Discovery::new(client.clone())
+        .run()
+        .await
+        .map_err(|e| format!("Failed to run API discovery: {}", e))?;
+
+    let mut matches: Vec<(ApiResource, ApiCapabilities)> = discovery
+        .groups()
+        .flat_map(|group| group.recommended_resources())
+        .filter(|(ar, _)| {
+            ar.kind.eq_ignore_ascii_case(kind) || ar.plural.eq_ignore_ascii_case(kind)
+        })
+        .collect();
+    matches.sort_by_key(|(ar, _)| !ar.group.is_empty());
+
+    matches
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Unknown resource kind '{}'", kind))
+}
+
+/// Build a dynamic Api for a resolved resource. Namespaced resources without a
+/// namespace are addressed across all namespaces.
+pub fn dynamic_api(
+    client: Client,
+    resource: &ApiResource,
+    capabilities: &ApiCapabilities,
+    namespace: Option<&str>,
+) -> Api<DynamicObject> {
+    match (&capabilities.scope, namespace.filter(|ns| !ns.is_empty())) {
+        (Scope::Namespaced, Some(ns)) => Api::namespaced_with(client, ns, resource),
+        _ => Api::all_with(client, resource),
+    }
+}
+
+/// Summarize any object for list views. Status comes from `status.phase` or the
+/// `Ready` condition; images from the pod spec or pod template when present.
+pub fn map_dynamic_to_summary(obj: DynamicObject) -> WorkloadSummary {
+    let meta = obj.metadata;
+    let status = &obj.data["status"];
+
+    let status_str = if let Some(phase) = status["phase"].as_str() {
+        phase.to_string()
+    } else if let Some(ready) = status["conditions"].as_array().and_then(|conditions| {
+        conditions
+            .iter()
+            .find(|c| c["type"].as_str() == Some("Ready"))
+            .and_then(|c| c["status"].as_str())
+    }) {
+        if ready == "True" { "Ready" } else { "NotReady" }.to_string()
+    } else {
+        "-".to_string()
+    };
+
+    let spec = &obj.data["spec"];
+    let pod_spec = if spec["containers"].is_array() {
+        spec
+    } else {
+        &spec["template"]["spec"]
+    };
+    let images = pod_spec["containers"]
+        .as_array()
+        .map(|containers| {
+            containers
+                .iter()
+                .filter_map(|c| c["image"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    WorkloadSummary {
+        id: meta.uid.clone().unwrap_or_default(),
+        name: meta.name.clone().unwrap_or_default(),
+        namespace: meta.namespace.clone().unwrap_or_default(),
+        age: calculate_age(meta.creation_timestamp.as_ref()),
+        created_at: get_created_at(meta.creation_timestamp.as_ref()),
+        labels: meta.labels.unwrap_or_default(),
+        status: status_str,
+        images,
+    }
+}
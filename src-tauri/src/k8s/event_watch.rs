@@ -0,0 +1,354 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::{get_created_at, K8sEventInfo};
+use crate::k8s::metrics::{format_event_age, parse_rfc3339_ts, ClusterEventSummary};
+use crate::k8s::resource_list::map_watch_event;
+use crate::k8s::resource_watch::{WatchDelta, WatchTracker};
+use crate::k8s::tasks::{BackgroundTaskState, TaskInfo};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use k8s_openapi::api::core::v1::{Event as CoreEvent, ObjectReference};
+use k8s_openapi::api::events::v1::Event as EventV1;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::{Api, Client};
+use std::fmt::Debug;
+use tauri::{Emitter, State, Window};
+
+const EVENTS_V1: &str = "events.k8s.io/v1";
+
+type EventStream = BoxStream<'static, Result<watcher::Event<ClusterEventSummary>, String>>;
+
+/// The fields both event APIs share, before they are shaped into a summary
+struct EventFields {
+    meta: ObjectMeta,
+    event_type: Option<String>,
+    reason: Option<String>,
+    message: Option<String>,
+    regarding: ObjectReference,
+    source: Option<String>,
+    first_timestamp: Option<String>,
+    last_timestamp: Option<String>,
+    count: Option<i32>,
+}
+
+impl From<EventFields> for ClusterEventSummary {
+    fn from(fields: EventFields) -> Self {
+        let meta = fields.meta;
+        let name = meta.name.clone().unwrap_or_default();
+        let event_type = fields.event_type.unwrap_or_else(|| "Normal".to_string());
+        let reason = fields.reason.unwrap_or_default();
+        let regarding = fields.regarding;
+
+        ClusterEventSummary {
+            id: meta.uid.clone().unwrap_or_else(|| name.clone()),
+            name,
+            namespace: meta
+                .namespace
+                .clone()
+                .or(regarding.namespace)
+                .unwrap_or_else(|| "-".to_string()),
+            age: format_event_age(
+                fields
+                    .last_timestamp
+                    .as_deref()
+                    .or(fields.first_timestamp.as_deref()),
+            ),
+            labels: meta.labels.unwrap_or_default(),
+            status: format!("{}: {}", event_type, reason),
+            images: vec![],
+            created_at: get_created_at(meta.creation_timestamp.as_ref()),
+            event_type,
+            reason,
+            message: fields.message.unwrap_or_default(),
+            object: format!(
+                "{}/{}",
+                regarding.kind.unwrap_or_default(),
+                regarding.name.unwrap_or_default()
+            ),
+            count: fields.count.unwrap_or(1),
+            source: fields.source.unwrap_or_else(|| "unknown".to_string()),
+            first_timestamp: fields.first_timestamp,
+            last_timestamp: fields.last_timestamp,
+        }
+    }
+}
+
+impl From<&ClusterEventSummary> for K8sEventInfo {
+    fn from(summary: &ClusterEventSummary) -> Self {
+        K8sEventInfo {
+            event_type: summary.event_type.clone(),
+            reason: summary.reason.clone(),
+            message: summary.message.clone(),
+            count: summary.count,
+            first_timestamp: summary.first_timestamp.clone(),
+            last_timestamp: summary.last_timestamp.clone(),
+            source: summary.source.clone(),
+        }
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
+
+/// Normalize a `core/v1` Event. Newer clusters fill `eventTime`/`series` even here.
+pub fn summarize_core_event(event: CoreEvent) -> ClusterEventSummary {
+    let event_time = event.event_time.as_ref().map(|t| t.0.to_string());
+    let series_last = event
+        .series
+        .as_ref()
+        .and_then(|s| s.last_observed_time.as_ref())
+        .map(|t| t.0.to_string());
+
+    EventFields {
+        meta: event.metadata,
+        event_type: event.type_,
+        reason: event.reason,
+        message: event.message,
+        regarding: event.involved_object,
+        source: non_empty(event.reporting_component)
+            .or_else(|| non_empty(event.source.and_then(|s| s.component))),
+        first_timestamp: event
+            .first_timestamp
+            .map(|t| t.0.to_string())
+            .or(event_time.clone()),
+        last_timestamp: series_last
+            .or(event.last_timestamp.map(|t| t.0.to_string()))
+            .or(event_time),
+        count: event.series.and_then(|s| s.count).or(event.count),
+    }
+    .into()
+}
+
+/// Normalize an `events.k8s.io/v1` Event, where repeats are folded into `series`
+pub fn summarize_event_v1(event: EventV1) -> ClusterEventSummary {
+    let event_time = event.event_time.as_ref().map(|t| t.0.to_string());
+
+    EventFields {
+        meta: event.metadata,
+        event_type: event.type_,
+        reason: event.reason,
+        message: event.note,
+        regarding: event.regarding.unwrap_or_default(),
+        source: non_empty(event.reporting_controller)
+            .or_else(|| non_empty(event.deprecated_source.and_then(|s| s.component))),
+        first_timestamp: event
+            .deprecated_first_timestamp
+            .map(|t| t.0.to_string())
+            .or(event_time.clone()),
+        last_timestamp: event
+            .series
+            .as_ref()
+            .map(|s| s.last_observed_time.0.to_string())
+            .or(event.deprecated_last_timestamp.map(|t| t.0.to_string()))
+            .or(event_time),
+        count: event.series.map(|s| s.count).or(event.deprecated_count),
+    }
+    .into()
+}
+
+/// Unix time of the latest occurrence, falling back to when the event was created
+pub fn event_last_seen(summary: &ClusterEventSummary) -> i64 {
+    summary
+        .last_timestamp
+        .as_deref()
+        .and_then(parse_rfc3339_ts)
+        .map(|ts| ts.timestamp())
+        .unwrap_or(summary.created_at)
+}
+
+fn is_shown(event: &watcher::Event<ClusterEventSummary>, include_normal: bool) -> bool {
+    match event {
+        watcher::Event::InitApply(e) | watcher::Event::Apply(e) | watcher::Event::Delete(e) => {
+            include_normal || e.event_type == "Warning"
+        }
+        watcher::Event::Init | watcher::Event::InitDone => true,
+    }
+}
+
+fn summary_stream<K>(api: Api<K>, map: fn(K) -> ClusterEventSummary) -> EventStream
+where
+    K: kube::Resource + Clone + serde::de::DeserializeOwned + Debug + Send + Sync + 'static,
+{
+    watcher(api, watcher::Config::default())
+        .default_backoff()
+        .map_ok(move |event| map_watch_event(event, map))
+        .map_err(|e| e.to_string())
+        .boxed()
+}
+
+/// Watch `events.k8s.io/v1` when the cluster serves it, else `core/v1`
+async fn event_stream(client: Client, namespace: Option<&str>) -> (EventStream, &'static str) {
+    if client.list_api_group_resources(EVENTS_V1).await.is_ok() {
+        let api: Api<EventV1> = match namespace {
+            Some(ns) => Api::namespaced(client, ns),
+            None => Api::all(client),
+        };
+        return (summary_stream(api, summarize_event_v1), EVENTS_V1);
+    }
+    let api: Api<CoreEvent> = match namespace {
+        Some(ns) => Api::namespaced(client, ns),
+        None => Api::all(client),
+    };
+    (summary_stream(api, summarize_core_event), "v1")
+}
+
+fn event_key(summary: &ClusterEventSummary) -> String {
+    summary.id.clone()
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct EventWatchParams {
+    pub cluster_id: String,
+    pub namespace: Option<String>, // None watches all namespaces
+    pub include_normal: Option<bool>,
+    pub watch_id: String,
+}
+
+/// Stream events as `WatchDelta<ClusterEventSummary>` on `event_watch_{watch_id}`.
+/// Snapshots are sorted most recently seen first. Returns the API version used.
+#[tauri::command]
+pub async fn cluster_watch_events(
+    params: EventWatchParams,
+    window: Window,
+    state: State<'_, ClusterManagerState>,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<String, String> {
+    let client = create_client_for_cluster(&params.cluster_id, &state).await?;
+    let namespace = params.namespace.as_deref().filter(|ns| !ns.is_empty());
+    let (mut stream, api_version) = event_stream(client, namespace).await;
+    let include_normal = params.include_normal.unwrap_or(true);
+
+    let info = TaskInfo::new(
+        format!("event_watch:{}", params.watch_id),
+        "event_watch",
+        Some(&params.cluster_id),
+        &window,
+        format!(
+            "{} events in {}",
+            api_version,
+            namespace.unwrap_or("all namespaces")
+        ),
+    );
+    let event_name = format!("event_watch_{}", params.watch_id);
+
+    tasks.spawn(info, async move {
+        let mut tracker = WatchTracker::new(event_key);
+
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(event) => {
+                    if !is_shown(&event, include_normal) {
+                        continue;
+                    }
+                    let Some(mut delta) = tracker.apply(event) else {
+                        continue;
+                    };
+                    if let WatchDelta::Synced(items) = &mut delta {
+                        items.sort_by_key(|e| std::cmp::Reverse(event_last_seen(e)));
+                    }
+                    if let Err(e) = window.emit(&event_name, delta) {
+                        println!("Failed to emit event: {}", e);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    println!("Event watch error: {}", e);
+                }
+            }
+        }
+    })?;
+    Ok(api_version.to_string())
+}
+
+#[tauri::command]
+pub async fn cluster_stop_event_watch(
+    watch_id: String,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    tasks.stop(&format!("event_watch:{}", watch_id))?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn v1_event() -> EventV1 {
+        serde_json::from_value(json!({
+            "metadata": {"name": "api.17a", "namespace": "prod", "uid": "e1"},
+            "eventTime": "2026-01-01T10:00:00.000000Z",
+            "type": "Warning",
+            "reason": "BackOff",
+            "note": "Back-off restarting failed container",
+            "regarding": {"kind": "Pod", "name": "api-0", "namespace": "prod"},
+            "reportingController": "kubelet",
+            "series": {"count": 7, "lastObservedTime": "2026-01-01T10:30:00.000000Z"}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_summarize_event_v1_uses_series() {
+        let summary = summarize_event_v1(v1_event());
+        assert_eq!(summary.id, "e1");
+        assert_eq!(summary.object, "Pod/api-0");
+        assert_eq!(summary.message, "Back-off restarting failed container");
+        assert_eq!(summary.source, "kubelet");
+        assert_eq!(summary.count, 7);
+        assert_eq!(summary.status, "Warning: BackOff");
+        assert!(summary
+            .first_timestamp
+            .unwrap()
+            .starts_with("2026-01-01T10:00"));
+        assert!(summary
+            .last_timestamp
+            .unwrap()
+            .starts_with("2026-01-01T10:30"));
+    }
+
+    #[test]
+    fn test_summarize_core_event_falls_back_to_legacy_fields() {
+        let event: CoreEvent = serde_json::from_value(json!({
+            "metadata": {"name": "db.1", "namespace": "prod"},
+            "involvedObject": {"kind": "StatefulSet", "name": "db"},
+            "reason": "SuccessfulCreate",
+            "message": "create Pod db-0",
+            "source": {"component": "statefulset-controller"},
+            "count": 3,
+            "firstTimestamp": "2026-01-01T09:00:00Z",
+            "lastTimestamp": "2026-01-01T09:05:00Z"
+        }))
+        .unwrap();
+        let summary = summarize_core_event(event);
+        assert_eq!(summary.id, "db.1");
+        assert_eq!(summary.event_type, "Normal");
+        assert_eq!(summary.source, "statefulset-controller");
+        assert_eq!(summary.count, 3);
+        assert_eq!(
+            event_last_seen(&summary),
+            parse_rfc3339_ts("2026-01-01T09:05:00Z")
+                .unwrap()
+                .timestamp()
+        );
+
+        let info = K8sEventInfo::from(&summary);
+        assert_eq!(info.reason, "SuccessfulCreate");
+        assert_eq!(info.source, "statefulset-controller");
+    }
+
+    #[test]
+    fn test_is_shown_filters_normal_events() {
+        let warning = summarize_event_v1(v1_event());
+        let mut normal = warning.clone();
+        normal.event_type = "Normal".to_string();
+
+        assert!(is_shown(&watcher::Event::Apply(warning), false));
+        assert!(!is_shown(&watcher::Event::Apply(normal.clone()), false));
+        assert!(is_shown(&watcher::Event::Apply(normal), true));
+        assert!(is_shown(&watcher::Event::InitDone, false));
+    }
+}
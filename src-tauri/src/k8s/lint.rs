@@ -0,0 +1,693 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::{label_selector_matches, list_namespaced_or_all, Severity};
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, StatefulSet};
+use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
+use k8s_openapi::api::batch::v1::CronJob;
+use k8s_openapi::api::core::v1::{Container, Endpoints, Pod, PodSpec, Service};
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use std::collections::BTreeMap;
+use tauri::State;
+
+/// A best-practice check that can be switched off individually
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    MissingRequests,
+    MissingLimits,
+    MutableImageTag,
+    MissingProbes,
+    PrivilegedContainer,
+    RunsAsRoot,
+    SingleReplicaWithoutPdb,
+    HpaMissingTarget,
+    ServiceWithoutEndpoints,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 9] = [
+        LintRule::MissingRequests,
+        LintRule::MissingLimits,
+        LintRule::MutableImageTag,
+        LintRule::MissingProbes,
+        LintRule::PrivilegedContainer,
+        LintRule::RunsAsRoot,
+        LintRule::SingleReplicaWithoutPdb,
+        LintRule::HpaMissingTarget,
+        LintRule::ServiceWithoutEndpoints,
+    ];
+
+    pub fn severity(self) -> Severity {
+        match self {
+            LintRule::PrivilegedContainer | LintRule::HpaMissingTarget => Severity::Critical,
+            LintRule::MissingRequests
+            | LintRule::MissingLimits
+            | LintRule::MutableImageTag
+            | LintRule::MissingProbes
+            | LintRule::RunsAsRoot
+            | LintRule::ServiceWithoutEndpoints => Severity::Warning,
+            LintRule::SingleReplicaWithoutPdb => Severity::Info,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            LintRule::MissingRequests => "Containers without CPU or memory requests",
+            LintRule::MissingLimits => "Containers without a memory limit",
+            LintRule::MutableImageTag => "Images using :latest or no tag",
+            LintRule::MissingProbes => {
+                "Long-running containers without readiness or liveness probes"
+            }
+            LintRule::PrivilegedContainer => "Privileged containers",
+            LintRule::RunsAsRoot => "Containers that may run as root",
+            LintRule::SingleReplicaWithoutPdb => {
+                "Single-replica workloads without a PodDisruptionBudget"
+            }
+            LintRule::HpaMissingTarget => "HorizontalPodAutoscalers targeting a missing workload",
+            LintRule::ServiceWithoutEndpoints => "Services whose selector has no ready endpoints",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LintRuleInfo {
+    pub rule: LintRule,
+    pub severity: Severity,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub severity: Severity,
+    pub container: Option<String>,
+    pub message: String,
+}
+
+/// All findings for one object
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LintObject {
+    pub kind: String,
+    pub namespace: String,
+    pub name: String,
+    pub severity: Severity, // Most severe finding
+    pub findings: Vec<LintFinding>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LintReport {
+    pub scanned: usize,
+    pub critical: usize,
+    pub warning: usize,
+    pub info: usize,
+    pub objects: Vec<LintObject>, // Most severe first
+}
+
+/// Objects the linter looks at
+#[derive(Default)]
+pub struct LintInput {
+    pub deployments: Vec<Deployment>,
+    pub stateful_sets: Vec<StatefulSet>,
+    pub daemon_sets: Vec<DaemonSet>,
+    pub cron_jobs: Vec<CronJob>,
+    pub pods: Vec<Pod>,
+    pub pdbs: Vec<PodDisruptionBudget>,
+    pub hpas: Vec<HorizontalPodAutoscaler>,
+    pub services: Vec<Service>,
+    pub endpoints: Vec<Endpoints>,
+}
+
+struct Linter<'a> {
+    disabled: &'a [LintRule],
+    findings: Vec<LintFinding>,
+}
+
+impl Linter<'_> {
+    fn flag(&mut self, rule: LintRule, container: Option<&str>, message: impl Into<String>) {
+        if !self.disabled.contains(&rule) {
+            self.findings.push(LintFinding {
+                rule,
+                severity: rule.severity(),
+                container: container.map(str::to_string),
+                message: message.into(),
+            });
+        }
+    }
+}
+
+/// Why an image reference is mutable, if it is; digests are always pinned
+pub fn mutable_image_tag(image: &str) -> Option<&'static str> {
+    if image.contains('@') {
+        return None;
+    }
+    // The last path segment holds the tag; earlier colons belong to a registry port
+    let last = image.rsplit('/').next().unwrap_or(image);
+    match last.split_once(':') {
+        Some((_, "latest")) => Some("uses the :latest tag"),
+        Some(_) => None,
+        None => Some("has no tag, so it resolves to :latest"),
+    }
+}
+
+fn lint_container(linter: &mut Linter, spec: &PodSpec, container: &Container, long_running: bool) {
+    let name = Some(container.name.as_str());
+    let resources = container.resources.as_ref();
+
+    let requests = resources.and_then(|r| r.requests.as_ref());
+    let missing: Vec<&str> = ["cpu", "memory"]
+        .into_iter()
+        .filter(|r| requests.is_none_or(|q| !q.contains_key(*r)))
+        .collect();
+    if !missing.is_empty() {
+        linter.flag(
+            LintRule::MissingRequests,
+            name,
+            format!("No {} request", missing.join(" or ")),
+        );
+    }
+    let limits = resources.and_then(|r| r.limits.as_ref());
+    if limits.is_none_or(|l| !l.contains_key("memory")) {
+        linter.flag(LintRule::MissingLimits, name, "No memory limit");
+    }
+
+    if let Some(problem) = container.image.as_deref().and_then(mutable_image_tag) {
+        linter.flag(
+            LintRule::MutableImageTag,
+            name,
+            format!(
+                "Image {} {}",
+                container.image.as_deref().unwrap_or_default(),
+                problem
+            ),
+        );
+    }
+
+    if long_running {
+        let missing: Vec<&str> = [
+            ("readiness", container.readiness_probe.is_none()),
+            ("liveness", container.liveness_probe.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(probe, missing)| missing.then_some(probe))
+        .collect();
+        if !missing.is_empty() {
+            linter.flag(
+                LintRule::MissingProbes,
+                name,
+                format!("No {} probe", missing.join(" or ")),
+            );
+        }
+    }
+
+    let security = container.security_context.as_ref();
+    if security.and_then(|s| s.privileged) == Some(true) {
+        linter.flag(LintRule::PrivilegedContainer, name, "Runs privileged");
+    }
+
+    // Container settings override the pod's
+    let pod_security = spec.security_context.as_ref();
+    let run_as_user = security
+        .and_then(|s| s.run_as_user)
+        .or(pod_security.and_then(|s| s.run_as_user));
+    let run_as_non_root = security
+        .and_then(|s| s.run_as_non_root)
+        .or(pod_security.and_then(|s| s.run_as_non_root));
+    if run_as_user == Some(0) {
+        linter.flag(LintRule::RunsAsRoot, name, "Runs as root (runAsUser 0)");
+    } else if run_as_user.is_none() && run_as_non_root != Some(true) {
+        linter.flag(
+            LintRule::RunsAsRoot,
+            name,
+            "May run as root; set runAsNonRoot or a non-zero runAsUser",
+        );
+    }
+}
+
+fn lint_pod_spec(linter: &mut Linter, spec: &PodSpec, long_running: bool) {
+    for container in spec.init_containers.iter().flatten() {
+        lint_container(linter, spec, container, false);
+    }
+    for container in &spec.containers {
+        lint_container(linter, spec, container, long_running);
+    }
+}
+
+fn has_pdb(
+    pdbs: &[PodDisruptionBudget],
+    namespace: &str,
+    labels: &BTreeMap<String, String>,
+) -> bool {
+    pdbs.iter().any(|pdb| {
+        pdb.metadata.namespace.as_deref() == Some(namespace)
+            && pdb
+                .spec
+                .as_ref()
+                .and_then(|s| s.selector.as_ref())
+                .is_some_and(|s| label_selector_matches(s, labels))
+    })
+}
+
+struct ReportBuilder<'a> {
+    disabled: &'a [LintRule],
+    scanned: usize,
+    objects: Vec<LintObject>,
+}
+
+impl<'a> ReportBuilder<'a> {
+    fn object(
+        &mut self,
+        kind: &str,
+        metadata: &k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta,
+        check: impl FnOnce(&mut Linter<'a>),
+    ) {
+        let mut linter = Linter {
+            disabled: self.disabled,
+            findings: vec![],
+        };
+        check(&mut linter);
+        self.scanned += 1;
+        if let Some(severity) = linter.findings.iter().map(|f| f.severity).min() {
+            self.objects.push(LintObject {
+                kind: kind.to_string(),
+                namespace: metadata.namespace.clone().unwrap_or_default(),
+                name: metadata.name.clone().unwrap_or_default(),
+                severity,
+                findings: linter.findings,
+            });
+        }
+    }
+}
+
+fn single_replica_check(
+    linter: &mut Linter,
+    replicas: Option<i32>,
+    namespace: Option<&str>,
+    labels: Option<&BTreeMap<String, String>>,
+    pdbs: &[PodDisruptionBudget],
+) {
+    let labels = labels.cloned().unwrap_or_default();
+    if replicas.unwrap_or(1) == 1 && !has_pdb(pdbs, namespace.unwrap_or_default(), &labels) {
+        linter.flag(
+            LintRule::SingleReplicaWithoutPdb,
+            None,
+            "Single replica with no PodDisruptionBudget; voluntary disruptions cause downtime",
+        );
+    }
+}
+
+/// Run every enabled rule over the input and group findings per object
+pub fn lint(input: &LintInput, disabled: &[LintRule]) -> LintReport {
+    let mut report = ReportBuilder {
+        disabled,
+        scanned: 0,
+        objects: vec![],
+    };
+
+    for d in &input.deployments {
+        report.object("Deployment", &d.metadata, |linter| {
+            let spec = d.spec.as_ref();
+            if let Some(pod_spec) = spec.and_then(|s| s.template.spec.as_ref()) {
+                lint_pod_spec(linter, pod_spec, true);
+            }
+            let template_labels = spec.and_then(|s| s.template.metadata.as_ref()?.labels.as_ref());
+            single_replica_check(
+                linter,
+                spec.and_then(|s| s.replicas),
+                d.metadata.namespace.as_deref(),
+                template_labels,
+                &input.pdbs,
+            );
+        });
+    }
+    for s in &input.stateful_sets {
+        report.object("StatefulSet", &s.metadata, |linter| {
+            let spec = s.spec.as_ref();
+            if let Some(pod_spec) = spec.and_then(|s| s.template.spec.as_ref()) {
+                lint_pod_spec(linter, pod_spec, true);
+            }
+            let template_labels = spec.and_then(|s| s.template.metadata.as_ref()?.labels.as_ref());
+            single_replica_check(
+                linter,
+                spec.and_then(|s| s.replicas),
+                s.metadata.namespace.as_deref(),
+                template_labels,
+                &input.pdbs,
+            );
+        });
+    }
+    for d in &input.daemon_sets {
+        report.object("DaemonSet", &d.metadata, |linter| {
+            if let Some(pod_spec) = d.spec.as_ref().and_then(|s| s.template.spec.as_ref()) {
+                lint_pod_spec(linter, pod_spec, true);
+            }
+        });
+    }
+    for c in &input.cron_jobs {
+        report.object("CronJob", &c.metadata, |linter| {
+            let pod_spec = c
+                .spec
+                .as_ref()
+                .and_then(|s| s.job_template.spec.as_ref())
+                .and_then(|s| s.template.spec.as_ref());
+            if let Some(pod_spec) = pod_spec {
+                lint_pod_spec(linter, pod_spec, false);
+            }
+        });
+    }
+    // Controlled pods are covered by their workload's template
+    let standalone = input.pods.iter().filter(|p| {
+        p.metadata
+            .owner_references
+            .as_ref()
+            .is_none_or(|o| o.is_empty())
+    });
+    for p in standalone {
+        report.object("Pod", &p.metadata, |linter| {
+            if let Some(pod_spec) = &p.spec {
+                lint_pod_spec(linter, pod_spec, true);
+            }
+        });
+    }
+
+    for hpa in &input.hpas {
+        report.object("HorizontalPodAutoscaler", &hpa.metadata, |linter| {
+            let Some(target) = hpa.spec.as_ref().map(|s| &s.scale_target_ref) else {
+                return;
+            };
+            let namespace = hpa.metadata.namespace.as_deref();
+            let same = |meta: &k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta| {
+                meta.namespace.as_deref() == namespace
+                    && meta.name.as_deref() == Some(target.name.as_str())
+            };
+            let exists = match target.kind.as_str() {
+                "Deployment" => input.deployments.iter().any(|d| same(&d.metadata)),
+                "StatefulSet" => input.stateful_sets.iter().any(|s| same(&s.metadata)),
+                _ => return, // Other scale targets are not fetched
+            };
+            if !exists {
+                linter.flag(
+                    LintRule::HpaMissingTarget,
+                    None,
+                    format!(
+                        "Scale target {} {} does not exist",
+                        target.kind, target.name
+                    ),
+                );
+            }
+        });
+    }
+
+    for service in &input.services {
+        report.object("Service", &service.metadata, |linter| {
+            let spec = service.spec.as_ref();
+            let has_selector = spec
+                .and_then(|s| s.selector.as_ref())
+                .is_some_and(|s| !s.is_empty());
+            if !has_selector || spec.and_then(|s| s.type_.as_deref()) == Some("ExternalName") {
+                return;
+            }
+            let subsets = input
+                .endpoints
+                .iter()
+                .find(|e| {
+                    e.metadata.namespace == service.metadata.namespace
+                        && e.metadata.name == service.metadata.name
+                })
+                .and_then(|e| e.subsets.as_ref());
+            let count = |ready: bool| {
+                subsets
+                    .into_iter()
+                    .flatten()
+                    .map(|s| {
+                        let addresses = if ready {
+                            &s.addresses
+                        } else {
+                            &s.not_ready_addresses
+                        };
+                        addresses.as_ref().map_or(0, |a| a.len())
+                    })
+                    .sum::<usize>()
+            };
+            if count(true) == 0 {
+                let not_ready = count(false);
+                let message = if not_ready > 0 {
+                    format!("No ready endpoints ({} not ready)", not_ready)
+                } else {
+                    "Selector matches no ready pods".to_string()
+                };
+                linter.flag(LintRule::ServiceWithoutEndpoints, None, message);
+            }
+        });
+    }
+
+    let mut objects = report.objects;
+    objects.sort_by(|a, b| {
+        (a.severity, &a.namespace, &a.kind, &a.name).cmp(&(
+            b.severity,
+            &b.namespace,
+            &b.kind,
+            &b.name,
+        ))
+    });
+    let count = |severity: Severity| {
+        objects
+            .iter()
+            .flat_map(|o| &o.findings)
+            .filter(|f| f.severity == severity)
+            .count()
+    };
+
+    LintReport {
+        scanned: report.scanned,
+        critical: count(Severity::Critical),
+        warning: count(Severity::Warning),
+        info: count(Severity::Info),
+        objects,
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct LintParams {
+    pub cluster_id: String,
+    pub namespace: Option<String>, // None scans all namespaces
+    #[serde(default)]
+    pub disabled_rules: Vec<LintRule>,
+}
+
+#[tauri::command]
+pub fn cluster_lint_rules() -> Vec<LintRuleInfo> {
+    LintRule::ALL
+        .into_iter()
+        .map(|rule| LintRuleInfo {
+            rule,
+            severity: rule.severity(),
+            description: rule.description(),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn cluster_lint(
+    params: LintParams,
+    state: State<'_, ClusterManagerState>,
+) -> Result<LintReport, String> {
+    let client = create_client_for_cluster(&params.cluster_id, &state).await?;
+    let ns = params.namespace.as_deref().filter(|ns| !ns.is_empty());
+
+    let input = LintInput {
+        deployments: list_namespaced_or_all(&client, ns).await?,
+        stateful_sets: list_namespaced_or_all(&client, ns).await?,
+        daemon_sets: list_namespaced_or_all(&client, ns).await?,
+        cron_jobs: list_namespaced_or_all(&client, ns).await?,
+        pods: list_namespaced_or_all(&client, ns).await?,
+        pdbs: list_namespaced_or_all(&client, ns).await?,
+        hpas: list_namespaced_or_all(&client, ns).await?,
+        services: list_namespaced_or_all(&client, ns).await?,
+        endpoints: list_namespaced_or_all(&client, ns).await?,
+    };
+    Ok(lint(&input, &params.disabled_rules))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn deployment(name: &str, replicas: i32, container: serde_json::Value) -> Deployment {
+        serde_json::from_value(json!({
+            "metadata": {"name": name, "namespace": "prod"},
+            "spec": {
+                "replicas": replicas,
+                "selector": {"matchLabels": {"app": name}},
+                "template": {
+                    "metadata": {"labels": {"app": name}},
+                    "spec": {"containers": [container]}
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn compliant_container() -> serde_json::Value {
+        json!({
+            "name": "app",
+            "image": "registry.local:5000/app:1.4.2",
+            "resources": {"requests": {"cpu": "100m", "memory": "128Mi"}, "limits": {"memory": "256Mi"}},
+            "readinessProbe": {"httpGet": {"path": "/ready", "port": 8080}},
+            "livenessProbe": {"httpGet": {"path": "/live", "port": 8080}},
+            "securityContext": {"runAsNonRoot": true}
+        })
+    }
+
+    fn rules(object: &LintObject) -> Vec<LintRule> {
+        object.findings.iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn test_mutable_image_tag() {
+        assert_eq!(
+            mutable_image_tag("nginx:latest"),
+            Some("uses the :latest tag")
+        );
+        assert!(mutable_image_tag("nginx").is_some());
+        assert!(mutable_image_tag("registry.local:5000/nginx").is_some());
+        assert_eq!(mutable_image_tag("registry.local:5000/nginx:1.27"), None);
+        assert_eq!(mutable_image_tag("nginx@sha256:abc"), None);
+    }
+
+    #[test]
+    fn test_container_rules_grouped_per_object() {
+        let input = LintInput {
+            deployments: vec![
+                deployment("good", 2, compliant_container()),
+                deployment(
+                    "bad",
+                    3,
+                    json!({"name": "app", "image": "app:latest", "securityContext": {"privileged": true, "runAsUser": 0}}),
+                ),
+            ],
+            ..Default::default()
+        };
+        let report = lint(&input, &[]);
+
+        assert_eq!(report.scanned, 2);
+        assert_eq!(report.objects.len(), 1);
+        let bad = &report.objects[0];
+        assert_eq!(
+            (bad.kind.as_str(), bad.name.as_str()),
+            ("Deployment", "bad")
+        );
+        assert_eq!(bad.severity, Severity::Critical);
+        assert_eq!(
+            rules(bad),
+            vec![
+                LintRule::MissingRequests,
+                LintRule::MissingLimits,
+                LintRule::MutableImageTag,
+                LintRule::MissingProbes,
+                LintRule::PrivilegedContainer,
+                LintRule::RunsAsRoot,
+            ]
+        );
+        assert_eq!(bad.findings[0].message, "No cpu or memory request");
+        assert_eq!(bad.findings[0].container.as_deref(), Some("app"));
+        assert_eq!((report.critical, report.warning, report.info), (1, 5, 0));
+
+        let filtered = lint(
+            &input,
+            &[LintRule::PrivilegedContainer, LintRule::RunsAsRoot],
+        );
+        assert_eq!(filtered.objects[0].severity, Severity::Warning);
+        assert_eq!(filtered.objects[0].findings.len(), 4);
+    }
+
+    #[test]
+    fn test_single_replica_pdb_and_hpa_target() {
+        let pdb: PodDisruptionBudget = serde_json::from_value(json!({
+            "metadata": {"name": "covered", "namespace": "prod"},
+            "spec": {"minAvailable": 1, "selector": {"matchLabels": {"app": "covered"}}}
+        }))
+        .unwrap();
+        let hpa: HorizontalPodAutoscaler = serde_json::from_value(json!({
+            "metadata": {"name": "web", "namespace": "prod"},
+            "spec": {"maxReplicas": 5, "scaleTargetRef": {"apiVersion": "apps/v1", "kind": "Deployment", "name": "web"}}
+        }))
+        .unwrap();
+        let input = LintInput {
+            deployments: vec![
+                deployment("covered", 1, compliant_container()),
+                deployment("lonely", 1, compliant_container()),
+            ],
+            pdbs: vec![pdb],
+            hpas: vec![hpa],
+            ..Default::default()
+        };
+        let report = lint(&input, &[]);
+
+        let summary: Vec<(&str, &str, Vec<LintRule>)> = report
+            .objects
+            .iter()
+            .map(|o| (o.kind.as_str(), o.name.as_str(), rules(o)))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "HorizontalPodAutoscaler",
+                    "web",
+                    vec![LintRule::HpaMissingTarget]
+                ),
+                (
+                    "Deployment",
+                    "lonely",
+                    vec![LintRule::SingleReplicaWithoutPdb]
+                ),
+            ]
+        );
+        assert_eq!(
+            report.objects[0].findings[0].message,
+            "Scale target Deployment web does not exist"
+        );
+    }
+
+    #[test]
+    fn test_service_without_ready_endpoints() {
+        let service = |name: &str| -> Service {
+            serde_json::from_value(json!({
+                "metadata": {"name": name, "namespace": "prod"},
+                "spec": {"selector": {"app": name}}
+            }))
+            .unwrap()
+        };
+        let endpoints: Endpoints = serde_json::from_value(json!({
+            "metadata": {"name": "api", "namespace": "prod"},
+            "subsets": [{"notReadyAddresses": [{"ip": "10.0.0.4"}]}]
+        }))
+        .unwrap();
+        let external: Service = serde_json::from_value(json!({
+            "metadata": {"name": "ext", "namespace": "prod"},
+            "spec": {"type": "ExternalName", "externalName": "example.com"}
+        }))
+        .unwrap();
+        let input = LintInput {
+            services: vec![service("api"), service("orphan"), external],
+            endpoints: vec![endpoints],
+            ..Default::default()
+        };
+        let report = lint(&input, &[]);
+
+        let messages: Vec<(&str, &str)> = report
+            .objects
+            .iter()
+            .map(|o| (o.name.as_str(), o.findings[0].message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                ("api", "No ready endpoints (1 not ready)"),
+                ("orphan", "Selector matches no ready pods"),
+            ]
+        );
+    }
+}
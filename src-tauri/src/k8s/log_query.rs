@@ -0,0 +1,655 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::logs::LogStreamOptions;
+use futures::{AsyncBufReadExt, TryStreamExt};
+use k8s_openapi::api::core::v1::Pod;
+use kube::Api;
+use regex::{Regex, RegexBuilder};
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+/// Matches returned by a query unless a lower limit is requested
+const DEFAULT_MAX_MATCHES: usize = 1000;
+
+/// Upper bound on context lines around each match
+const MAX_CONTEXT_LINES: usize = 20;
+
+/// Lines buffered between the log stream and the blocking file writer of an export
+const EXPORT_CHANNEL_LINES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Json,
+    Logfmt,
+    Plain,
+}
+
+/// Fields extracted from a single log line
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ParsedLogLine {
+    pub format: LogFormat,
+    pub level: Option<String>,
+    pub timestamp: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LogQueryEntry {
+    pub line_number: usize, // 1-based, within the fetched log
+    pub raw: String,
+    pub is_match: bool, // false for context lines
+    #[serde(flatten)]
+    pub parsed: ParsedLogLine,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LogQueryResult {
+    pub total_lines: usize,
+    pub matched_lines: usize,
+    pub truncated: bool,
+    pub format: Option<LogFormat>, // Dominant format of the log, if any lines were read
+    pub entries: Vec<LogQueryEntry>,
+}
+
+/// Substring or regex line filter
+#[derive(Debug, Clone)]
+pub enum LogFilter {
+    All,
+    Substring {
+        needle: String,
+        case_sensitive: bool,
+    },
+    Pattern(Regex),
+}
+
+impl LogFilter {
+    pub fn new(pattern: Option<&str>, regex: bool, case_sensitive: bool) -> Result<Self, String> {
+        match pattern.filter(|p| !p.is_empty()) {
+            None => Ok(LogFilter::All),
+            Some(p) if regex => RegexBuilder::new(p)
+                .case_insensitive(!case_sensitive)
+                .build()
+                .map(LogFilter::Pattern)
+                .map_err(|e| format!("Invalid pattern: {}", e)),
+            Some(p) => Ok(LogFilter::Substring {
+                needle: if case_sensitive {
+                    p.to_string()
+                } else {
+                    p.to_lowercase()
+                },
+                case_sensitive,
+            }),
+        }
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        match self {
+            LogFilter::All => true,
+            LogFilter::Substring {
+                needle,
+                case_sensitive: true,
+            } => line.contains(needle.as_str()),
+            LogFilter::Substring { needle, .. } => line.to_lowercase().contains(needle.as_str()),
+            LogFilter::Pattern(re) => re.is_match(line),
+        }
+    }
+}
+
+/// Normalize level spellings ("WARNING", "err", pino's numeric 50, ...) to a common set
+pub fn normalize_level(level: &str) -> Option<String> {
+    let level = level.trim().trim_matches(|c| c == '[' || c == ']');
+    let normalized = match level.to_lowercase().as_str() {
+        "trace" | "10" => "trace",
+        "debug" | "dbug" | "20" => "debug",
+        "info" | "information" | "notice" | "30" => "info",
+        "warn" | "warning" | "40" => "warn",
+        "error" | "err" | "eror" | "50" => "error",
+        "fatal" | "panic" | "critical" | "crit" | "emergency" | "alert" | "60" => "fatal",
+        _ => return None,
+    };
+    Some(normalized.to_string())
+}
+
+const LEVEL_KEYS: [&str; 5] = ["level", "lvl", "severity", "log.level", "loglevel"];
+const TIMESTAMP_KEYS: [&str; 5] = ["time", "ts", "timestamp", "@timestamp", "t"];
+const MESSAGE_KEYS: [&str; 4] = ["msg", "message", "log", "@message"];
+
+fn json_field_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_json_line(line: &str) -> Option<ParsedLogLine> {
+    let value: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    let object = value.as_object()?;
+    let find = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| object.get(*k).and_then(json_field_string))
+    };
+
+    Some(ParsedLogLine {
+        format: LogFormat::Json,
+        level: find(&LEVEL_KEYS).and_then(|l| normalize_level(&l)),
+        timestamp: find(&TIMESTAMP_KEYS),
+        message: find(&MESSAGE_KEYS).unwrap_or_else(|| line.to_string()),
+    })
+}
+
+/// Split a logfmt line into key/value pairs, honouring double-quoted values
+pub fn parse_logfmt_pairs(line: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        let key: String =
+            std::iter::from_fn(|| chars.next_if(|c| *c != '=' && !c.is_whitespace())).collect();
+        if key.is_empty() {
+            break;
+        }
+        if chars.next_if_eq(&'=').is_none() {
+            pairs.push((key, String::new()));
+            continue;
+        }
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+        } else {
+            value.extend(std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())));
+        }
+        pairs.push((key, value));
+    }
+
+    pairs
+}
+
+fn parse_logfmt_line(line: &str) -> Option<ParsedLogLine> {
+    let pairs = parse_logfmt_pairs(line);
+    let find = |keys: &[&str]| {
+        keys.iter().find_map(|k| {
+            pairs
+                .iter()
+                .find(|(key, _)| key == k)
+                .map(|(_, v)| v.clone())
+        })
+    };
+
+    let level = find(&LEVEL_KEYS);
+    let message = find(&MESSAGE_KEYS);
+    let with_values = pairs.iter().filter(|(_, v)| !v.is_empty()).count();
+    if with_values < 2 || (level.is_none() && message.is_none()) {
+        return None;
+    }
+
+    Some(ParsedLogLine {
+        format: LogFormat::Logfmt,
+        level: level.and_then(|l| normalize_level(&l)),
+        timestamp: find(&TIMESTAMP_KEYS),
+        message: message.unwrap_or_else(|| line.to_string()),
+    })
+}
+
+fn parse_plain_line(line: &str) -> ParsedLogLine {
+    let mut words = line.split_whitespace().take(4);
+    let timestamp = line
+        .split_whitespace()
+        .next()
+        .filter(|w| w.parse::<k8s_openapi::jiff::Timestamp>().is_ok())
+        .map(str::to_string);
+
+    ParsedLogLine {
+        format: LogFormat::Plain,
+        level: words.find_map(|w| {
+            normalize_level(w.trim_end_matches(':')).filter(|_| {
+                // Plain-text levels are only trusted when written in upper case
+                w.chars().any(|c| c.is_ascii_uppercase())
+                    && !w.chars().any(|c| c.is_ascii_lowercase())
+            })
+        }),
+        timestamp,
+        message: line.to_string(),
+    }
+}
+
+/// Detect JSON or logfmt structure and extract level, timestamp and message
+pub fn parse_log_line(line: &str) -> ParsedLogLine {
+    if line.trim_start().starts_with('{') {
+        if let Some(parsed) = parse_json_line(line) {
+            return parsed;
+        }
+    }
+    parse_logfmt_line(line).unwrap_or_else(|| parse_plain_line(line))
+}
+
+/// Filter lines and attach up to `context` surrounding lines to each match
+pub fn query_lines(
+    lines: &[String],
+    filter: &LogFilter,
+    levels: &BTreeSet<String>,
+    context: usize,
+    max_matches: usize,
+) -> LogQueryResult {
+    let parsed: Vec<ParsedLogLine> = lines.iter().map(|l| parse_log_line(l)).collect();
+
+    let mut counts = [0usize; 3];
+    for p in &parsed {
+        counts[p.format as usize] += 1;
+    }
+    let format = [LogFormat::Json, LogFormat::Logfmt, LogFormat::Plain]
+        .into_iter()
+        .filter(|f| counts[*f as usize] > 0)
+        .max_by_key(|f| counts[*f as usize]);
+
+    let matched: Vec<usize> = (0..lines.len())
+        .filter(|&i| {
+            filter.matches(&lines[i])
+                && (levels.is_empty()
+                    || parsed[i]
+                        .level
+                        .as_ref()
+                        .is_some_and(|level| levels.contains(level)))
+        })
+        .collect();
+    let truncated = matched.len() > max_matches;
+
+    let mut selected: BTreeSet<usize> = BTreeSet::new();
+    for &i in matched.iter().take(max_matches) {
+        let end = (i + context).min(lines.len().saturating_sub(1));
+        selected.extend(i.saturating_sub(context)..=end);
+    }
+    let match_set: BTreeSet<usize> = matched.iter().take(max_matches).copied().collect();
+
+    LogQueryResult {
+        total_lines: lines.len(),
+        matched_lines: matched.len(),
+        truncated,
+        format,
+        entries: selected
+            .into_iter()
+            .map(|i| LogQueryEntry {
+                line_number: i + 1,
+                raw: lines[i].clone(),
+                is_match: match_set.contains(&i),
+                parsed: parsed[i].clone(),
+            })
+            .collect(),
+    }
+}
+
+/// Parameters for querying one container's log
+#[derive(serde::Deserialize)]
+pub struct LogQueryParams {
+    cluster_id: String,
+    namespace: String,
+    pod_name: String,
+    container_name: String,
+    pattern: Option<String>,
+    #[serde(default)]
+    regex: bool,
+    #[serde(default)]
+    case_sensitive: bool,
+    #[serde(default)]
+    levels: Vec<String>, // Normalized levels to keep, e.g. ["error", "warn"]
+    #[serde(default)]
+    context_lines: usize,
+    max_matches: Option<usize>,
+    #[serde(flatten)]
+    options: LogStreamOptions,
+}
+
+/// Fetch a container's log within since/tail bounds and search it
+#[tauri::command]
+pub async fn cluster_query_container_logs(
+    params: LogQueryParams,
+    state: State<'_, ClusterManagerState>,
+) -> Result<LogQueryResult, String> {
+    let filter = LogFilter::new(
+        params.pattern.as_deref(),
+        params.regex,
+        params.case_sensitive,
+    )?;
+    let levels: BTreeSet<String> = params
+        .levels
+        .iter()
+        .filter_map(|l| normalize_level(l))
+        .collect();
+    let log_params = LogStreamOptions {
+        follow: Some(false),
+        ..params.options
+    }
+    .to_log_params(&params.container_name)?;
+
+    let client = create_client_for_cluster(&params.cluster_id, &state).await?;
+    let pods: Api<Pod> = Api::namespaced(client, &params.namespace);
+    let log = pods
+        .logs(&params.pod_name, &log_params)
+        .await
+        .map_err(|e| format!("Failed to get logs: {}", e))?;
+    let lines: Vec<String> = log.lines().map(str::to_string).collect();
+
+    Ok(query_lines(
+        &lines,
+        &filter,
+        &levels,
+        params.context_lines.min(MAX_CONTEXT_LINES),
+        params.max_matches.unwrap_or(DEFAULT_MAX_MATCHES),
+    ))
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct LogExportTarget {
+    pub pod_name: String,
+    pub container_name: String,
+}
+
+/// Parameters for writing container logs to a local file
+#[derive(serde::Deserialize)]
+pub struct LogExportParams {
+    cluster_id: String,
+    namespace: String,
+    targets: Vec<LogExportTarget>,
+    path: String, // Absolute; chosen by the user through the save dialog
+    pattern: Option<String>,
+    #[serde(default)]
+    regex: bool,
+    #[serde(default)]
+    case_sensitive: bool,
+    #[serde(flatten)]
+    options: LogStreamOptions,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LogExportResult {
+    pub path: String,
+    pub lines_written: usize,
+    pub containers: usize,
+}
+
+/// Validate an export path and derive the temporary file written next to it.
+/// The path must be absolute, must not be a directory, and its parent must exist.
+pub fn export_destination(path: &str) -> Result<(PathBuf, PathBuf), String> {
+    let target = Path::new(path);
+    if !target.is_absolute() {
+        return Err(format!("Export path '{}' must be absolute", path));
+    }
+    if target.is_dir() {
+        return Err(format!("Export path '{}' is a directory", path));
+    }
+    let (Some(parent), Some(file_name)) = (target.parent(), target.file_name()) else {
+        return Err(format!("Export path '{}' has no file name", path));
+    };
+    if !parent.is_dir() {
+        return Err(format!(
+            "Directory '{}' does not exist",
+            parent.to_string_lossy()
+        ));
+    }
+    let temp = parent.join(format!(".{}.part", file_name.to_string_lossy()));
+    Ok((target.to_path_buf(), temp))
+}
+
+/// Write the full (optionally filtered) log of one or more containers to a file.
+/// With several containers each line is prefixed with `[pod/container]`.
+#[tauri::command]
+pub async fn cluster_export_container_logs(
+    params: LogExportParams,
+    state: State<'_, ClusterManagerState>,
+) -> Result<LogExportResult, String> {
+    if params.targets.is_empty() {
+        return Err("No containers selected for export".to_string());
+    }
+    let filter = LogFilter::new(
+        params.pattern.as_deref(),
+        params.regex,
+        params.case_sensitive,
+    )?;
+    // Exports cover the whole log unless bounded explicitly
+    let options = LogStreamOptions {
+        follow: Some(false),
+        tail_lines: params.options.tail_lines.or(Some(-1)),
+        ..params.options
+    };
+
+    let (target_path, temp_path) = export_destination(&params.path)?;
+
+    let client = create_client_for_cluster(&params.cluster_id, &state).await?;
+    let pods: Api<Pod> = Api::namespaced(client, &params.namespace);
+
+    // Written to a temporary file first so a failed export never leaves a partial log.
+    // File I/O runs on the blocking pool; lines reach it through a bounded channel.
+    let (tx, rx) = tokio::sync::mpsc::channel(EXPORT_CHANNEL_LINES);
+    let writer_path = temp_path.clone();
+    let writer = tauri::async_runtime::spawn_blocking(move || write_export_file(&writer_path, rx));
+    let streamed = stream_export(&pods, &params.targets, &options, &filter, &tx).await;
+    drop(tx);
+    let file_written = writer
+        .await
+        .map_err(|e| format!("Export writer failed: {}", e))
+        .and_then(|r| r.map_err(|e| format!("Failed to write '{}': {}", params.path, e)));
+    // A write error closes the channel, so report it over the stream's "writer stopped"
+    let written = match (streamed, file_written) {
+        (_, Err(e)) => Err(e),
+        (streamed, Ok(())) => streamed,
+    };
+
+    let path = params.path.clone();
+    let lines_written = tauri::async_runtime::spawn_blocking(move || match written {
+        Ok(lines_written) => std::fs::rename(&temp_path, &target_path)
+            .map(|_| lines_written)
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp_path);
+                format!("Failed to write '{}': {}", path, e)
+            }),
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp_path);
+            Err(e)
+        }
+    })
+    .await
+    .map_err(|e| format!("Export writer failed: {}", e))??;
+
+    Ok(LogExportResult {
+        path: params.path,
+        lines_written,
+        containers: params.targets.len(),
+    })
+}
+
+/// Write lines received from `lines` to a new file at `path` until the channel closes
+fn write_export_file(
+    path: &Path,
+    mut lines: tokio::sync::mpsc::Receiver<String>,
+) -> std::io::Result<()> {
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    while let Some(line) = lines.blocking_recv() {
+        writeln!(writer, "{}", line)?;
+    }
+    writer.flush()
+}
+
+/// Stream every target's log through `filter` into `lines`, returning the lines sent
+async fn stream_export(
+    pods: &Api<Pod>,
+    targets: &[LogExportTarget],
+    options: &LogStreamOptions,
+    filter: &LogFilter,
+    lines: &tokio::sync::mpsc::Sender<String>,
+) -> Result<usize, String> {
+    let prefix_lines = targets.len() > 1;
+    let mut lines_written = 0;
+
+    for target in targets {
+        let log_params = options.to_log_params(&target.container_name)?;
+        let stream = pods
+            .log_stream(&target.pod_name, &log_params)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to get logs for {}/{}: {}",
+                    target.pod_name, target.container_name, e
+                )
+            })?;
+        let mut log_lines = stream.lines();
+
+        while let Some(line) = log_lines
+            .try_next()
+            .await
+            .map_err(|e| format!("Error reading log line: {}", e))?
+        {
+            if !filter.matches(&line) {
+                continue;
+            }
+            let line = if prefix_lines {
+                format!("[{}/{}] {}", target.pod_name, target.container_name, line)
+            } else {
+                line
+            };
+            lines
+                .send(line)
+                .await
+                .map_err(|_| "Export writer stopped".to_string())?;
+            lines_written += 1;
+        }
+    }
+    Ok(lines_written)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn lines(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn export_destination_requires_an_existing_parent() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("kore-export.log");
+        let (target, temp) = export_destination(&path.to_string_lossy()).unwrap();
+        assert_eq!(target, path);
+        assert_eq!(temp, dir.join(".kore-export.log.part"));
+
+        assert!(export_destination("relative/app.log").is_err());
+        assert!(export_destination(&dir.to_string_lossy()).is_err());
+        let missing = dir.join("kore-missing-dir").join("app.log");
+        assert!(export_destination(&missing.to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn export_file_writes_lines_until_the_channel_closes() {
+        let path = std::env::temp_dir().join("kore-export-writer.log");
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tx.try_send("first".to_string()).unwrap();
+        tx.try_send("[api-0/api] second".to_string()).unwrap();
+        drop(tx);
+
+        write_export_file(&path, rx).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(written, "first\n[api-0/api] second\n");
+    }
+
+    #[test]
+    fn parses_json_lines() {
+        let parsed = parse_log_line(
+            r#"{"level":"WARNING","ts":"2024-05-01T10:00:00Z","msg":"disk almost full"}"#,
+        );
+        assert_eq!(parsed.format, LogFormat::Json);
+        assert_eq!(parsed.level.as_deref(), Some("warn"));
+        assert_eq!(parsed.timestamp.as_deref(), Some("2024-05-01T10:00:00Z"));
+        assert_eq!(parsed.message, "disk almost full");
+
+        let pino = parse_log_line(r#"{"level":50,"time":1714557600000,"msg":"boom"}"#);
+        assert_eq!(pino.level.as_deref(), Some("error"));
+        assert_eq!(pino.timestamp.as_deref(), Some("1714557600000"));
+    }
+
+    #[test]
+    fn parses_logfmt_lines() {
+        let parsed = parse_log_line(
+            r#"time=2024-05-01T10:00:00Z level=error msg="connection \"db\" refused" retry=3"#,
+        );
+        assert_eq!(parsed.format, LogFormat::Logfmt);
+        assert_eq!(parsed.level.as_deref(), Some("error"));
+        assert_eq!(parsed.message, r#"connection "db" refused"#);
+        assert_eq!(parsed.timestamp.as_deref(), Some("2024-05-01T10:00:00Z"));
+    }
+
+    #[test]
+    fn parses_plain_lines() {
+        let parsed = parse_log_line("2024-05-01T10:00:00Z ERROR: failed to bind port");
+        assert_eq!(parsed.format, LogFormat::Plain);
+        assert_eq!(parsed.level.as_deref(), Some("error"));
+        assert_eq!(parsed.timestamp.as_deref(), Some("2024-05-01T10:00:00Z"));
+
+        // Lower-case words are not mistaken for levels, nor a single key=value for logfmt
+        let prose = parse_log_line("no error here key=value");
+        assert_eq!(prose.format, LogFormat::Plain);
+        assert_eq!(prose.level, None);
+    }
+
+    #[test]
+    fn filters_substring_and_regex() {
+        let insensitive = LogFilter::new(Some("Timeout"), false, false).unwrap();
+        assert!(insensitive.matches("request TIMEOUT after 30s"));
+        let sensitive = LogFilter::new(Some("Timeout"), false, true).unwrap();
+        assert!(!sensitive.matches("request TIMEOUT after 30s"));
+        let re = LogFilter::new(Some(r"status=5\d\d"), true, false).unwrap();
+        assert!(re.matches("GET / status=503"));
+        assert!(!re.matches("GET / status=200"));
+        assert!(LogFilter::new(Some("("), true, false).is_err());
+    }
+
+    #[test]
+    fn query_lines_adds_context_and_truncates() {
+        let log = lines(&[
+            "level=info msg=start",
+            "level=info msg=working",
+            "level=error msg=failed",
+            "level=info msg=retry",
+            "level=info msg=working",
+            "level=error msg=failed again",
+        ]);
+        let filter = LogFilter::new(Some("failed"), false, false).unwrap();
+
+        let result = query_lines(&log, &filter, &BTreeSet::new(), 1, 10);
+        assert_eq!(result.total_lines, 6);
+        assert_eq!(result.matched_lines, 2);
+        assert!(!result.truncated);
+        assert_eq!(result.format, Some(LogFormat::Logfmt));
+        let numbers: Vec<(usize, bool)> = result
+            .entries
+            .iter()
+            .map(|e| (e.line_number, e.is_match))
+            .collect();
+        assert_eq!(
+            numbers,
+            vec![(2, false), (3, true), (4, false), (5, false), (6, true)]
+        );
+
+        let truncated = query_lines(&log, &filter, &BTreeSet::new(), 0, 1);
+        assert!(truncated.truncated);
+        assert_eq!(truncated.entries.len(), 1);
+
+        let errors = BTreeSet::from(["error".to_string()]);
+        let by_level = query_lines(&log, &LogFilter::All, &errors, 0, 10);
+        assert_eq!(by_level.matched_lines, 2);
+    }
+}
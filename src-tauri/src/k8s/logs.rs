@@ -0,0 +1,1111 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::label_selector_to_string;
+use crate::k8s::dynamic::{dynamic_api, resolve_api_resource};
+use crate::k8s::tasks::{BackgroundTaskState, TaskInfo};
+use futures::{AsyncBufReadExt, Stream, StreamExt};
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use kube::api::LogParams;
+use kube::runtime::watcher;
+use kube::Api;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
+use tauri::{Emitter, State, Window};
+
+/// Lines of history fetched when a pod joins an aggregated stream
+const AGGREGATED_TAIL_LINES: i64 = 100;
+
+/// Lines of history fetched by a single-container stream unless configured
+const DEFAULT_TAIL_LINES: i64 = 1000;
+
+/// Optional knobs for reading container logs
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct LogStreamOptions {
+    pub follow: Option<bool>,    // Defaults to true
+    pub tail_lines: Option<i64>, // Negative reads the whole log; defaults to 1000 without a since bound
+    pub since_seconds: Option<i64>,
+    pub since_time: Option<String>, // RFC3339
+    pub timestamps: bool,
+    pub previous: bool, // Logs of the last terminated instance of the container
+    pub limit_bytes: Option<i64>,
+}
+
+impl LogStreamOptions {
+    /// Validate the options and build the LogParams for `container`
+    pub fn to_log_params(&self, container: &str) -> Result<LogParams, String> {
+        if self.since_seconds.is_some() && self.since_time.is_some() {
+            return Err("Only one of since_seconds or since_time may be set".to_string());
+        }
+        if self.since_seconds.is_some_and(|s| s <= 0) {
+            return Err("since_seconds must be positive".to_string());
+        }
+        if self.limit_bytes.is_some_and(|b| b <= 0) {
+            return Err("limit_bytes must be positive".to_string());
+        }
+
+        let since_time = self
+            .since_time
+            .as_deref()
+            .map(|t| {
+                t.parse::<k8s_openapi::jiff::Timestamp>()
+                    .map_err(|e| format!("Invalid since_time '{}': {}", t, e))
+            })
+            .transpose()?;
+
+        let tail_lines = match self.tail_lines {
+            Some(n) if n < 0 => None,
+            Some(n) => Some(n),
+            None if self.since_seconds.is_some() || since_time.is_some() => None,
+            None => Some(DEFAULT_TAIL_LINES),
+        };
+
+        Ok(LogParams {
+            container: Some(container.to_string()),
+            // A previous container has exited, so there is nothing to follow
+            follow: self.follow.unwrap_or(true) && !self.previous,
+            tail_lines,
+            since_seconds: self.since_seconds,
+            since_time,
+            timestamps: self.timestamps,
+            previous: self.previous,
+            limit_bytes: self.limit_bytes,
+            ..Default::default()
+        })
+    }
+}
+
+/// Parameters for following the logs of every pod matching a selector or workload
+#[derive(serde::Deserialize)]
+pub struct AggregatedLogStreamParams {
+    cluster_id: String,
+    namespace: String,
+    label_selector: Option<String>,
+    workload_kind: Option<String>, // e.g. "Deployment"; its spec.selector is used
+    workload_name: Option<String>,
+    container_pattern: Option<String>, // Regex matched against container names
+    stream_id: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum AggregatedLogEvent {
+    Lines {
+        pod: String,
+        container: String,
+        batch: LogBatch,
+    },
+    PodAdded {
+        pod: String,
+        containers: Vec<String>,
+    },
+    PodRemoved {
+        pod: String,
+    },
+}
+
+/// Lines kept in memory per stream before the oldest are dropped
+const LOG_BUFFER_CAPACITY: usize = 2000;
+
+/// Buffered lines that trigger an early flush
+const LOG_BATCH_LINES: usize = 500;
+
+/// Regular flush interval for buffered lines
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Minimum spacing between two flushes, so floods cannot outpace the webview
+const LOG_MIN_FLUSH_SPACING: Duration = Duration::from_millis(50);
+
+/// Per-stream throughput counters
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct LogStreamStats {
+    pub lines_received: u64,
+    pub lines_emitted: u64,
+    pub lines_skipped: u64,
+    pub batches: u64,
+    pub lines_per_second: f64,
+}
+
+/// A batch of log lines emitted to the frontend
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LogBatch {
+    pub lines: Vec<String>,
+    pub skipped: u64, // Lines dropped since the previous batch; shown as a marker before `lines`
+    pub stats: LogStreamStats,
+}
+
+/// Bounded drop-oldest line buffer with size/time based flushing
+#[derive(Debug)]
+pub struct LogBatcher {
+    buffer: VecDeque<String>,
+    capacity: usize,
+    skipped: u64,
+    started: Instant,
+    last_flush: Instant,
+    stats: LogStreamStats,
+}
+
+impl LogBatcher {
+    pub fn new(capacity: usize, now: Instant) -> Self {
+        LogBatcher {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            skipped: 0,
+            started: now,
+            last_flush: now,
+            stats: LogStreamStats::default(),
+        }
+    }
+
+    pub fn push(&mut self, line: String) {
+        self.stats.lines_received += 1;
+        if self.buffer.len() >= self.capacity {
+            self.buffer.pop_front();
+            self.skipped += 1;
+            self.stats.lines_skipped += 1;
+        }
+        self.buffer.push_back(line);
+    }
+
+    /// Size threshold reached and the last flush is far enough in the past
+    pub fn batch_ready(&self, now: Instant) -> bool {
+        self.buffer.len() >= LOG_BATCH_LINES
+            && now.duration_since(self.last_flush) >= LOG_MIN_FLUSH_SPACING
+    }
+
+    /// Drain the buffer into a batch, or `None` when there is nothing to report
+    pub fn flush(&mut self, now: Instant) -> Option<LogBatch> {
+        if self.buffer.is_empty() && self.skipped == 0 {
+            return None;
+        }
+
+        let lines: Vec<String> = self.buffer.drain(..).collect();
+        let skipped = std::mem::take(&mut self.skipped);
+        self.last_flush = now;
+        self.stats.lines_emitted += lines.len() as u64;
+        self.stats.batches += 1;
+        let elapsed = now.duration_since(self.started).as_secs_f64();
+        if elapsed > 0.0 {
+            self.stats.lines_per_second = self.stats.lines_received as f64 / elapsed;
+        }
+
+        Some(LogBatch {
+            lines,
+            skipped,
+            stats: self.stats.clone(),
+        })
+    }
+}
+
+/// Why batched emission stopped before the log stream ended
+#[derive(Debug)]
+pub enum LogPumpError {
+    Read(String), // The log stream failed; it may be reopened
+    Emit(String), // The window is gone; the stream should stop
+}
+
+impl std::fmt::Display for LogPumpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogPumpError::Read(e) => write!(f, "Error reading log line: {}", e),
+            LogPumpError::Emit(e) => write!(f, "Failed to emit log batch: {}", e),
+        }
+    }
+}
+
+/// Read lines from `lines` and emit them to `event_name` as batched `LogBatch` events.
+/// Returns when the log stream ends, fails, or the window is gone.
+pub async fn emit_log_batches<S>(
+    lines: S,
+    window: &Window,
+    event_name: &str,
+) -> Result<(), LogPumpError>
+where
+    S: Stream<Item = std::io::Result<String>>,
+{
+    emit_wrapped_log_batches(lines, window, event_name, |batch| batch).await
+}
+
+/// Like `emit_log_batches`, wrapping each batch into the emitted payload with `wrap`
+pub async fn emit_wrapped_log_batches<S, T, F>(
+    lines: S,
+    window: &Window,
+    event_name: &str,
+    wrap: F,
+) -> Result<(), LogPumpError>
+where
+    S: Stream<Item = std::io::Result<String>>,
+    T: serde::Serialize + Clone,
+    F: Fn(LogBatch) -> T,
+{
+    let mut lines = std::pin::pin!(lines);
+    let mut batcher = LogBatcher::new(LOG_BUFFER_CAPACITY, Instant::now());
+    let mut ticker = tokio::time::interval(LOG_FLUSH_INTERVAL);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    let emit = |batcher: &mut LogBatcher| -> Result<(), LogPumpError> {
+        match batcher.flush(Instant::now()) {
+            Some(batch) => window
+                .emit(event_name, wrap(batch))
+                .map_err(|e| LogPumpError::Emit(e.to_string())),
+            None => Ok(()),
+        }
+    };
+
+    loop {
+        tokio::select! {
+            line = lines.next() => match line {
+                Some(Ok(line)) => {
+                    batcher.push(line);
+                    if batcher.batch_ready(Instant::now()) {
+                        emit(&mut batcher)?;
+                    }
+                }
+                Some(Err(e)) => {
+                    emit(&mut batcher)?;
+                    return Err(LogPumpError::Read(e.to_string()));
+                }
+                None => return emit(&mut batcher),
+            },
+            _ = ticker.tick() => emit(&mut batcher)?,
+        }
+    }
+}
+
+/// First delay between reconnect attempts; doubled per attempt
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound for the reconnect delay
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Consecutive failed reconnects before a stream gives up
+const MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
+/// Lifecycle of a followed log stream, emitted on `container_logs_status_{stream_id}`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum LogStreamStatus {
+    Connected,
+    Reconnecting {
+        attempt: u32,
+        delay_ms: u64,
+        reason: String,
+    },
+    ContainerRestarted {
+        restart_count: i32,
+    },
+    Ended {
+        reason: String,
+    },
+}
+
+/// What to do after a followed log stream closed
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEndAction {
+    Reconnect,
+    Restarted(i32),
+    End(String),
+}
+
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1).min(16));
+    RECONNECT_BASE_DELAY
+        .saturating_mul(factor)
+        .min(RECONNECT_MAX_DELAY)
+}
+
+/// Split the RFC3339 timestamp the API prefixes to each line when `timestamps` is set
+pub fn split_timestamp(line: &str) -> Option<(k8s_openapi::jiff::Timestamp, &str)> {
+    let (ts, rest) = line.split_once(' ').unwrap_or((line, ""));
+    ts.parse().ok().map(|ts| (ts, rest))
+}
+
+/// Tracks the newest line timestamp so a reopened stream can resume without duplicates
+#[derive(Debug, Default)]
+pub struct ResumePoint {
+    pub last_seen: Option<k8s_openapi::jiff::Timestamp>,
+    pub delivered: u64,
+    at_last_seen: usize, // Lines delivered with the `last_seen` timestamp
+    replayed: usize,     // Lines with the `last_seen` timestamp read since the stream reopened
+}
+
+impl ResumePoint {
+    /// Start reading a reopened stream, which replays lines from `last_seen` on
+    pub fn reopen(&mut self) {
+        self.replayed = 0;
+    }
+
+    /// Returns the line to emit, or `None` if it was already delivered
+    pub fn accept(&mut self, line: &str, keep_timestamp: bool) -> Option<String> {
+        let Some((ts, rest)) = split_timestamp(line) else {
+            self.delivered += 1;
+            return Some(line.to_string());
+        };
+        match self.last_seen {
+            Some(last) if ts < last => return None,
+            Some(last) if ts == last => {
+                self.replayed += 1;
+                if self.replayed <= self.at_last_seen {
+                    return None;
+                }
+                self.at_last_seen += 1;
+            }
+            _ => {
+                self.last_seen = Some(ts);
+                self.at_last_seen = 1;
+                self.replayed = 1;
+            }
+        }
+        self.delivered += 1;
+        Some(if keep_timestamp { line } else { rest }.to_string())
+    }
+}
+
+/// Decide how to continue after the log stream of `container` closed
+pub fn classify_stream_end(
+    pod: Option<&Pod>,
+    container: &str,
+    known_restarts: i32,
+) -> StreamEndAction {
+    let Some(pod) = pod else {
+        return StreamEndAction::End("Pod was deleted".to_string());
+    };
+    if pod.metadata.deletion_timestamp.is_some() {
+        return StreamEndAction::End("Pod is terminating".to_string());
+    }
+
+    let Some(status) = pod
+        .status
+        .as_ref()
+        .and_then(|s| s.container_statuses.as_ref())
+        .and_then(|statuses| statuses.iter().find(|cs| cs.name == container))
+    else {
+        return StreamEndAction::Reconnect;
+    };
+
+    if status.restart_count > known_restarts {
+        return StreamEndAction::Restarted(status.restart_count);
+    }
+
+    let Some(terminated) = status.state.as_ref().and_then(|s| s.terminated.as_ref()) else {
+        return StreamEndAction::Reconnect;
+    };
+    let restart_policy = pod
+        .spec
+        .as_ref()
+        .and_then(|s| s.restart_policy.as_deref())
+        .unwrap_or("Always");
+    let restarts = match restart_policy {
+        "Never" => false,
+        "OnFailure" => terminated.exit_code != 0,
+        _ => true,
+    };
+    if restarts {
+        // Wait for the kubelet to start the next instance
+        return StreamEndAction::Reconnect;
+    }
+
+    StreamEndAction::End(format!(
+        "Container terminated: {} (exit code {})",
+        terminated.reason.as_deref().unwrap_or("Completed"),
+        terminated.exit_code
+    ))
+}
+
+fn container_restarts(pod: &Pod, container: &str) -> i32 {
+    pod.status
+        .as_ref()
+        .and_then(|s| s.container_statuses.as_ref())
+        .and_then(|statuses| statuses.iter().find(|cs| cs.name == container))
+        .map(|cs| cs.restart_count)
+        .unwrap_or(0)
+}
+
+/// Follow one container's logs, reopening the stream with backoff after connection
+/// errors and container restarts, and reporting each transition as a status event.
+pub async fn follow_container_logs(
+    pods: Api<Pod>,
+    pod_name: String,
+    container: String,
+    mut log_params: LogParams,
+    window: Window,
+    stream_id: String,
+) {
+    let event_name = format!("container_logs_{}", stream_id);
+    let status_event = format!("container_logs_status_{}", stream_id);
+    let emit_status = |status: LogStreamStatus| {
+        if let Err(e) = window.emit(&status_event, status) {
+            println!("Failed to emit log stream status: {}", e);
+        }
+    };
+
+    // Timestamps are always requested so reconnects can resume; stripped unless asked for
+    let keep_timestamps = log_params.timestamps;
+    log_params.timestamps = true;
+
+    let mut known_restarts = match pods.get_opt(&pod_name).await {
+        Ok(Some(pod)) => container_restarts(&pod, &container),
+        _ => 0,
+    };
+    let mut resume = ResumePoint::default();
+    let mut attempt: u32 = 0;
+
+    loop {
+        let delivered_before = resume.delivered;
+        let reason = match pods.log_stream(&pod_name, &log_params).await {
+            Ok(stream) => {
+                emit_status(LogStreamStatus::Connected);
+                resume.reopen();
+                let lines = stream.lines().filter_map(|line| {
+                    futures::future::ready(match line {
+                        Ok(line) => resume.accept(&line, keep_timestamps).map(Ok),
+                        Err(e) => Some(Err(e)),
+                    })
+                });
+                match emit_log_batches(lines, &window, &event_name).await {
+                    Ok(()) => "Log stream closed".to_string(),
+                    Err(LogPumpError::Read(e)) => format!("Error reading log line: {}", e),
+                    Err(LogPumpError::Emit(e)) => {
+                        println!("Failed to emit log batch: {}", e);
+                        return;
+                    }
+                }
+            }
+            Err(e) => format!("Failed to open log stream: {}", e),
+        };
+
+        if !log_params.follow {
+            emit_status(LogStreamStatus::Ended { reason });
+            return;
+        }
+
+        if resume.delivered != delivered_before {
+            attempt = 0;
+        }
+
+        // An unreachable API is treated as a connection blip
+        let pod = pods.get_opt(&pod_name).await.ok();
+        match pod.map(|pod| classify_stream_end(pod.as_ref(), &container, known_restarts)) {
+            Some(StreamEndAction::End(reason)) => {
+                emit_status(LogStreamStatus::Ended { reason });
+                return;
+            }
+            Some(StreamEndAction::Restarted(restart_count)) => {
+                known_restarts = restart_count;
+                emit_status(LogStreamStatus::ContainerRestarted { restart_count });
+            }
+            Some(StreamEndAction::Reconnect) | None => {}
+        }
+
+        attempt += 1;
+        if attempt > MAX_RECONNECT_ATTEMPTS {
+            emit_status(LogStreamStatus::Ended {
+                reason: format!(
+                    "Gave up after {} reconnect attempts: {}",
+                    MAX_RECONNECT_ATTEMPTS, reason
+                ),
+            });
+            return;
+        }
+
+        let delay = reconnect_delay(attempt);
+        emit_status(LogStreamStatus::Reconnecting {
+            attempt,
+            delay_ms: delay.as_millis() as u64,
+            reason,
+        });
+        tokio::time::sleep(delay).await;
+
+        if let Some(last_seen) = resume.last_seen {
+            log_params.since_time = Some(last_seen);
+            log_params.since_seconds = None;
+            log_params.tail_lines = None;
+        }
+    }
+}
+
+/// Running per-container follow tasks, aborted when dropped
+#[derive(Default)]
+struct ContainerStreams(HashMap<(String, String), (i32, JoinHandle<()>)>);
+
+impl ContainerStreams {
+    fn remove_pod(&mut self, pod: &str) -> bool {
+        let keys: Vec<(String, String)> =
+            self.0.keys().filter(|(p, _)| p == pod).cloned().collect();
+        for key in &keys {
+            if let Some((_, handle)) = self.0.remove(key) {
+                handle.abort();
+            }
+        }
+        !keys.is_empty()
+    }
+
+    /// Stop every followed pod not in `present`, returning the removed pod names
+    fn retain_pods(&mut self, present: &HashSet<String>) -> Vec<String> {
+        let gone = pods_not_present(self.0.keys().map(|(pod, _)| pod.as_str()), present);
+        for pod in &gone {
+            self.remove_pod(pod);
+        }
+        gone
+    }
+}
+
+/// Tracked pod names missing from a re-listed snapshot, deduplicated and sorted
+fn pods_not_present<'a>(
+    tracked: impl Iterator<Item = &'a str>,
+    present: &HashSet<String>,
+) -> Vec<String> {
+    tracked
+        .filter(|pod| !present.contains(*pod))
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+impl Drop for ContainerStreams {
+    fn drop(&mut self) {
+        for (_, handle) in self.0.values() {
+            handle.abort();
+        }
+    }
+}
+
+/// Containers of a pod whose logs can be read right now, with their restart count
+pub fn loggable_containers(pod: &Pod, pattern: Option<&Regex>) -> Vec<(String, i32)> {
+    pod.status
+        .as_ref()
+        .and_then(|s| s.container_statuses.as_ref())
+        .map(|statuses| {
+            statuses
+                .iter()
+                .filter(|cs| pattern.map(|re| re.is_match(&cs.name)).unwrap_or(true))
+                .filter(|cs| {
+                    cs.state
+                        .as_ref()
+                        .map(|s| s.running.is_some() || s.terminated.is_some())
+                        .unwrap_or(false)
+                })
+                .map(|cs| (cs.name.clone(), cs.restart_count))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Label selector string for a workload, read from its `spec.selector`
+async fn workload_label_selector(
+    client: &kube::Client,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+) -> Result<String, String> {
+    let (resource, capabilities) = resolve_api_resource(client, None, kind).await?;
+    let object = dynamic_api(client.clone(), &resource, &capabilities, Some(namespace))
+        .get(name)
+        .await
+        .map_err(|e| format!("Failed to get {} '{}': {}", resource.kind, name, e))?;
+
+    let selector: LabelSelector = object
+        .data
+        .pointer("/spec/selector")
+        .and_then(|s| serde_json::from_value(s.clone()).ok())
+        .ok_or_else(|| format!("{} '{}' has no label selector", resource.kind, name))?;
+
+    let rendered = label_selector_to_string(&selector);
+    if rendered.is_empty() {
+        return Err(format!(
+            "{} '{}' has an empty label selector",
+            resource.kind, name
+        ));
+    }
+    Ok(rendered)
+}
+
+fn spawn_container_stream(
+    pods: Api<Pod>,
+    window: Window,
+    event_name: String,
+    pod: String,
+    container: String,
+    tail_lines: Option<i64>,
+) -> JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let log_params = LogParams {
+            follow: true,
+            tail_lines,
+            container: Some(container.clone()),
+            ..Default::default()
+        };
+
+        match pods.log_stream(&pod, &log_params).await {
+            Ok(stream) => {
+                let wrap = |batch| AggregatedLogEvent::Lines {
+                    pod: pod.clone(),
+                    container: container.clone(),
+                    batch,
+                };
+                if let Err(e) =
+                    emit_wrapped_log_batches(stream.lines(), &window, &event_name, wrap).await
+                {
+                    println!("{}", e);
+                }
+            }
+            Err(e) => {
+                println!("Failed to open log stream for {}/{}: {}", pod, container, e);
+            }
+        }
+    })
+}
+
+/// Follow logs from every pod matching a label selector or workload (stern-style).
+/// Pods are picked up and dropped as the pod watcher reports them.
+#[tauri::command]
+pub async fn cluster_stream_aggregated_logs(
+    params: AggregatedLogStreamParams,
+    window: Window,
+    state: State<'_, ClusterManagerState>,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    let AggregatedLogStreamParams {
+        cluster_id,
+        namespace,
+        label_selector,
+        workload_kind,
+        workload_name,
+        container_pattern,
+        stream_id,
+    } = params;
+
+    let pattern = container_pattern
+        .filter(|p| !p.is_empty())
+        .map(|p| Regex::new(&p).map_err(|e| format!("Invalid container pattern: {}", e)))
+        .transpose()?;
+
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+
+    let selector = match (workload_kind, workload_name, label_selector) {
+        (Some(kind), Some(name), _) => {
+            workload_label_selector(&client, &namespace, &kind, &name).await?
+        }
+        (_, _, Some(selector)) if !selector.trim().is_empty() => selector,
+        _ => return Err("Either a workload or a label selector is required".to_string()),
+    };
+
+    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+    let config = watcher::Config::default().labels(&selector);
+    let event_name = format!("aggregated_logs_{}", stream_id);
+    let info = TaskInfo::new(
+        format!("logs:{}", stream_id),
+        "logs",
+        Some(&cluster_id),
+        &window,
+        format!("{} pods matching '{}'", namespace, selector),
+    );
+
+    tasks.spawn(info, async move {
+        let mut streams = ContainerStreams::default();
+        let mut initial_sync = true;
+        // Pods reported by the current (re-)list, to drop those deleted while disconnected
+        let mut listed = HashSet::new();
+        let mut stream = watcher(pods.clone(), config).boxed();
+
+        while let Some(result) = stream.next().await {
+            let pod = match result {
+                Ok(watcher::Event::Init) => {
+                    initial_sync = true;
+                    listed.clear();
+                    continue;
+                }
+                Ok(watcher::Event::InitDone) => {
+                    initial_sync = false;
+                    for pod in streams.retain_pods(&std::mem::take(&mut listed)) {
+                        let _ = window.emit(&event_name, AggregatedLogEvent::PodRemoved { pod });
+                    }
+                    continue;
+                }
+                Ok(watcher::Event::Delete(pod)) => {
+                    let name = pod.metadata.name.unwrap_or_default();
+                    if streams.remove_pod(&name) {
+                        let _ =
+                            window.emit(&event_name, AggregatedLogEvent::PodRemoved { pod: name });
+                    }
+                    continue;
+                }
+                Ok(watcher::Event::InitApply(pod)) => {
+                    listed.insert(pod.metadata.name.clone().unwrap_or_default());
+                    pod
+                }
+                Ok(watcher::Event::Apply(pod)) => pod,
+                Err(e) => {
+                    println!("Watch error: {}", e);
+                    continue;
+                }
+            };
+
+            let name = pod.metadata.name.clone().unwrap_or_default();
+            if pod.metadata.deletion_timestamp.is_some() {
+                if streams.remove_pod(&name) {
+                    let _ = window.emit(&event_name, AggregatedLogEvent::PodRemoved { pod: name });
+                }
+                continue;
+            }
+
+            let mut added = Vec::new();
+            for (container, restarts) in loggable_containers(&pod, pattern.as_ref()) {
+                let key = (name.clone(), container.clone());
+                let tail_lines = match streams.0.get(&key) {
+                    // Same container instance still being followed
+                    Some((known, _)) if *known == restarts => continue,
+                    // Restarted container: read the new instance from the start
+                    Some(_) => None,
+                    // New pods read their whole log; pods present at startup only the tail
+                    None if initial_sync => Some(AGGREGATED_TAIL_LINES),
+                    None => None,
+                };
+                if let Some((_, old)) = streams.0.remove(&key) {
+                    old.abort();
+                }
+
+                let handle = spawn_container_stream(
+                    pods.clone(),
+                    window.clone(),
+                    event_name.clone(),
+                    name.clone(),
+                    container.clone(),
+                    tail_lines,
+                );
+                streams.0.insert(key, (restarts, handle));
+                added.push(container);
+            }
+
+            if !added.is_empty() {
+                let event = AggregatedLogEvent::PodAdded {
+                    pod: name,
+                    containers: added,
+                };
+                if let Err(e) = window.emit(&event_name, event) {
+                    println!("Failed to emit event: {}", e);
+                    break;
+                }
+            }
+        }
+    })
+}
+
+/// Stop a cluster log stream (single container or aggregated)
+#[tauri::command]
+pub async fn cluster_stop_stream_logs(
+    stream_id: String,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    tasks.stop(&format!("logs:{}", stream_id))?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{
+        ContainerState, ContainerStateRunning, ContainerStateWaiting, ContainerStatus, PodStatus,
+    };
+    use k8s_openapi::api::core::v1::{ContainerStateTerminated, PodSpec};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelectorRequirement;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
+    use std::collections::BTreeMap;
+
+    fn pod_with(restart_policy: &str, status: ContainerStatus) -> Pod {
+        Pod {
+            spec: Some(PodSpec {
+                restart_policy: Some(restart_policy.to_string()),
+                ..Default::default()
+            }),
+            status: Some(PodStatus {
+                container_statuses: Some(vec![status]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn terminated(name: &str, exit_code: i32) -> ContainerStatus {
+        ContainerStatus {
+            name: name.to_string(),
+            state: Some(ContainerState {
+                terminated: Some(ContainerStateTerminated {
+                    exit_code,
+                    reason: Some("Error".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reconnect_delay_backs_off_and_caps() {
+        assert_eq!(reconnect_delay(1), Duration::from_secs(1));
+        assert_eq!(reconnect_delay(3), Duration::from_secs(4));
+        assert_eq!(reconnect_delay(10), RECONNECT_MAX_DELAY);
+        assert_eq!(reconnect_delay(100), RECONNECT_MAX_DELAY);
+    }
+
+    #[test]
+    fn resume_point_strips_timestamps_and_skips_replayed_lines() {
+        let mut resume = ResumePoint::default();
+        assert_eq!(
+            resume.accept("2024-05-01T10:00:00.100Z first", false),
+            Some("first".to_string())
+        );
+        assert_eq!(
+            resume.accept("2024-05-01T10:00:00.200Z second", true),
+            Some("2024-05-01T10:00:00.200Z second".to_string())
+        );
+
+        // Reopened stream replays from the start of the second
+        resume.reopen();
+        assert_eq!(resume.accept("2024-05-01T10:00:00.100Z first", false), None);
+        assert_eq!(
+            resume.accept("2024-05-01T10:00:00.200Z second", false),
+            None
+        );
+        assert_eq!(
+            resume.accept("2024-05-01T10:00:01Z third", false),
+            Some("third".to_string())
+        );
+        assert_eq!(
+            resume.accept("no timestamp", false),
+            Some("no timestamp".to_string())
+        );
+    }
+
+    #[test]
+    fn resume_point_keeps_new_lines_sharing_the_last_timestamp() {
+        let mut resume = ResumePoint::default();
+        assert!(resume.accept("2024-05-01T10:00:00Z a", false).is_some());
+        assert!(resume.accept("2024-05-01T10:00:00Z b", false).is_some());
+
+        // The reopened stream replays both lines, then continues within the same second
+        resume.reopen();
+        assert_eq!(resume.accept("2024-05-01T10:00:00Z a", false), None);
+        assert_eq!(resume.accept("2024-05-01T10:00:00Z b", false), None);
+        assert_eq!(
+            resume.accept("2024-05-01T10:00:00Z c", false),
+            Some("c".to_string())
+        );
+
+        resume.reopen();
+        assert_eq!(resume.accept("2024-05-01T10:00:00Z a", false), None);
+        assert_eq!(resume.accept("2024-05-01T10:00:00Z b", false), None);
+        assert_eq!(resume.accept("2024-05-01T10:00:00Z c", false), None);
+        assert_eq!(resume.delivered, 3);
+    }
+
+    #[test]
+    fn pods_missing_from_relist_are_reported_once() {
+        let present: HashSet<String> = ["web-1".to_string()].into();
+        let tracked = ["web-2", "web-1", "web-2", "web-0"];
+        assert_eq!(
+            pods_not_present(tracked.into_iter(), &present),
+            vec!["web-0".to_string(), "web-2".to_string()]
+        );
+    }
+
+    #[test]
+    fn classify_stream_end_detects_restarts_and_final_states() {
+        assert_eq!(
+            classify_stream_end(None, "app", 0),
+            StreamEndAction::End("Pod was deleted".to_string())
+        );
+
+        let restarted = pod_with("Always", status("app", true, 3));
+        assert_eq!(
+            classify_stream_end(Some(&restarted), "app", 2),
+            StreamEndAction::Restarted(3)
+        );
+        assert_eq!(
+            classify_stream_end(Some(&restarted), "app", 3),
+            StreamEndAction::Reconnect
+        );
+
+        let crashed = pod_with("Always", terminated("app", 1));
+        assert_eq!(
+            classify_stream_end(Some(&crashed), "app", 0),
+            StreamEndAction::Reconnect
+        );
+
+        let finished = pod_with("OnFailure", terminated("app", 0));
+        assert_eq!(
+            classify_stream_end(Some(&finished), "app", 0),
+            StreamEndAction::End("Container terminated: Error (exit code 0)".to_string())
+        );
+
+        let mut terminating = pod_with("Always", status("app", true, 0));
+        terminating.metadata = ObjectMeta {
+            deletion_timestamp: Some(Time(k8s_openapi::jiff::Timestamp::UNIX_EPOCH)),
+            ..Default::default()
+        };
+        assert_eq!(
+            classify_stream_end(Some(&terminating), "app", 0),
+            StreamEndAction::End("Pod is terminating".to_string())
+        );
+    }
+
+    #[test]
+    fn log_batcher_drops_oldest_and_reports_skipped() {
+        let start = Instant::now();
+        let mut batcher = LogBatcher::new(3, start);
+        for i in 0..5 {
+            batcher.push(format!("line {}", i));
+        }
+
+        let batch = batcher.flush(start + Duration::from_secs(1)).unwrap();
+        assert_eq!(batch.lines, vec!["line 2", "line 3", "line 4"]);
+        assert_eq!(batch.skipped, 2);
+        assert_eq!(batch.stats.lines_received, 5);
+        assert_eq!(batch.stats.lines_emitted, 3);
+        assert_eq!(batch.stats.lines_skipped, 2);
+        assert!((batch.stats.lines_per_second - 5.0).abs() < f64::EPSILON);
+
+        assert!(batcher.flush(start + Duration::from_secs(2)).is_none());
+    }
+
+    #[test]
+    fn log_batcher_flushes_early_only_when_spaced() {
+        let start = Instant::now();
+        let mut batcher = LogBatcher::new(LOG_BUFFER_CAPACITY, start);
+        for i in 0..LOG_BATCH_LINES {
+            batcher.push(i.to_string());
+        }
+        assert!(!batcher.batch_ready(start));
+        assert!(batcher.batch_ready(start + LOG_MIN_FLUSH_SPACING));
+    }
+
+    #[test]
+    fn log_options_default_to_following_the_tail() {
+        let params = LogStreamOptions::default().to_log_params("app").unwrap();
+        assert!(params.follow);
+        assert_eq!(params.tail_lines, Some(DEFAULT_TAIL_LINES));
+        assert_eq!(params.container.as_deref(), Some("app"));
+    }
+
+    #[test]
+    fn log_options_map_since_previous_and_tail() {
+        let options = LogStreamOptions {
+            since_time: Some("2024-05-01T10:00:00Z".to_string()),
+            previous: true,
+            timestamps: true,
+            ..Default::default()
+        };
+        let params = options.to_log_params("app").unwrap();
+        assert!(!params.follow);
+        assert!(params.previous && params.timestamps);
+        assert_eq!(params.tail_lines, None);
+        assert_eq!(
+            params.since_time.unwrap().to_string(),
+            "2024-05-01T10:00:00Z"
+        );
+
+        let whole_log = LogStreamOptions {
+            tail_lines: Some(-1),
+            follow: Some(false),
+            ..Default::default()
+        };
+        let params = whole_log.to_log_params("app").unwrap();
+        assert_eq!(params.tail_lines, None);
+        assert!(!params.follow);
+    }
+
+    #[test]
+    fn log_options_reject_invalid_combinations() {
+        let both = LogStreamOptions {
+            since_seconds: Some(60),
+            since_time: Some("2024-05-01T10:00:00Z".to_string()),
+            ..Default::default()
+        };
+        assert!(both.to_log_params("app").is_err());
+
+        let bad_time = LogStreamOptions {
+            since_time: Some("yesterday".to_string()),
+            ..Default::default()
+        };
+        assert!(bad_time.to_log_params("app").is_err());
+
+        let bad_limit = LogStreamOptions {
+            limit_bytes: Some(0),
+            ..Default::default()
+        };
+        assert!(bad_limit.to_log_params("app").is_err());
+    }
+
+    fn status(name: &str, running: bool, restarts: i32) -> ContainerStatus {
+        ContainerStatus {
+            name: name.to_string(),
+            restart_count: restarts,
+            state: Some(if running {
+                ContainerState {
+                    running: Some(ContainerStateRunning::default()),
+                    ..Default::default()
+                }
+            } else {
+                ContainerState {
+                    waiting: Some(ContainerStateWaiting::default()),
+                    ..Default::default()
+                }
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn loggable_containers_filters_state_and_pattern() {
+        let pod = Pod {
+            status: Some(PodStatus {
+                container_statuses: Some(vec![
+                    status("app", true, 2),
+                    status("istio-proxy", true, 0),
+                    status("pending", false, 0),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            loggable_containers(&pod, None),
+            vec![("app".to_string(), 2), ("istio-proxy".to_string(), 0)]
+        );
+        let re = Regex::new("^app$").unwrap();
+        assert_eq!(
+            loggable_containers(&pod, Some(&re)),
+            vec![("app".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn label_selector_renders_query_syntax() {
+        let selector = LabelSelector {
+            match_labels: Some(BTreeMap::from([("app".to_string(), "web".to_string())])),
+            match_expressions: Some(vec![
+                LabelSelectorRequirement {
+                    key: "tier".to_string(),
+                    operator: "In".to_string(),
+                    values: Some(vec!["a".to_string(), "b".to_string()]),
+                },
+                LabelSelectorRequirement {
+                    key: "canary".to_string(),
+                    operator: "DoesNotExist".to_string(),
+                    values: None,
+                },
+            ]),
+        };
+        assert_eq!(
+            label_selector_to_string(&selector),
+            "app=web,tier in (a,b),!canary"
+        );
+    }
+}
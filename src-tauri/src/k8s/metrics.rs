@@ -1,6 +1,8 @@
 use crate::cluster_manager::ClusterManagerState;
 use crate::k8s::client::create_client_for_cluster;
 use crate::k8s::common::{calculate_age, get_created_at};
+use crate::k8s::event_watch::{event_last_seen, summarize_core_event};
+use crate::k8s::selectors::selector_list_params;
 use k8s_openapi::api::core::v1::{Event, Node, Pod};
 use kube::api::Api;
 use tauri::State;
@@ -30,7 +32,7 @@ pub struct WarningEvent {
     pub count: i32,
 }
 
-#[derive(serde::Serialize, Debug)]
+#[derive(serde::Serialize, Clone, Debug)]
 pub struct ClusterEventSummary {
     pub id: String,
     pub name: String,
@@ -45,6 +47,9 @@ pub struct ClusterEventSummary {
     pub message: String,
     pub object: String,
     pub count: i32,
+    pub source: String, // reportingController, or the legacy source component
+    pub first_timestamp: Option<String>,
+    pub last_timestamp: Option<String>, // Latest occurrence, including series observations
 }
 
 #[derive(serde::Serialize, Debug)]
@@ -72,7 +77,7 @@ pub struct NodeSummary {
     pub allocatable_pods: String,
 }
 
-fn parse_cpu(q: &str) -> f64 {
+pub(crate) fn parse_cpu(q: &str) -> f64 {
     if q.ends_with('m') {
         q.trim_end_matches('m').parse::<f64>().unwrap_or(0.0) / 1000.0
     } else {
@@ -80,7 +85,7 @@ fn parse_cpu(q: &str) -> f64 {
     }
 }
 
-fn parse_memory(q: &str) -> f64 {
+pub(crate) fn parse_memory(q: &str) -> f64 {
     let q = q.trim();
     if let Some(val) = q.strip_suffix("Ki") {
         val.parse::<f64>().unwrap_or(0.0) * 1024.0
@@ -90,6 +95,14 @@ fn parse_memory(q: &str) -> f64 {
         val.parse::<f64>().unwrap_or(0.0) * 1024.0f64.powi(3)
     } else if let Some(val) = q.strip_suffix("Ti") {
         val.parse::<f64>().unwrap_or(0.0) * 1024.0f64.powi(4)
+    } else if let Some(val) = q.strip_suffix("k") {
+        val.parse::<f64>().unwrap_or(0.0) * 1e3
+    } else if let Some(val) = q.strip_suffix("M") {
+        val.parse::<f64>().unwrap_or(0.0) * 1e6
+    } else if let Some(val) = q.strip_suffix("G") {
+        val.parse::<f64>().unwrap_or(0.0) * 1e9
+    } else if let Some(val) = q.strip_suffix("T") {
+        val.parse::<f64>().unwrap_or(0.0) * 1e12
     } else if let Some(val) = q.strip_suffix("m") {
         val.parse::<f64>().unwrap_or(0.0) / 1000.0
     } else {
@@ -97,20 +110,14 @@ fn parse_memory(q: &str) -> f64 {
     }
 }
 
-fn parse_rfc3339_ts(ts: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+pub(crate) fn parse_rfc3339_ts(ts: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     chrono::DateTime::parse_from_rfc3339(ts)
         .ok()
         .map(|dt| dt.with_timezone(&chrono::Utc))
 }
 
-fn format_event_age(
-    last_timestamp: Option<&k8s_openapi::apimachinery::pkg::apis::meta::v1::Time>,
-) -> String {
-    let Some(last_ts) = last_timestamp else {
-        return "-".to_string();
-    };
-
-    let Some(last_ts_parsed) = parse_rfc3339_ts(&last_ts.0.to_string()) else {
+pub(crate) fn format_event_age(last_timestamp: Option<&str>) -> String {
+    let Some(last_ts_parsed) = last_timestamp.and_then(parse_rfc3339_ts) else {
         return "-".to_string();
     };
 
@@ -327,29 +334,27 @@ pub async fn cluster_get_events(
     let lp = kube::api::ListParams::default();
     let event_list = events.list(&lp).await.map_err(|e| e.to_string())?;
 
-    let mut warnings = Vec::new();
-
-    for e in event_list.items {
-        if e.type_.as_deref() == Some("Warning") {
-            let age = format_event_age(e.last_timestamp.as_ref());
-
-            warnings.push(WarningEvent {
-                message: e.message.unwrap_or_default(),
-                object: format!(
-                    "{}/{}",
-                    e.involved_object.kind.unwrap_or_default(),
-                    e.involved_object.name.unwrap_or_default()
-                ),
-                type_: e.type_.unwrap_or_default(),
-                age,
-                count: e.count.unwrap_or(1),
-            });
-        }
-    }
+    let mut summaries: Vec<ClusterEventSummary> = event_list
+        .items
+        .into_iter()
+        .filter(|e| e.type_.as_deref() == Some("Warning"))
+        .map(summarize_core_event)
+        .collect();
 
-    // Limit to 50 most recent warnings
-    warnings.reverse();
-    warnings.truncate(50);
+    // Limit to the 50 most recently seen warnings
+    summaries.sort_by_key(|s| std::cmp::Reverse(event_last_seen(s)));
+    summaries.truncate(50);
+
+    let warnings = summaries
+        .into_iter()
+        .map(|s| WarningEvent {
+            message: s.message,
+            object: s.object,
+            type_: s.event_type,
+            age: s.age,
+            count: s.count,
+        })
+        .collect();
 
     Ok(warnings)
 }
@@ -359,8 +364,11 @@ pub async fn cluster_list_events(
     cluster_id: String,
     namespace: Option<String>,
     include_normal: Option<bool>,
+    label_selector: Option<String>,
+    field_selector: Option<String>,
     state: State<'_, ClusterManagerState>,
 ) -> Result<Vec<ClusterEventSummary>, String> {
+    let list_params = selector_list_params(label_selector.as_deref(), field_selector.as_deref())?;
     let client = create_client_for_cluster(&cluster_id, &state).await?;
     let events: Api<Event> = if let Some(ns) = namespace.clone() {
         Api::namespaced(client, &ns)
@@ -369,53 +377,16 @@ pub async fn cluster_list_events(
     };
 
     let include_normal = include_normal.unwrap_or(true);
-    let event_list = events
-        .list(&kube::api::ListParams::default())
-        .await
-        .map_err(|e| e.to_string())?;
+    let event_list = events.list(&list_params).await.map_err(|e| e.to_string())?;
 
     let mut summaries: Vec<ClusterEventSummary> = event_list
         .items
         .into_iter()
         .filter(|e| include_normal || e.type_.as_deref() == Some("Warning"))
-        .map(|e| {
-            let meta = e.metadata;
-            let name = meta.name.clone().unwrap_or_default();
-            let event_type = e.type_.clone().unwrap_or_else(|| "Normal".to_string());
-            let reason = e.reason.clone().unwrap_or_default();
-            let message = e.message.clone().unwrap_or_default();
-            let object = format!(
-                "{}/{}",
-                e.involved_object.kind.clone().unwrap_or_default(),
-                e.involved_object.name.clone().unwrap_or_default()
-            );
-            let event_namespace = meta
-                .namespace
-                .clone()
-                .or(e.involved_object.namespace.clone())
-                .unwrap_or_else(|| "-".to_string());
-            let age = format_event_age(e.last_timestamp.as_ref().or(e.first_timestamp.as_ref()));
-            let created_at = get_created_at(meta.creation_timestamp.as_ref());
-
-            ClusterEventSummary {
-                id: meta.uid.clone().unwrap_or_else(|| name.clone()),
-                name,
-                namespace: event_namespace,
-                age,
-                labels: meta.labels.unwrap_or_default(),
-                status: format!("{}: {}", event_type, reason),
-                images: vec![],
-                created_at,
-                event_type,
-                reason,
-                message,
-                object,
-                count: e.count.unwrap_or(1),
-            }
-        })
+        .map(summarize_core_event)
         .collect();
 
-    summaries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    summaries.sort_by_key(|s| std::cmp::Reverse(event_last_seen(s)));
     Ok(summaries)
 }
 
@@ -489,6 +460,13 @@ mod tests {
         assert_eq!(result, 1024.0_f64.powi(4));
     }
 
+    #[test]
+    fn test_parse_memory_decimal_suffixes() {
+        assert_eq!(parse_memory("500k"), 500e3);
+        assert_eq!(parse_memory("128M"), 128e6);
+        assert_eq!(parse_memory("1G"), 1e9);
+    }
+
     #[test]
     fn test_parse_memory_bytes() {
         let result = parse_memory("1000000");
@@ -1,20 +1,58 @@
+pub mod alerts;
+pub mod broken_refs;
 pub mod client;
 pub mod common;
 pub mod deployment;
+pub mod diagnose;
+pub mod dynamic;
+pub mod event_watch;
 pub mod helm;
+pub mod lint;
+pub mod log_query;
+pub mod logs;
 pub mod metrics;
+pub mod multi_cluster;
 pub mod mutate;
+pub mod network_policy;
 pub mod pod;
+pub mod rbac;
+pub mod relationships;
+pub mod resource_cache;
+pub mod resource_copy;
+pub mod resource_diff;
+pub mod resource_list;
+pub mod resource_watch;
+pub mod scheduling;
+pub mod search;
+pub mod selectors;
 pub mod statefulset;
-pub mod watcher;
+pub mod tasks;
 pub mod workload;
 
+pub use alerts::*;
+pub use broken_refs::*;
 pub use client::*;
 pub use deployment::*;
+pub use diagnose::*;
+pub use event_watch::*;
 pub use helm::*;
+pub use lint::*;
+pub use log_query::*;
+pub use logs::*;
 pub use metrics::*;
+pub use multi_cluster::*;
 pub use mutate::*;
+pub use network_policy::*;
 pub use pod::*;
+pub use rbac::*;
+pub use relationships::*;
+pub use resource_cache::*;
+pub use resource_copy::*;
+pub use resource_diff::*;
+pub use resource_list::*;
+pub use resource_watch::*;
+pub use scheduling::*;
+pub use search::*;
 pub use statefulset::*;
-pub use watcher::*;
+pub use tasks::*;
 pub use workload::*;
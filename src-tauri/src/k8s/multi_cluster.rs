@@ -0,0 +1,336 @@
+use crate::cluster_manager::{Cluster, ClusterManagerState};
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::WorkloadSummary;
+use crate::k8s::pod::{map_pod_to_summary, PodSummary};
+use crate::k8s::resource_list::{list_all_pages, list_all_summaries, summary_api};
+use crate::k8s::selectors::selector_list_params;
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::ListParams;
+use kube::{Api, Client};
+use std::time::{Duration, Instant};
+use tauri::State;
+
+/// An unreachable cluster must not hold up the others for long
+const FANOUT_CLUSTER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A cluster picked for a fan-out, or an id that matches no known cluster
+#[derive(Debug, Clone)]
+enum SelectedCluster {
+    Known(Cluster),
+    Unknown(String),
+}
+
+impl SelectedCluster {
+    fn id(&self) -> &str {
+        match self {
+            SelectedCluster::Known(cluster) => &cluster.id,
+            SelectedCluster::Unknown(id) => id,
+        }
+    }
+}
+
+/// Pick clusters by explicit ids and/or tag. Ids keep their given order; tag
+/// matches follow in list order. Unknown ids are kept so they can be reported
+/// per cluster without failing the others.
+fn select_clusters(
+    clusters: Vec<Cluster>,
+    cluster_ids: &[String],
+    tag: Option<&str>,
+) -> Result<Vec<SelectedCluster>, String> {
+    if cluster_ids.is_empty() && tag.is_none() {
+        return Err("Select clusters by id or by tag".to_string());
+    }
+
+    let mut selected: Vec<SelectedCluster> = Vec::new();
+    for id in cluster_ids {
+        if selected.iter().any(|c| c.id() == id) {
+            continue;
+        }
+        selected.push(match clusters.iter().find(|c| &c.id == id) {
+            Some(cluster) => SelectedCluster::Known(cluster.clone()),
+            None => SelectedCluster::Unknown(id.clone()),
+        });
+    }
+    if let Some(tag) = tag {
+        for cluster in clusters {
+            let tagged = cluster
+                .tag_list()
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(tag));
+            if tagged && !selected.iter().any(|c| c.id() == cluster.id) {
+                selected.push(SelectedCluster::Known(cluster));
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        return Err(format!(
+            "No clusters are tagged '{}'",
+            tag.unwrap_or_default()
+        ));
+    }
+    Ok(selected)
+}
+
+fn resolve_clusters(
+    state: &State<'_, ClusterManagerState>,
+    cluster_ids: &[String],
+    tag: Option<&str>,
+) -> Result<Vec<SelectedCluster>, String> {
+    let clusters = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to acquire lock: {}", e))?
+        .list_clusters()?;
+    select_clusters(clusters, cluster_ids, tag)
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct FanoutListParams {
+    #[serde(default)]
+    pub cluster_ids: Vec<String>,
+    pub tag: Option<String>,
+    pub kind: String,
+    pub api_version: Option<String>,
+    pub namespace: Option<String>, // None lists all namespaces
+    pub label_selector: Option<String>,
+    pub field_selector: Option<String>,
+}
+
+/// The list-view summary of a row, so Pods render like the Pods list
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum FanoutSummary {
+    Pod(Box<PodSummary>),
+    Workload(WorkloadSummary),
+}
+
+/// A list row annotated with the cluster it came from
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FanoutRow {
+    pub cluster_id: String,
+    pub cluster_name: String,
+    #[serde(flatten)]
+    pub item: FanoutSummary,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FanoutClusterStatus {
+    pub cluster_id: String,
+    pub cluster_name: String,
+    pub item_count: usize,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FanoutListResult {
+    pub rows: Vec<FanoutRow>,
+    pub clusters: Vec<FanoutClusterStatus>, // One entry per selected cluster, in selection order
+}
+
+fn is_pod_kind(params: &FanoutListParams) -> bool {
+    params.kind.eq_ignore_ascii_case("Pod")
+        && params
+            .api_version
+            .as_deref()
+            .is_none_or(|v| v.is_empty() || v == "v1")
+}
+
+async fn list_pods(
+    client: Client,
+    namespace: Option<&str>,
+    list_params: ListParams,
+) -> Result<Vec<FanoutSummary>, String> {
+    let api: Api<Pod> = match namespace {
+        Some(ns) => Api::namespaced(client, ns),
+        None => Api::all(client),
+    };
+    let api = &api;
+    list_all_pages(list_params, |params| async move {
+        let list = api.list(&params).await?;
+        let items = list
+            .items
+            .into_iter()
+            .map(|pod| FanoutSummary::Pod(Box::new(map_pod_to_summary(pod))))
+            .collect();
+        Ok((items, list.metadata.continue_))
+    })
+    .await
+    .map_err(|e| format!("Failed to list pods: {}", e))
+}
+
+async fn list_in_cluster(
+    cluster_id: &str,
+    params: &FanoutListParams,
+    list_params: ListParams,
+    state: &State<'_, ClusterManagerState>,
+) -> Result<Vec<FanoutSummary>, String> {
+    let client = create_client_for_cluster(cluster_id, state).await?;
+    let namespace = params.namespace.as_deref().filter(|ns| !ns.is_empty());
+    if is_pod_kind(params) {
+        return list_pods(client, namespace, list_params).await;
+    }
+    let api = summary_api(
+        client,
+        &params.kind,
+        params.api_version.as_deref(),
+        namespace,
+    )
+    .await?;
+    let items = list_all_summaries(api.as_ref(), list_params)
+        .await
+        .map_err(|e| format!("Failed to list {}: {}", params.kind, e))?;
+    Ok(items
+        .into_iter()
+        .map(|(summary, _)| FanoutSummary::Workload(summary))
+        .collect())
+}
+
+/// List a kind across several clusters concurrently. Failures are reported per
+/// cluster in `clusters` instead of failing the whole call.
+#[tauri::command]
+pub async fn multi_cluster_list_resources(
+    params: FanoutListParams,
+    state: State<'_, ClusterManagerState>,
+) -> Result<FanoutListResult, String> {
+    let clusters = resolve_clusters(&state, &params.cluster_ids, params.tag.as_deref())?;
+    let list_params = selector_list_params(
+        params.label_selector.as_deref(),
+        params.field_selector.as_deref(),
+    )?;
+
+    let listings = clusters.iter().map(|cluster| {
+        let list_params = list_params.clone();
+        let params = &params;
+        let state = &state;
+        async move {
+            let SelectedCluster::Known(cluster) = cluster else {
+                let error = format!("Cluster '{}' not found", cluster.id());
+                return (Err(error), Duration::ZERO);
+            };
+            let started = Instant::now();
+            let result = tokio::time::timeout(
+                FANOUT_CLUSTER_TIMEOUT,
+                list_in_cluster(&cluster.id, params, list_params, state),
+            )
+            .await
+            .unwrap_or_else(|_| {
+                Err(format!(
+                    "Timed out after {}s",
+                    FANOUT_CLUSTER_TIMEOUT.as_secs()
+                ))
+            });
+            (result, started.elapsed())
+        }
+    });
+    let outcomes = futures::future::join_all(listings).await;
+
+    let mut rows = Vec::new();
+    let mut statuses = Vec::new();
+    for (selected, (result, elapsed)) in clusters.into_iter().zip(outcomes) {
+        let (cluster_id, cluster_name) = match selected {
+            SelectedCluster::Known(cluster) => (cluster.id, cluster.name),
+            SelectedCluster::Unknown(id) => (id.clone(), id),
+        };
+        let (item_count, error) = match result {
+            Ok(items) => {
+                let count = items.len();
+                rows.extend(items.into_iter().map(|item| FanoutRow {
+                    cluster_id: cluster_id.clone(),
+                    cluster_name: cluster_name.clone(),
+                    item,
+                }));
+                (count, None)
+            }
+            Err(error) => (0, Some(error)),
+        };
+        statuses.push(FanoutClusterStatus {
+            cluster_id,
+            cluster_name,
+            item_count,
+            error,
+            duration_ms: elapsed.as_millis() as u64,
+        });
+    }
+
+    Ok(FanoutListResult {
+        rows,
+        clusters: statuses,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn cluster(id: &str, tags: &[&str]) -> Cluster {
+        Cluster {
+            id: id.to_string(),
+            name: format!("cluster-{}", id),
+            context_name: id.to_string(),
+            config_path: String::new(),
+            icon: None,
+            description: None,
+            tags: serde_json::to_string(tags).unwrap(),
+            created_at: 0,
+            last_accessed: 0,
+            credentials_expires_at: None,
+        }
+    }
+
+    fn ids(clusters: &[SelectedCluster]) -> Vec<&str> {
+        clusters.iter().map(SelectedCluster::id).collect()
+    }
+
+    #[test]
+    fn test_select_clusters_by_tag() {
+        let clusters = vec![
+            cluster("eu", &["prod", "eu"]),
+            cluster("us", &["Prod"]),
+            cluster("dev", &["dev"]),
+        ];
+        let selected = select_clusters(clusters, &[], Some("prod")).unwrap();
+        assert_eq!(ids(&selected), vec!["eu", "us"]);
+    }
+
+    #[test]
+    fn test_select_clusters_by_ids_and_tag_without_duplicates() {
+        let clusters = vec![
+            cluster("eu", &["prod"]),
+            cluster("us", &["prod"]),
+            cluster("dev", &["dev"]),
+        ];
+        let selected = select_clusters(
+            clusters,
+            &["dev".to_string(), "us".to_string()],
+            Some("prod"),
+        )
+        .unwrap();
+        assert_eq!(ids(&selected), vec!["dev", "us", "eu"]);
+    }
+
+    #[test]
+    fn test_select_clusters_errors() {
+        let clusters = vec![cluster("eu", &["prod"])];
+        assert!(select_clusters(clusters.clone(), &[], None).is_err());
+        assert!(select_clusters(clusters, &[], Some("staging")).is_err());
+    }
+
+    #[test]
+    fn test_unknown_cluster_ids_are_kept_for_reporting() {
+        let clusters = vec![cluster("eu", &["prod"]), cluster("us", &["prod"])];
+        let selected = select_clusters(clusters, &["nope".to_string()], Some("prod")).unwrap();
+        assert_eq!(ids(&selected), vec!["nope", "eu", "us"]);
+        assert!(matches!(selected[0], SelectedCluster::Unknown(_)));
+        assert!(matches!(selected[1], SelectedCluster::Known(_)));
+    }
+
+    #[test]
+    fn test_malformed_tags_are_ignored() {
+        let mut broken = cluster("eu", &[]);
+        broken.tags = "not json".to_string();
+        assert!(broken.tag_list().is_empty());
+    }
+}
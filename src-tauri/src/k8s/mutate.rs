@@ -17,15 +17,15 @@ fn get_cluster_kubeconfig_and_context(
     Ok((cluster.config_path, cluster.context_name))
 }
 
-#[tauri::command]
-pub async fn cluster_get_resource_yaml(
-    cluster_id: String,
-    kind: String,
-    name: String,
-    namespace: Option<String>,
-    state: State<'_, ClusterManagerState>,
+/// Fetch an object as YAML with kubectl, which resolves kind aliases ("deploy", "svc", CRDs)
+pub(crate) fn get_resource_yaml(
+    cluster_id: &str,
+    kind: &str,
+    name: &str,
+    namespace: Option<&str>,
+    state: &State<'_, ClusterManagerState>,
 ) -> Result<String, String> {
-    let (kubeconfig, context_name) = get_cluster_kubeconfig_and_context(&cluster_id, &state)?;
+    let (kubeconfig, context_name) = get_cluster_kubeconfig_and_context(cluster_id, state)?;
 
     let mut cmd = Command::new("kubectl");
     cmd.args([
@@ -34,15 +34,15 @@ pub async fn cluster_get_resource_yaml(
         "--context",
         &context_name,
         "get",
-        &kind,
-        &name,
+        kind,
+        name,
         "-o",
         "yaml",
     ]);
 
     if let Some(ns) = namespace {
         if !ns.is_empty() && ns != "-" {
-            cmd.args(["-n", &ns]);
+            cmd.args(["-n", ns]);
         }
     }
 
@@ -61,23 +61,41 @@ pub async fn cluster_get_resource_yaml(
 }
 
 #[tauri::command]
-pub async fn cluster_apply_resource_yaml(
+pub async fn cluster_get_resource_yaml(
     cluster_id: String,
-    yaml: String,
+    kind: String,
+    name: String,
+    namespace: Option<String>,
     state: State<'_, ClusterManagerState>,
 ) -> Result<String, String> {
-    let (kubeconfig, context_name) = get_cluster_kubeconfig_and_context(&cluster_id, &state)?;
+    get_resource_yaml(&cluster_id, &kind, &name, namespace.as_deref(), &state)
+}
 
-    let mut child = Command::new("kubectl")
-        .args([
-            "--kubeconfig",
-            &kubeconfig,
-            "--context",
-            &context_name,
-            "apply",
-            "-f",
-            "-",
-        ])
+/// Pipe manifests to `kubectl apply`. With `server_dry_run` the API server
+/// validates and admits the objects without persisting them.
+pub(crate) fn apply_resource_yaml(
+    cluster_id: &str,
+    yaml: &str,
+    server_dry_run: bool,
+    state: &State<'_, ClusterManagerState>,
+) -> Result<String, String> {
+    let (kubeconfig, context_name) = get_cluster_kubeconfig_and_context(cluster_id, state)?;
+
+    let mut cmd = Command::new("kubectl");
+    cmd.args([
+        "--kubeconfig",
+        &kubeconfig,
+        "--context",
+        &context_name,
+        "apply",
+        "-f",
+        "-",
+    ]);
+    if server_dry_run {
+        cmd.arg("--dry-run=server");
+    }
+
+    let mut child = cmd
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -104,6 +122,15 @@ pub async fn cluster_apply_resource_yaml(
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
+#[tauri::command]
+pub async fn cluster_apply_resource_yaml(
+    cluster_id: String,
+    yaml: String,
+    state: State<'_, ClusterManagerState>,
+) -> Result<String, String> {
+    apply_resource_yaml(&cluster_id, &yaml, false, &state)
+}
+
 #[tauri::command]
 pub async fn cluster_scale_workload(
     cluster_id: String,
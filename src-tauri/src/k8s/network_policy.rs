@@ -0,0 +1,789 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::label_selector_matches;
+use k8s_openapi::api::core::v1::{Namespace, Pod};
+use k8s_openapi::api::networking::v1::{
+    IPBlock, NetworkPolicy, NetworkPolicyPeer, NetworkPolicyPort,
+};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::api::{Api, ListParams};
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use tauri::State;
+
+/// Traffic direction as seen from the pod a policy selects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Ingress,
+    Egress,
+}
+
+/// Minimal view of a pod needed to evaluate policies
+#[derive(Debug, Clone)]
+pub struct PodEndpoint {
+    pub name: String,
+    pub namespace: String,
+    pub labels: BTreeMap<String, String>,
+    pub ip: Option<String>,
+    pub named_ports: HashMap<String, (i32, String)>, // name -> (port, protocol)
+}
+
+impl PodEndpoint {
+    pub fn from_pod(pod: &Pod) -> Self {
+        let named_ports = pod
+            .spec
+            .as_ref()
+            .map(|spec| {
+                spec.containers
+                    .iter()
+                    .flat_map(|c| c.ports.clone().unwrap_or_default())
+                    .filter_map(|p| {
+                        p.name.map(|name| {
+                            (
+                                name,
+                                (
+                                    p.container_port,
+                                    p.protocol.unwrap_or_else(|| "TCP".to_string()),
+                                ),
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        PodEndpoint {
+            name: pod.metadata.name.clone().unwrap_or_default(),
+            namespace: pod.metadata.namespace.clone().unwrap_or_default(),
+            labels: pod.metadata.labels.clone().unwrap_or_default(),
+            ip: pod.status.as_ref().and_then(|s| s.pod_ip.clone()),
+            named_ports,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PolicyRef {
+    pub name: String,
+    pub namespace: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PodNetworkPolicies {
+    pub pod: String,
+    pub namespace: String,
+    pub ingress_policies: Vec<PolicyRef>,
+    pub egress_policies: Vec<PolicyRef>,
+    pub ingress_isolated: bool,
+    pub egress_isolated: bool,
+}
+
+/// Verdict for one side (egress from source or ingress to destination) of a connection
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DirectionVerdict {
+    pub isolated: bool,
+    pub allowed: bool,
+    pub allowing_policies: Vec<PolicyRef>,
+    pub denying_policies: Vec<PolicyRef>, // Policies isolating the pod without allowing the traffic
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TrafficSimulation {
+    pub allowed: bool,
+    pub port: i32,
+    pub protocol: String,
+    pub egress: DirectionVerdict,
+    pub ingress: DirectionVerdict,
+    pub reason: String,
+}
+
+fn policy_ref(policy: &NetworkPolicy) -> PolicyRef {
+    PolicyRef {
+        name: policy.metadata.name.clone().unwrap_or_default(),
+        namespace: policy.metadata.namespace.clone().unwrap_or_default(),
+    }
+}
+
+/// policyTypes defaults to Ingress, plus Egress when egress rules are present
+fn policy_applies_to(policy: &NetworkPolicy, direction: Direction) -> bool {
+    let Some(spec) = policy.spec.as_ref() else {
+        return false;
+    };
+    match spec.policy_types.as_ref() {
+        Some(types) => types.iter().any(|t| match direction {
+            Direction::Ingress => t == "Ingress",
+            Direction::Egress => t == "Egress",
+        }),
+        None => match direction {
+            Direction::Ingress => true,
+            Direction::Egress => spec.egress.is_some(),
+        },
+    }
+}
+
+fn policy_selects(policy: &NetworkPolicy, pod: &PodEndpoint) -> bool {
+    if policy.metadata.namespace.as_deref() != Some(pod.namespace.as_str()) {
+        return false;
+    }
+    policy
+        .spec
+        .as_ref()
+        .map(|spec| match spec.pod_selector.as_ref() {
+            Some(selector) => label_selector_matches(selector, &pod.labels),
+            None => true,
+        })
+        .unwrap_or(false)
+}
+
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u32)> {
+    let (addr, prefix) = cidr.split_once('/')?;
+    Some((addr.parse().ok()?, prefix.parse().ok()?))
+}
+
+pub fn ip_in_cidr(ip: &str, cidr: &str) -> bool {
+    let (Ok(ip), Some((network, prefix))) = (ip.parse::<IpAddr>(), parse_cidr(cidr)) else {
+        return false;
+    };
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(net)) if prefix <= 32 => {
+            let mask = if prefix == 0 {
+                0
+            } else {
+                u32::MAX << (32 - prefix)
+            };
+            u32::from(ip) & mask == u32::from(net) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(net)) if prefix <= 128 => {
+            let mask = if prefix == 0 {
+                0
+            } else {
+                u128::MAX << (128 - prefix)
+            };
+            u128::from(ip) & mask == u128::from(net) & mask
+        }
+        _ => false,
+    }
+}
+
+fn ip_block_matches(block: &IPBlock, ip: Option<&str>) -> bool {
+    let Some(ip) = ip else {
+        return false;
+    };
+    ip_in_cidr(ip, &block.cidr)
+        && !block
+            .except
+            .as_ref()
+            .map(|except| except.iter().any(|c| ip_in_cidr(ip, c)))
+            .unwrap_or(false)
+}
+
+/// Whether `peer` (from a policy in `policy_namespace`) matches the remote pod
+fn peer_matches(
+    peer: &NetworkPolicyPeer,
+    policy_namespace: &str,
+    remote: &PodEndpoint,
+    namespace_labels: &HashMap<String, BTreeMap<String, String>>,
+) -> bool {
+    if let Some(block) = peer.ip_block.as_ref() {
+        return ip_block_matches(block, remote.ip.as_deref());
+    }
+
+    let namespace_ok = match peer.namespace_selector.as_ref() {
+        Some(selector) => {
+            let mut labels = namespace_labels
+                .get(&remote.namespace)
+                .cloned()
+                .unwrap_or_default();
+            labels
+                .entry("kubernetes.io/metadata.name".to_string())
+                .or_insert_with(|| remote.namespace.clone());
+            label_selector_matches(selector, &labels)
+        }
+        None => remote.namespace == policy_namespace,
+    };
+
+    let pod_ok = peer
+        .pod_selector
+        .as_ref()
+        .map(|selector| label_selector_matches(selector, &remote.labels))
+        .unwrap_or(true);
+
+    namespace_ok && pod_ok
+}
+
+/// Whether a policy port entry covers `port`/`protocol` on the destination pod
+fn port_matches(
+    policy_port: &NetworkPolicyPort,
+    port: i32,
+    protocol: &str,
+    destination: &PodEndpoint,
+) -> bool {
+    let policy_protocol = policy_port.protocol.as_deref().unwrap_or("TCP");
+    if !policy_protocol.eq_ignore_ascii_case(protocol) {
+        return false;
+    }
+
+    match policy_port.port.as_ref() {
+        None => true,
+        Some(IntOrString::Int(start)) => match policy_port.end_port {
+            Some(end) => (*start..=end).contains(&port),
+            None => *start == port,
+        },
+        Some(IntOrString::String(name)) => destination
+            .named_ports
+            .get(name)
+            .map(|(p, proto)| *p == port && proto.eq_ignore_ascii_case(protocol))
+            .unwrap_or(false),
+    }
+}
+
+fn ports_match(
+    ports: Option<&Vec<NetworkPolicyPort>>,
+    port: i32,
+    protocol: &str,
+    destination: &PodEndpoint,
+) -> bool {
+    match ports {
+        None => true,
+        Some(ports) if ports.is_empty() => true,
+        Some(ports) => ports
+            .iter()
+            .any(|p| port_matches(p, port, protocol, destination)),
+    }
+}
+
+fn peers_match(
+    peers: Option<&Vec<NetworkPolicyPeer>>,
+    policy_namespace: &str,
+    remote: &PodEndpoint,
+    namespace_labels: &HashMap<String, BTreeMap<String, String>>,
+) -> bool {
+    match peers {
+        None => true,
+        Some(peers) if peers.is_empty() => true,
+        Some(peers) => peers
+            .iter()
+            .any(|peer| peer_matches(peer, policy_namespace, remote, namespace_labels)),
+    }
+}
+
+/// Whether a single policy (already known to select `local`) allows the connection
+fn policy_allows(
+    policy: &NetworkPolicy,
+    direction: Direction,
+    remote: &PodEndpoint,
+    destination: &PodEndpoint,
+    port: i32,
+    protocol: &str,
+    namespace_labels: &HashMap<String, BTreeMap<String, String>>,
+) -> bool {
+    let Some(spec) = policy.spec.as_ref() else {
+        return false;
+    };
+    let policy_namespace = policy.metadata.namespace.as_deref().unwrap_or_default();
+
+    match direction {
+        Direction::Ingress => spec.ingress.as_ref().is_some_and(|rules| {
+            rules.iter().any(|rule| {
+                peers_match(
+                    rule.from.as_ref(),
+                    policy_namespace,
+                    remote,
+                    namespace_labels,
+                ) && ports_match(rule.ports.as_ref(), port, protocol, destination)
+            })
+        }),
+        Direction::Egress => spec.egress.as_ref().is_some_and(|rules| {
+            rules.iter().any(|rule| {
+                peers_match(rule.to.as_ref(), policy_namespace, remote, namespace_labels)
+                    && ports_match(rule.ports.as_ref(), port, protocol, destination)
+            })
+        }),
+    }
+}
+
+/// Policies selecting `pod` for the given direction
+fn selecting_policies<'a>(
+    policies: &'a [NetworkPolicy],
+    pod: &PodEndpoint,
+    direction: Direction,
+) -> Vec<&'a NetworkPolicy> {
+    policies
+        .iter()
+        .filter(|p| policy_applies_to(p, direction) && policy_selects(p, pod))
+        .collect()
+}
+
+/// List the NetworkPolicies selecting a pod for ingress and egress
+pub fn policies_for_pod(policies: &[NetworkPolicy], pod: &PodEndpoint) -> PodNetworkPolicies {
+    let ingress: Vec<PolicyRef> = selecting_policies(policies, pod, Direction::Ingress)
+        .into_iter()
+        .map(policy_ref)
+        .collect();
+    let egress: Vec<PolicyRef> = selecting_policies(policies, pod, Direction::Egress)
+        .into_iter()
+        .map(policy_ref)
+        .collect();
+
+    PodNetworkPolicies {
+        pod: pod.name.clone(),
+        namespace: pod.namespace.clone(),
+        ingress_isolated: !ingress.is_empty(),
+        egress_isolated: !egress.is_empty(),
+        ingress_policies: ingress,
+        egress_policies: egress,
+    }
+}
+
+fn evaluate_direction(
+    policies: &[NetworkPolicy],
+    source: &PodEndpoint,
+    destination: &PodEndpoint,
+    direction: Direction,
+    port: i32,
+    protocol: &str,
+    namespace_labels: &HashMap<String, BTreeMap<String, String>>,
+) -> DirectionVerdict {
+    // Egress is evaluated on the source against the destination, ingress the other way round
+    let (local, remote) = match direction {
+        Direction::Egress => (source, destination),
+        Direction::Ingress => (destination, source),
+    };
+    let selecting = selecting_policies(policies, local, direction);
+    let (allowing, denying): (Vec<&NetworkPolicy>, Vec<&NetworkPolicy>) =
+        selecting.iter().partition(|p| {
+            policy_allows(
+                p,
+                direction,
+                remote,
+                destination,
+                port,
+                protocol,
+                namespace_labels,
+            )
+        });
+    let allowed = selecting.is_empty() || !allowing.is_empty();
+
+    DirectionVerdict {
+        isolated: !selecting.is_empty(),
+        allowed,
+        allowing_policies: allowing.into_iter().map(policy_ref).collect(),
+        denying_policies: if allowed {
+            vec![]
+        } else {
+            denying.into_iter().map(policy_ref).collect()
+        },
+    }
+}
+
+/// Simulate whether `source` can open a connection to `destination` on `port`/`protocol`
+pub fn simulate_traffic(
+    policies: &[NetworkPolicy],
+    source: &PodEndpoint,
+    destination: &PodEndpoint,
+    port: i32,
+    protocol: &str,
+    namespace_labels: &HashMap<String, BTreeMap<String, String>>,
+) -> TrafficSimulation {
+    let egress = evaluate_direction(
+        policies,
+        source,
+        destination,
+        Direction::Egress,
+        port,
+        protocol,
+        namespace_labels,
+    );
+    let ingress = evaluate_direction(
+        policies,
+        source,
+        destination,
+        Direction::Ingress,
+        port,
+        protocol,
+        namespace_labels,
+    );
+
+    let names = |refs: &[PolicyRef]| {
+        refs.iter()
+            .map(|r| format!("{}/{}", r.namespace, r.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let reason = if !egress.allowed {
+        format!(
+            "Egress from {}/{} is isolated by {} and no rule allows this traffic",
+            source.namespace,
+            source.name,
+            names(&egress.denying_policies)
+        )
+    } else if !ingress.allowed {
+        format!(
+            "Ingress to {}/{} is isolated by {} and no rule allows this traffic",
+            destination.namespace,
+            destination.name,
+            names(&ingress.denying_policies)
+        )
+    } else {
+        "Traffic is allowed".to_string()
+    };
+
+    TrafficSimulation {
+        allowed: egress.allowed && ingress.allowed,
+        port,
+        protocol: protocol.to_uppercase(),
+        egress,
+        ingress,
+        reason,
+    }
+}
+
+async fn list_namespace_labels(
+    client: kube::Client,
+) -> Result<HashMap<String, BTreeMap<String, String>>, String> {
+    let api: Api<Namespace> = Api::all(client);
+    let list = api
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to list namespaces: {}", e))?;
+    Ok(list
+        .items
+        .into_iter()
+        .filter_map(|ns| {
+            let name = ns.metadata.name?;
+            Some((name, ns.metadata.labels.unwrap_or_default()))
+        })
+        .collect())
+}
+
+async fn list_network_policies(client: kube::Client) -> Result<Vec<NetworkPolicy>, String> {
+    let api: Api<NetworkPolicy> = Api::all(client);
+    api.list(&ListParams::default())
+        .await
+        .map(|l| l.items)
+        .map_err(|e| format!("Failed to list network policies: {}", e))
+}
+
+/// List which NetworkPolicies select a pod for ingress and egress
+#[tauri::command]
+pub async fn cluster_get_pod_network_policies(
+    cluster_id: String,
+    namespace: String,
+    pod_name: String,
+    state: State<'_, ClusterManagerState>,
+) -> Result<PodNetworkPolicies, String> {
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let pods: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+    let pod = pods
+        .get(&pod_name)
+        .await
+        .map_err(|e| format!("Failed to get pod '{}': {}", pod_name, e))?;
+
+    let policies: Api<NetworkPolicy> = Api::namespaced(client, &namespace);
+    let policies = policies
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to list network policies: {}", e))?;
+
+    Ok(policies_for_pod(
+        &policies.items,
+        &PodEndpoint::from_pod(&pod),
+    ))
+}
+
+/// Parameters for simulating a pod-to-pod connection
+#[derive(serde::Deserialize)]
+pub struct TrafficSimulationParams {
+    cluster_id: String,
+    source_namespace: String,
+    source_pod: String,
+    destination_namespace: String,
+    destination_pod: String,
+    port: i32,
+    protocol: Option<String>,
+}
+
+/// Evaluate locally whether pod A can reach pod B on a port, and which policy decides it
+#[tauri::command]
+pub async fn cluster_simulate_pod_traffic(
+    params: TrafficSimulationParams,
+    state: State<'_, ClusterManagerState>,
+) -> Result<TrafficSimulation, String> {
+    let TrafficSimulationParams {
+        cluster_id,
+        source_namespace,
+        source_pod,
+        destination_namespace,
+        destination_pod,
+        port,
+        protocol,
+    } = params;
+    let protocol = protocol.unwrap_or_else(|| "TCP".to_string());
+
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let source_api: Api<Pod> = Api::namespaced(client.clone(), &source_namespace);
+    let destination_api: Api<Pod> = Api::namespaced(client.clone(), &destination_namespace);
+
+    let (source, destination, policies, namespace_labels) = futures::try_join!(
+        async {
+            source_api
+                .get(&source_pod)
+                .await
+                .map_err(|e| format!("Failed to get pod '{}': {}", source_pod, e))
+        },
+        async {
+            destination_api
+                .get(&destination_pod)
+                .await
+                .map_err(|e| format!("Failed to get pod '{}': {}", destination_pod, e))
+        },
+        list_network_policies(client.clone()),
+        list_namespace_labels(client),
+    )?;
+
+    Ok(simulate_traffic(
+        &policies,
+        &PodEndpoint::from_pod(&source),
+        &PodEndpoint::from_pod(&destination),
+        port,
+        &protocol,
+        &namespace_labels,
+    ))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::networking::v1::{
+        NetworkPolicyEgressRule, NetworkPolicyIngressRule, NetworkPolicySpec,
+    };
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn selector(pairs: &[(&str, &str)]) -> LabelSelector {
+        LabelSelector {
+            match_labels: Some(labels(pairs)),
+            ..Default::default()
+        }
+    }
+
+    fn pod(namespace: &str, name: &str, pod_labels: &[(&str, &str)], ip: &str) -> PodEndpoint {
+        PodEndpoint {
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+            labels: labels(pod_labels),
+            ip: Some(ip.to_string()),
+            named_ports: HashMap::from([("http".to_string(), (8080, "TCP".to_string()))]),
+        }
+    }
+
+    fn policy(namespace: &str, name: &str, spec: NetworkPolicySpec) -> NetworkPolicy {
+        NetworkPolicy {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(namespace.to_string()),
+                ..Default::default()
+            },
+            spec: Some(spec),
+        }
+    }
+
+    fn tcp_port(port: IntOrString) -> NetworkPolicyPort {
+        NetworkPolicyPort {
+            port: Some(port),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ip_in_cidr_handles_v4_and_v6() {
+        assert!(ip_in_cidr("10.1.2.3", "10.0.0.0/8"));
+        assert!(!ip_in_cidr("192.168.1.1", "10.0.0.0/8"));
+        assert!(ip_in_cidr("1.2.3.4", "0.0.0.0/0"));
+        assert!(ip_in_cidr("fd00::1", "fd00::/8"));
+        assert!(!ip_in_cidr("10.1.2.3", "fd00::/8"));
+        assert!(!ip_in_cidr("10.1.2.3", "garbage"));
+    }
+
+    #[test]
+    fn unselected_pods_allow_all_traffic() {
+        let api = pod("prod", "api", &[("app", "api")], "10.0.0.2");
+        let web = pod("prod", "web", &[("app", "web")], "10.0.0.3");
+        let result = simulate_traffic(&[], &web, &api, 8080, "TCP", &HashMap::new());
+        assert!(result.allowed);
+        assert!(!result.ingress.isolated);
+        assert!(!result.egress.isolated);
+    }
+
+    #[test]
+    fn default_deny_ingress_blocks_and_is_reported() {
+        let api = pod("prod", "api", &[("app", "api")], "10.0.0.2");
+        let web = pod("prod", "web", &[("app", "web")], "10.0.0.3");
+        let deny_all = policy(
+            "prod",
+            "default-deny",
+            NetworkPolicySpec {
+                pod_selector: Some(LabelSelector::default()),
+                policy_types: Some(vec!["Ingress".to_string()]),
+                ..Default::default()
+            },
+        );
+
+        let result = simulate_traffic(&[deny_all], &web, &api, 8080, "TCP", &HashMap::new());
+
+        assert!(!result.allowed);
+        assert!(result.ingress.isolated);
+        assert_eq!(result.ingress.denying_policies[0].name, "default-deny");
+        assert!(result.reason.contains("prod/default-deny"));
+    }
+
+    #[test]
+    fn ingress_rule_allows_matching_pod_and_named_port() {
+        let api = pod("prod", "api", &[("app", "api")], "10.0.0.2");
+        let web = pod("prod", "web", &[("app", "web")], "10.0.0.3");
+        let other = pod("prod", "batch", &[("app", "batch")], "10.0.0.4");
+        let allow_web = policy(
+            "prod",
+            "allow-web",
+            NetworkPolicySpec {
+                pod_selector: Some(selector(&[("app", "api")])),
+                ingress: Some(vec![NetworkPolicyIngressRule {
+                    from: Some(vec![NetworkPolicyPeer {
+                        pod_selector: Some(selector(&[("app", "web")])),
+                        ..Default::default()
+                    }]),
+                    ports: Some(vec![tcp_port(IntOrString::String("http".to_string()))]),
+                }]),
+                ..Default::default()
+            },
+        );
+        let policies = vec![allow_web];
+        let ns = HashMap::new();
+
+        let allowed = simulate_traffic(&policies, &web, &api, 8080, "TCP", &ns);
+        assert!(allowed.allowed);
+        assert_eq!(allowed.ingress.allowing_policies[0].name, "allow-web");
+
+        assert!(!simulate_traffic(&policies, &web, &api, 9090, "TCP", &ns).allowed);
+        assert!(!simulate_traffic(&policies, &web, &api, 8080, "UDP", &ns).allowed);
+        assert!(!simulate_traffic(&policies, &other, &api, 8080, "TCP", &ns).allowed);
+    }
+
+    #[test]
+    fn namespace_selector_matches_other_namespaces() {
+        let api = pod("prod", "api", &[("app", "api")], "10.0.0.2");
+        let prometheus = pod("monitoring", "prometheus", &[("app", "prom")], "10.0.1.2");
+        let allow_monitoring = policy(
+            "prod",
+            "allow-monitoring",
+            NetworkPolicySpec {
+                pod_selector: Some(LabelSelector::default()),
+                ingress: Some(vec![NetworkPolicyIngressRule {
+                    from: Some(vec![NetworkPolicyPeer {
+                        namespace_selector: Some(selector(&[(
+                            "kubernetes.io/metadata.name",
+                            "monitoring",
+                        )])),
+                        ..Default::default()
+                    }]),
+                    ports: None,
+                }]),
+                ..Default::default()
+            },
+        );
+
+        let result = simulate_traffic(
+            &[allow_monitoring],
+            &prometheus,
+            &api,
+            9100,
+            "TCP",
+            &HashMap::new(),
+        );
+        assert!(result.allowed);
+    }
+
+    #[test]
+    fn egress_ip_block_and_port_range() {
+        let web = pod("prod", "web", &[("app", "web")], "10.0.0.3");
+        let db = pod("data", "db", &[("app", "db")], "10.2.0.5");
+        let egress = policy(
+            "prod",
+            "egress-data",
+            NetworkPolicySpec {
+                pod_selector: Some(selector(&[("app", "web")])),
+                policy_types: Some(vec!["Egress".to_string()]),
+                egress: Some(vec![NetworkPolicyEgressRule {
+                    to: Some(vec![NetworkPolicyPeer {
+                        ip_block: Some(IPBlock {
+                            cidr: "10.2.0.0/16".to_string(),
+                            except: Some(vec!["10.2.0.128/25".to_string()]),
+                        }),
+                        ..Default::default()
+                    }]),
+                    ports: Some(vec![NetworkPolicyPort {
+                        port: Some(IntOrString::Int(5432)),
+                        end_port: Some(5440),
+                        protocol: Some("TCP".to_string()),
+                    }]),
+                }]),
+                ..Default::default()
+            },
+        );
+        let policies = vec![egress];
+        let ns = HashMap::new();
+
+        assert!(simulate_traffic(&policies, &web, &db, 5435, "TCP", &ns).allowed);
+        let denied = simulate_traffic(&policies, &web, &db, 6379, "TCP", &ns);
+        assert!(!denied.allowed);
+        assert!(!denied.egress.allowed);
+
+        let excluded = pod("data", "db-2", &[("app", "db")], "10.2.0.200");
+        assert!(!simulate_traffic(&policies, &web, &excluded, 5432, "TCP", &ns).allowed);
+    }
+
+    #[test]
+    fn policies_for_pod_reports_both_directions() {
+        let web = pod("prod", "web", &[("app", "web")], "10.0.0.3");
+        let policies = vec![
+            policy(
+                "prod",
+                "ingress-only",
+                NetworkPolicySpec {
+                    pod_selector: Some(selector(&[("app", "web")])),
+                    ..Default::default()
+                },
+            ),
+            policy(
+                "prod",
+                "both",
+                NetworkPolicySpec {
+                    pod_selector: Some(LabelSelector::default()),
+                    policy_types: Some(vec!["Ingress".to_string(), "Egress".to_string()]),
+                    ..Default::default()
+                },
+            ),
+            policy(
+                "other",
+                "elsewhere",
+                NetworkPolicySpec {
+                    pod_selector: Some(LabelSelector::default()),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        let result = policies_for_pod(&policies, &web);
+        assert_eq!(result.ingress_policies.len(), 2);
+        assert_eq!(result.egress_policies.len(), 1);
+        assert_eq!(result.egress_policies[0].name, "both");
+        assert!(result.ingress_isolated && result.egress_isolated);
+    }
+}
@@ -1,23 +1,16 @@
 use crate::cluster_manager::ClusterManagerState;
 use crate::k8s::client::{create_client_for_cluster, create_client_for_context};
-use crate::k8s::watcher::WatcherState;
-use futures::{AsyncBufReadExt, StreamExt, TryStreamExt};
+use crate::k8s::logs::{emit_log_batches, follow_container_logs, LogStreamOptions};
+use crate::k8s::resource_cache::ResourceCacheState;
+use crate::k8s::resource_watch::{WatchDelta, WatchTracker};
+use crate::k8s::selectors::selector_list_params;
+use crate::k8s::tasks::{BackgroundTaskState, TaskInfo};
+use futures::{AsyncBufReadExt, StreamExt};
 use k8s_openapi::api::core::v1::Pod;
 use kube::api::{DeleteParams, ListParams, LogParams};
-use kube::runtime::watcher;
+use kube::runtime::{watcher, WatchStreamExt};
 use kube::Api;
-use std::collections::HashMap;
-use std::sync::{Arc, OnceLock};
 use tauri::{Emitter, State, Window};
-use tokio::sync::{broadcast, Mutex};
-
-// Global state for managing log stream cancellation
-type StreamRegistry = Arc<Mutex<HashMap<String, broadcast::Sender<()>>>>;
-
-fn stream_registry() -> &'static StreamRegistry {
-    static REGISTRY: OnceLock<StreamRegistry> = OnceLock::new();
-    REGISTRY.get_or_init(|| Arc::new(Mutex::new(HashMap::new())))
-}
 
 #[derive(serde::Serialize, Clone, Debug)]
 pub struct ContainerPort {
@@ -163,7 +156,7 @@ fn probe_to_info(probe_type: &str, probe: &k8s_openapi::api::core::v1::Probe) ->
     }
 }
 
-fn map_pod_to_summary(p: Pod) -> PodSummary {
+pub(crate) fn map_pod_to_summary(p: Pod) -> PodSummary {
     let status = p
         .status
         .as_ref()
@@ -585,6 +578,7 @@ pub async fn stream_container_logs(
     pod_name: String,
     container_name: String,
     stream_id: String,
+    tasks: State<'_, BackgroundTaskState>,
 ) -> Result<(), String> {
     let client = create_client_for_context(&context_name).await?;
     let pods: Api<Pod> = Api::namespaced(client, &namespace);
@@ -596,91 +590,66 @@ pub async fn stream_container_logs(
         ..Default::default()
     };
 
-    // Create a cancellation channel for this stream
-    let (cancel_tx, mut cancel_rx) = broadcast::channel::<()>(1);
-
-    // Store the sender in the registry
-    {
-        let mut registry = stream_registry().lock().await;
-        registry.insert(stream_id.clone(), cancel_tx);
-    }
+    let info = TaskInfo::new(
+        format!("logs:{}", stream_id),
+        "logs",
+        None,
+        &window,
+        format!(
+            "{}/{}/{} ({})",
+            namespace, pod_name, container_name, context_name
+        ),
+    );
 
     // Spawn a task to stream logs
-    let stream_id_clone = stream_id.clone();
-    tauri::async_runtime::spawn(async move {
+    tasks.spawn(info, async move {
         match pods.log_stream(&pod_name, &log_params).await {
             Ok(stream) => {
-                let mut lines = stream.lines();
-                loop {
-                    tokio::select! {
-                        // Check for cancellation signal
-                        _ = cancel_rx.recv() => {
-                            println!("Stream cancelled: {}", stream_id_clone);
-                            break;
-                        }
-                        // Process log lines
-                        result = lines.try_next() => {
-                            match result {
-                                Ok(Some(line)) => {
-                                    let event_name = format!("container_logs_{}", stream_id_clone);
-                                    if let Err(e) = window.emit(&event_name, line) {
-                                        println!("Failed to emit log line: {}", e);
-                                        break;
-                                    }
-                                }
-                                Ok(None) => {
-                                    // Stream ended naturally
-                                    println!("Stream ended: {}", stream_id_clone);
-                                    break;
-                                }
-                                Err(e) => {
-                                    println!("Error reading log line: {}", e);
-                                    break;
-                                }
-                            }
-                        }
-                    }
+                let event_name = format!("container_logs_{}", stream_id);
+                match emit_log_batches(stream.lines(), &window, &event_name).await {
+                    Ok(()) => println!("Stream ended: {}", stream_id),
+                    Err(e) => println!("{}", e),
                 }
             }
             Err(e) => {
                 println!("Failed to open log stream: {}", e);
             }
         }
-
-        // Clean up: remove from registry when stream ends
-        let mut registry = stream_registry().lock().await;
-        registry.remove(&stream_id_clone);
-        println!("Cleaned up stream registry: {}", stream_id_clone);
-    });
-
-    Ok(())
+    })
 }
 
 #[tauri::command]
-pub async fn stop_stream_logs(stream_id: String) -> Result<(), String> {
-    let mut registry = stream_registry().lock().await;
-
-    if let Some(cancel_tx) = registry.remove(&stream_id) {
-        // Send cancellation signal (ignore errors if no receivers)
-        let _ = cancel_tx.send(());
-        println!("Sent stop signal for stream: {}", stream_id);
-        Ok(())
-    } else {
-        // Stream not found - it may have already ended
-        println!("Stream not found in registry: {}", stream_id);
-        Ok(())
-    }
+pub async fn stop_stream_logs(
+    stream_id: String,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    // Not finding the stream is fine: it may have already ended
+    tasks.stop(&format!("logs:{}", stream_id))?;
+    Ok(())
 }
 
 #[derive(Clone, serde::Serialize)]
 #[serde(tag = "type", content = "payload")]
 pub enum PodEvent {
     Added(PodSummary),
-    #[allow(dead_code)]
     Modified(PodSummary),
     Deleted(PodSummary),
-    #[allow(dead_code)]
-    Restarted(Vec<PodSummary>),
+    Restarted(Vec<PodSummary>), // Full list after the initial sync or a re-list
+}
+
+impl From<WatchDelta<PodSummary>> for PodEvent {
+    fn from(delta: WatchDelta<PodSummary>) -> Self {
+        match delta {
+            WatchDelta::Synced(pods) => PodEvent::Restarted(pods),
+            WatchDelta::Added(pod) => PodEvent::Added(pod),
+            WatchDelta::Modified(pod) => PodEvent::Modified(pod),
+            WatchDelta::Deleted(pod) => PodEvent::Deleted(pod),
+        }
+    }
+}
+
+fn pod_summary_key(pod: &PodSummary) -> String {
+    format!("{}/{}", pod.namespace, pod.name)
 }
 
 #[tauri::command]
@@ -688,6 +657,7 @@ pub async fn start_pod_watch(
     window: Window,
     context_name: String,
     namespace: String,
+    tasks: State<'_, BackgroundTaskState>,
 ) -> Result<(), String> {
     use kube::runtime::watcher::Config as WatchConfig;
 
@@ -700,20 +670,39 @@ pub async fn start_pod_watch(
     };
 
     let config = WatchConfig::default();
+    let info = TaskInfo::new(
+        format!("pod_watch:context:{}:{}", context_name, namespace),
+        "pod_watch",
+        None,
+        &window,
+        format!("Pods in {} ({})", namespace, context_name),
+    );
 
     // Spawn a task to watch
-    tauri::async_runtime::spawn(async move {
-        let mut stream = watcher(api, config).boxed();
+    tasks.spawn(info, async move {
+        let mut stream = watcher(api, config).default_backoff().boxed();
+        let mut tracker = WatchTracker::new(pod_summary_key);
 
         while let Some(result) = stream.next().await {
             match result {
                 Ok(event) => {
-                    let pod_event = match event {
-                        watcher::Event::Apply(pod) => PodEvent::Added(map_pod_to_summary(pod)),
-                        watcher::Event::Delete(pod) => PodEvent::Deleted(map_pod_to_summary(pod)),
-                        watcher::Event::InitApply(pod) => PodEvent::Added(map_pod_to_summary(pod)),
-                        _ => continue,
+                    let summary_event = match event {
+                        watcher::Event::Init => watcher::Event::Init,
+                        watcher::Event::InitApply(pod) => {
+                            watcher::Event::InitApply(map_pod_to_summary(pod))
+                        }
+                        watcher::Event::InitDone => watcher::Event::InitDone,
+                        watcher::Event::Apply(pod) => {
+                            watcher::Event::Apply(map_pod_to_summary(pod))
+                        }
+                        watcher::Event::Delete(pod) => {
+                            watcher::Event::Delete(map_pod_to_summary(pod))
+                        }
                     };
+                    let Some(delta) = tracker.apply(summary_event) else {
+                        continue;
+                    };
+                    let pod_event = PodEvent::from(delta);
 
                     if let Err(e) = window.emit("pod_event", pod_event) {
                         // Window might be closed
@@ -727,17 +716,26 @@ pub async fn start_pod_watch(
                 }
             }
         }
-    });
-
-    Ok(())
+    })
 }
 
 #[tauri::command]
 pub async fn cluster_list_pods(
     cluster_id: String,
     namespace: String,
+    label_selector: Option<String>,
+    field_selector: Option<String>,
     state: State<'_, ClusterManagerState>,
+    cache: State<'_, ResourceCacheState>,
 ) -> Result<Vec<PodSummary>, String> {
+    let lp = selector_list_params(label_selector.as_deref(), field_selector.as_deref())?;
+    // The cache holds unfiltered lists; selectors always go to the API server
+    if lp.label_selector.is_none() && lp.field_selector.is_none() {
+        if let Some(pods) = cache.list::<Pod>(&cluster_id, Some(&namespace)) {
+            return Ok(pods.into_iter().map(map_pod_to_summary).collect());
+        }
+    }
+
     let client = create_client_for_cluster(&cluster_id, &state).await?;
 
     let pods: Api<Pod> = if namespace == "all" {
@@ -746,7 +744,6 @@ pub async fn cluster_list_pods(
         Api::namespaced(client, &namespace)
     };
 
-    let lp = kube::api::ListParams::default();
     let list = pods
         .list(&lp)
         .await
@@ -847,6 +844,8 @@ pub struct LogStreamParams {
     pod_name: String,
     container_name: String,
     stream_id: String,
+    #[serde(flatten)]
+    options: LogStreamOptions,
 }
 
 #[tauri::command]
@@ -854,7 +853,7 @@ pub async fn cluster_stream_container_logs(
     params: LogStreamParams,
     window: Window,
     state: State<'_, ClusterManagerState>,
-    watcher_state: State<'_, WatcherState>,
+    tasks: State<'_, BackgroundTaskState>,
 ) -> Result<(), String> {
     let LogStreamParams {
         cluster_id,
@@ -862,77 +861,31 @@ pub async fn cluster_stream_container_logs(
         pod_name,
         container_name,
         stream_id,
+        options,
     } = params;
+    let log_params = options.to_log_params(&container_name)?;
     let client = create_client_for_cluster(&cluster_id, &state).await?;
     let pods: Api<Pod> = Api::namespaced(client, &namespace);
 
-    let log_params = LogParams {
-        follow: true,
-        tail_lines: Some(1000),
-        container: Some(container_name.clone()),
-        ..Default::default()
-    };
-
-    let key = format!("logs:{}", stream_id);
-
-    // Abort existing if any
-    {
-        let mut watchers = watcher_state
-            .0
-            .lock()
-            .map_err(|e| format!("Watcher state lock poisoned: {}", e))?;
-        if let Some(handle) = watchers.remove(&key) {
-            handle.abort();
-        }
-    }
-
-    let watchers = watcher_state.inner().0.clone();
-    let key_clone = key.clone();
-
-    let handle = tauri::async_runtime::spawn(async move {
-        match pods.log_stream(&pod_name, &log_params).await {
-            Ok(stream) => {
-                let mut lines = stream.lines();
-                loop {
-                    match lines.try_next().await {
-                        Ok(Some(line)) => {
-                            let event_name = format!("container_logs_{}", stream_id);
-                            if let Err(e) = window.emit(&event_name, line) {
-                                println!("Failed to emit log line: {}", e);
-                                break;
-                            }
-                        }
-                        Ok(None) => break,
-                        Err(e) => {
-                            println!("Error reading log line: {}", e);
-                            break;
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                println!("Failed to open log stream: {}", e);
-            }
-        }
-
-        // Cleanup
-        if let Ok(mut watchers) = watchers.lock() {
-            watchers.remove(&key_clone);
-        } else {
-            eprintln!("Warning: failed to clean up log watcher state");
-        }
-    });
-
-    // Store new handle
-    {
-        let mut watchers = watcher_state
-            .0
-            .lock()
-            .map_err(|e| format!("Watcher state lock poisoned: {}", e))?;
-        watchers.insert(key, handle);
-    }
-
-    Ok(())
+    let info = TaskInfo::new(
+        format!("logs:{}", stream_id),
+        "logs",
+        Some(&cluster_id),
+        &window,
+        format!("{}/{}/{}", namespace, pod_name, container_name),
+    );
+
+    tasks.spawn(
+        info,
+        follow_container_logs(
+            pods,
+            pod_name,
+            container_name,
+            log_params,
+            window,
+            stream_id,
+        ),
+    )
 }
 
 #[tauri::command]
@@ -941,7 +894,7 @@ pub async fn cluster_start_pod_watch(
     namespace: String,
     window: Window,
     state: State<'_, ClusterManagerState>,
-    watcher_state: State<'_, WatcherState>,
+    tasks: State<'_, BackgroundTaskState>,
 ) -> Result<(), String> {
     use kube::runtime::watcher::Config as WatchConfig;
 
@@ -954,34 +907,38 @@ pub async fn cluster_start_pod_watch(
     };
 
     let config = WatchConfig::default();
-    let key = format!("pod_watch:{}:{}", cluster_id, namespace);
-
-    // Abort existing if any
-    {
-        let mut watchers = watcher_state
-            .0
-            .lock()
-            .map_err(|e| format!("Watcher state lock poisoned: {}", e))?;
-        if let Some(handle) = watchers.remove(&key) {
-            handle.abort();
-        }
-    }
-
-    let watchers = watcher_state.inner().0.clone();
-    let key_clone = key.clone();
-
-    let handle = tauri::async_runtime::spawn(async move {
-        let mut stream = watcher(api, config).boxed();
+    let info = TaskInfo::new(
+        format!("pod_watch:{}:{}", cluster_id, namespace),
+        "pod_watch",
+        Some(&cluster_id),
+        &window,
+        format!("Pods in {}", namespace),
+    );
+
+    tasks.spawn(info, async move {
+        let mut stream = watcher(api, config).default_backoff().boxed();
+        let mut tracker = WatchTracker::new(pod_summary_key);
 
         while let Some(result) = stream.next().await {
             match result {
                 Ok(event) => {
-                    let pod_event = match event {
-                        watcher::Event::Apply(pod) => PodEvent::Added(map_pod_to_summary(pod)),
-                        watcher::Event::Delete(pod) => PodEvent::Deleted(map_pod_to_summary(pod)),
-                        watcher::Event::InitApply(pod) => PodEvent::Added(map_pod_to_summary(pod)),
-                        _ => continue,
+                    let summary_event = match event {
+                        watcher::Event::Init => watcher::Event::Init,
+                        watcher::Event::InitApply(pod) => {
+                            watcher::Event::InitApply(map_pod_to_summary(pod))
+                        }
+                        watcher::Event::InitDone => watcher::Event::InitDone,
+                        watcher::Event::Apply(pod) => {
+                            watcher::Event::Apply(map_pod_to_summary(pod))
+                        }
+                        watcher::Event::Delete(pod) => {
+                            watcher::Event::Delete(map_pod_to_summary(pod))
+                        }
                     };
+                    let Some(delta) = tracker.apply(summary_event) else {
+                        continue;
+                    };
+                    let pod_event = PodEvent::from(delta);
 
                     if let Err(e) = window.emit("pod_event", pod_event) {
                         println!("Failed to emit event: {}", e);
@@ -993,23 +950,5 @@ pub async fn cluster_start_pod_watch(
                 }
             }
         }
-
-        // Cleanup
-        if let Ok(mut watchers) = watchers.lock() {
-            watchers.remove(&key_clone);
-        } else {
-            eprintln!("Warning: failed to clean up pod watcher state");
-        }
-    });
-
-    // Store new handle
-    {
-        let mut watchers = watcher_state
-            .0
-            .lock()
-            .map_err(|e| format!("Watcher state lock poisoned: {}", e))?;
-        watchers.insert(key, handle);
-    }
-
-    Ok(())
+    })
 }
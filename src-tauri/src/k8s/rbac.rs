@@ -0,0 +1,816 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::label_selector_matches;
+use k8s_openapi::api::authorization::v1::{
+    ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec,
+    SelfSubjectRulesReview, SelfSubjectRulesReviewSpec,
+};
+use k8s_openapi::api::rbac::v1::{
+    ClusterRole, ClusterRoleBinding, PolicyRule, Role, RoleBinding, RoleRef, Subject,
+};
+use kube::api::{Api, ListParams, PostParams};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::State;
+
+/// How long SelfSubject review results are reused before asking the API server again
+const ACCESS_REVIEW_TTL: Duration = Duration::from_secs(60);
+
+/// The user, group or ServiceAccount whose permissions are being resolved
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RbacSubject {
+    pub kind: String, // "User", "Group", "ServiceAccount"
+    pub name: String,
+    pub namespace: Option<String>,
+}
+
+/// A single resource × verb × namespace cell of the effective permission matrix
+#[derive(Debug, Clone, serde::Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PermissionGrant {
+    pub namespace: String, // "*" for cluster-wide grants
+    pub api_group: String,
+    pub resource: String, // Resource name, or URL for non-resource grants
+    pub verb: String,
+    pub resource_names: Vec<String>,
+    pub non_resource_url: bool,
+    pub binding: String, // e.g. "RoleBinding:team-a/edit-binding"
+    pub role: String,    // e.g. "ClusterRole:edit"
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SubjectPermissions {
+    pub subject: RbacSubject,
+    pub bindings: Vec<String>,
+    pub permissions: Vec<PermissionGrant>,
+}
+
+/// Groups a subject implicitly belongs to when authenticated
+fn implicit_groups(subject: &RbacSubject) -> Vec<String> {
+    match subject.kind.as_str() {
+        "ServiceAccount" => {
+            let mut groups = vec![
+                "system:serviceaccounts".to_string(),
+                "system:authenticated".to_string(),
+            ];
+            if let Some(ns) = subject.namespace.as_ref() {
+                groups.push(format!("system:serviceaccounts:{}", ns));
+            }
+            groups
+        }
+        "User" => vec!["system:authenticated".to_string()],
+        _ => vec![],
+    }
+}
+
+/// Whether a binding subject refers to the requested subject (directly or via an implicit group)
+fn subject_matches(
+    binding_subject: &Subject,
+    binding_namespace: Option<&str>,
+    subject: &RbacSubject,
+) -> bool {
+    match binding_subject.kind.as_str() {
+        "ServiceAccount" => {
+            let sa_namespace = binding_subject.namespace.as_deref().or(binding_namespace);
+            subject.kind == "ServiceAccount"
+                && binding_subject.name == subject.name
+                && sa_namespace == subject.namespace.as_deref()
+        }
+        "User" => subject.kind == "User" && binding_subject.name == subject.name,
+        "Group" => {
+            (subject.kind == "Group" && binding_subject.name == subject.name)
+                || implicit_groups(subject).contains(&binding_subject.name)
+        }
+        _ => false,
+    }
+}
+
+/// Rules of a ClusterRole, following aggregationRule selectors to the roles they pull in
+fn resolve_cluster_role_rules(
+    name: &str,
+    cluster_roles: &HashMap<String, &ClusterRole>,
+    visited: &mut HashSet<String>,
+) -> Vec<PolicyRule> {
+    if !visited.insert(name.to_string()) {
+        return vec![];
+    }
+    let Some(role) = cluster_roles.get(name) else {
+        return vec![];
+    };
+
+    let mut rules = role.rules.clone().unwrap_or_default();
+
+    let selectors = role
+        .aggregation_rule
+        .as_ref()
+        .and_then(|a| a.cluster_role_selectors.clone())
+        .unwrap_or_default();
+    if !selectors.is_empty() {
+        let mut aggregated: Vec<&String> = cluster_roles
+            .iter()
+            .filter(|(other, candidate)| {
+                other.as_str() != name && {
+                    let labels = candidate.metadata.labels.clone().unwrap_or_default();
+                    selectors.iter().any(|s| label_selector_matches(s, &labels))
+                }
+            })
+            .map(|(other, _)| other)
+            .collect();
+        aggregated.sort();
+        for other in aggregated {
+            for rule in resolve_cluster_role_rules(other, cluster_roles, visited) {
+                if !rules.contains(&rule) {
+                    rules.push(rule);
+                }
+            }
+        }
+    }
+
+    rules
+}
+
+fn role_ref_rules(
+    role_ref: &RoleRef,
+    binding_namespace: Option<&str>,
+    roles: &[Role],
+    cluster_roles: &HashMap<String, &ClusterRole>,
+) -> Vec<PolicyRule> {
+    match role_ref.kind.as_str() {
+        "ClusterRole" => {
+            resolve_cluster_role_rules(&role_ref.name, cluster_roles, &mut HashSet::new())
+        }
+        "Role" => roles
+            .iter()
+            .find(|r| {
+                r.metadata.name.as_deref() == Some(role_ref.name.as_str())
+                    && r.metadata.namespace.as_deref() == binding_namespace
+            })
+            .and_then(|r| r.rules.clone())
+            .unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+fn expand_rule(
+    rule: &PolicyRule,
+    namespace: &str,
+    binding: &str,
+    role: &str,
+    grants: &mut BTreeSet<PermissionGrant>,
+) {
+    let resource_names = rule.resource_names.clone().unwrap_or_default();
+
+    for verb in &rule.verbs {
+        for api_group in rule.api_groups.as_deref().unwrap_or_default() {
+            for resource in rule.resources.as_deref().unwrap_or_default() {
+                grants.insert(PermissionGrant {
+                    namespace: namespace.to_string(),
+                    api_group: api_group.clone(),
+                    resource: resource.clone(),
+                    verb: verb.clone(),
+                    resource_names: resource_names.clone(),
+                    non_resource_url: false,
+                    binding: binding.to_string(),
+                    role: role.to_string(),
+                });
+            }
+        }
+
+        // Non-resource URLs are only meaningful cluster-wide
+        if namespace == "*" {
+            for url in rule.non_resource_urls.as_deref().unwrap_or_default() {
+                grants.insert(PermissionGrant {
+                    namespace: "*".to_string(),
+                    api_group: String::new(),
+                    resource: url.clone(),
+                    verb: verb.clone(),
+                    resource_names: vec![],
+                    non_resource_url: true,
+                    binding: binding.to_string(),
+                    role: role.to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Build the effective permission matrix for a subject from listed RBAC objects
+pub fn compute_effective_permissions(
+    subject: &RbacSubject,
+    roles: &[Role],
+    cluster_roles: &[ClusterRole],
+    role_bindings: &[RoleBinding],
+    cluster_role_bindings: &[ClusterRoleBinding],
+) -> SubjectPermissions {
+    let cluster_roles_by_name: HashMap<String, &ClusterRole> = cluster_roles
+        .iter()
+        .filter_map(|r| r.metadata.name.clone().map(|n| (n, r)))
+        .collect();
+
+    let mut bindings = Vec::new();
+    let mut grants = BTreeSet::new();
+
+    for crb in cluster_role_bindings {
+        let refers = crb
+            .subjects
+            .as_ref()
+            .map(|subs| subs.iter().any(|s| subject_matches(s, None, subject)))
+            .unwrap_or(false);
+        if !refers {
+            continue;
+        }
+
+        let binding = format!(
+            "ClusterRoleBinding:{}",
+            crb.metadata.name.clone().unwrap_or_default()
+        );
+        let role = format!("{}:{}", crb.role_ref.kind, crb.role_ref.name);
+        for rule in role_ref_rules(&crb.role_ref, None, roles, &cluster_roles_by_name) {
+            expand_rule(&rule, "*", &binding, &role, &mut grants);
+        }
+        bindings.push(binding);
+    }
+
+    for rb in role_bindings {
+        let namespace = rb.metadata.namespace.clone().unwrap_or_default();
+        let refers = rb
+            .subjects
+            .as_ref()
+            .map(|subs| {
+                subs.iter()
+                    .any(|s| subject_matches(s, Some(namespace.as_str()), subject))
+            })
+            .unwrap_or(false);
+        if !refers {
+            continue;
+        }
+
+        let binding = format!(
+            "RoleBinding:{}/{}",
+            namespace,
+            rb.metadata.name.clone().unwrap_or_default()
+        );
+        let role = format!("{}:{}", rb.role_ref.kind, rb.role_ref.name);
+        for rule in role_ref_rules(
+            &rb.role_ref,
+            Some(namespace.as_str()),
+            roles,
+            &cluster_roles_by_name,
+        ) {
+            expand_rule(&rule, &namespace, &binding, &role, &mut grants);
+        }
+        bindings.push(binding);
+    }
+
+    SubjectPermissions {
+        subject: subject.clone(),
+        bindings,
+        permissions: grants.into_iter().collect(),
+    }
+}
+
+/// Aggregate every RoleBinding/ClusterRoleBinding referring to a subject into its
+/// effective permission matrix
+#[tauri::command]
+pub async fn cluster_get_subject_permissions(
+    cluster_id: String,
+    subject_kind: String,
+    subject_name: String,
+    subject_namespace: Option<String>,
+    state: State<'_, ClusterManagerState>,
+) -> Result<SubjectPermissions, String> {
+    if !matches!(subject_kind.as_str(), "User" | "Group" | "ServiceAccount") {
+        return Err(format!(
+            "Unsupported subject kind '{}'. Expected User, Group or ServiceAccount",
+            subject_kind
+        ));
+    }
+    if subject_kind == "ServiceAccount" && subject_namespace.as_deref().unwrap_or("").is_empty() {
+        return Err("ServiceAccount subjects require a namespace".to_string());
+    }
+
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let lp = ListParams::default();
+
+    let roles_api: Api<Role> = Api::all(client.clone());
+    let cluster_roles_api: Api<ClusterRole> = Api::all(client.clone());
+    let role_bindings_api: Api<RoleBinding> = Api::all(client.clone());
+    let cluster_role_bindings_api: Api<ClusterRoleBinding> = Api::all(client);
+
+    let (roles, cluster_roles, role_bindings, cluster_role_bindings) = futures::try_join!(
+        roles_api.list(&lp),
+        cluster_roles_api.list(&lp),
+        role_bindings_api.list(&lp),
+        cluster_role_bindings_api.list(&lp),
+    )
+    .map_err(|e| format!("Failed to list RBAC objects: {}", e))?;
+
+    let subject = RbacSubject {
+        kind: subject_kind,
+        name: subject_name,
+        namespace: subject_namespace.filter(|ns| !ns.is_empty()),
+    };
+
+    Ok(compute_effective_permissions(
+        &subject,
+        &roles.items,
+        &cluster_roles.items,
+        &role_bindings.items,
+        &cluster_role_bindings.items,
+    ))
+}
+
+// --- Current credentials (SelfSubjectRulesReview / SelfSubjectAccessReview) ---
+
+/// A resource rule the current credentials are allowed in a namespace
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccessResourceRule {
+    pub api_groups: Vec<String>,
+    pub resources: Vec<String>,
+    pub resource_names: Vec<String>,
+    pub verbs: Vec<String>,
+}
+
+/// A non-resource URL rule the current credentials are allowed
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccessNonResourceRule {
+    pub non_resource_urls: Vec<String>,
+    pub verbs: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NamespaceAccessRules {
+    pub namespace: String,
+    pub resource_rules: Vec<AccessResourceRule>,
+    pub non_resource_rules: Vec<AccessNonResourceRule>,
+    pub incomplete: bool,
+    pub evaluation_error: Option<String>,
+    pub fetched_at: i64,
+}
+
+/// A single "can I <verb> <resource>" question
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct AccessCheck {
+    pub verb: String,
+    pub resource: String,
+    pub group: Option<String>,
+    pub subresource: Option<String>,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccessCheckResult {
+    pub check: AccessCheck,
+    pub allowed: bool,
+    pub denied: bool,
+    pub reason: Option<String>,
+    pub evaluation_error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AccessReviewCacheEntries {
+    rules: HashMap<(String, String), (Instant, NamespaceAccessRules)>,
+    checks: HashMap<(String, AccessCheck), (Instant, AccessCheckResult)>,
+}
+
+impl AccessReviewCacheEntries {
+    fn clear_cluster(&mut self, cluster_id: &str) {
+        self.rules.retain(|(id, _), _| id != cluster_id);
+        self.checks.retain(|(id, _), _| id != cluster_id);
+    }
+}
+
+/// Per-cluster cache of SelfSubject review results
+pub struct AccessReviewCache(pub Arc<Mutex<AccessReviewCacheEntries>>);
+
+impl Default for AccessReviewCache {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(AccessReviewCacheEntries::default())))
+    }
+}
+
+fn is_fresh(fetched: &Instant) -> bool {
+    fetched.elapsed() < ACCESS_REVIEW_TTL
+}
+
+/// List what the current credentials may do in a namespace (SelfSubjectRulesReview)
+#[tauri::command]
+pub async fn cluster_get_access_rules(
+    cluster_id: String,
+    namespace: String,
+    force_refresh: Option<bool>,
+    state: State<'_, ClusterManagerState>,
+    cache: State<'_, AccessReviewCache>,
+) -> Result<NamespaceAccessRules, String> {
+    let key = (cluster_id.clone(), namespace.clone());
+    if !force_refresh.unwrap_or(false) {
+        let entries = cache
+            .0
+            .lock()
+            .map_err(|e| format!("Access cache lock poisoned: {}", e))?;
+        if let Some((fetched, rules)) = entries.rules.get(&key) {
+            if is_fresh(fetched) {
+                return Ok(rules.clone());
+            }
+        }
+    }
+
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let api: Api<SelfSubjectRulesReview> = Api::all(client);
+    let review = SelfSubjectRulesReview {
+        spec: SelfSubjectRulesReviewSpec {
+            namespace: Some(namespace.clone()),
+        },
+        ..Default::default()
+    };
+
+    let response = api
+        .create(&PostParams::default(), &review)
+        .await
+        .map_err(|e| format!("Failed to review access rules: {}", e))?;
+    let status = response.status.unwrap_or_default();
+
+    let rules = NamespaceAccessRules {
+        namespace,
+        resource_rules: status
+            .resource_rules
+            .into_iter()
+            .map(|r| AccessResourceRule {
+                api_groups: r.api_groups.unwrap_or_default(),
+                resources: r.resources.unwrap_or_default(),
+                resource_names: r.resource_names.unwrap_or_default(),
+                verbs: r.verbs,
+            })
+            .collect(),
+        non_resource_rules: status
+            .non_resource_rules
+            .into_iter()
+            .map(|r| AccessNonResourceRule {
+                non_resource_urls: r.non_resource_urls.unwrap_or_default(),
+                verbs: r.verbs,
+            })
+            .collect(),
+        incomplete: status.incomplete,
+        evaluation_error: status.evaluation_error,
+        fetched_at: chrono::Utc::now().timestamp(),
+    };
+
+    cache
+        .0
+        .lock()
+        .map_err(|e| format!("Access cache lock poisoned: {}", e))?
+        .rules
+        .insert(key, (Instant::now(), rules.clone()));
+
+    Ok(rules)
+}
+
+async fn review_access(
+    api: &Api<SelfSubjectAccessReview>,
+    check: AccessCheck,
+) -> Result<AccessCheckResult, String> {
+    let review = SelfSubjectAccessReview {
+        spec: SelfSubjectAccessReviewSpec {
+            resource_attributes: Some(ResourceAttributes {
+                verb: Some(check.verb.clone()),
+                resource: Some(check.resource.clone()),
+                group: check.group.clone(),
+                subresource: check.subresource.clone(),
+                namespace: check.namespace.clone(),
+                name: check.name.clone(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let response = api
+        .create(&PostParams::default(), &review)
+        .await
+        .map_err(|e| format!("Failed to review access: {}", e))?;
+    let status = response.status.unwrap_or_default();
+
+    Ok(AccessCheckResult {
+        check,
+        allowed: status.allowed,
+        denied: status.denied.unwrap_or(false),
+        reason: status.reason,
+        evaluation_error: status.evaluation_error,
+    })
+}
+
+/// Check a batch of verb/resource pairs against the current credentials (SelfSubjectAccessReview)
+#[tauri::command]
+pub async fn cluster_check_access(
+    cluster_id: String,
+    checks: Vec<AccessCheck>,
+    force_refresh: Option<bool>,
+    state: State<'_, ClusterManagerState>,
+    cache: State<'_, AccessReviewCache>,
+) -> Result<Vec<AccessCheckResult>, String> {
+    let mut results: Vec<Option<AccessCheckResult>> = vec![None; checks.len()];
+    let mut pending = Vec::new();
+
+    {
+        let entries = cache
+            .0
+            .lock()
+            .map_err(|e| format!("Access cache lock poisoned: {}", e))?;
+        for (idx, check) in checks.iter().enumerate() {
+            let cached = entries
+                .checks
+                .get(&(cluster_id.clone(), check.clone()))
+                .filter(|(fetched, _)| !force_refresh.unwrap_or(false) && is_fresh(fetched));
+            match cached {
+                Some((_, result)) => results[idx] = Some(result.clone()),
+                None => pending.push(idx),
+            }
+        }
+    }
+
+    if !pending.is_empty() {
+        let client = create_client_for_cluster(&cluster_id, &state).await?;
+        let api: Api<SelfSubjectAccessReview> = Api::all(client);
+
+        let reviewed = futures::future::try_join_all(
+            pending
+                .iter()
+                .map(|idx| review_access(&api, checks[*idx].clone())),
+        )
+        .await?;
+
+        let mut entries = cache
+            .0
+            .lock()
+            .map_err(|e| format!("Access cache lock poisoned: {}", e))?;
+        for (idx, result) in pending.into_iter().zip(reviewed) {
+            entries.checks.insert(
+                (cluster_id.clone(), result.check.clone()),
+                (Instant::now(), result.clone()),
+            );
+            results[idx] = Some(result);
+        }
+    }
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Drop cached access reviews for a cluster (e.g. after its credentials changed)
+#[tauri::command]
+pub fn cluster_clear_access_cache(
+    cluster_id: String,
+    cache: State<'_, AccessReviewCache>,
+) -> Result<(), String> {
+    cache
+        .0
+        .lock()
+        .map_err(|e| format!("Access cache lock poisoned: {}", e))?
+        .clear_cluster(&cluster_id);
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::rbac::v1::AggregationRule;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
+    use std::collections::BTreeMap;
+
+    fn rule(groups: &[&str], resources: &[&str], verbs: &[&str]) -> PolicyRule {
+        PolicyRule {
+            api_groups: Some(groups.iter().map(|s| s.to_string()).collect()),
+            resources: Some(resources.iter().map(|s| s.to_string()).collect()),
+            verbs: verbs.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn cluster_role(name: &str, labels: &[(&str, &str)], rules: Vec<PolicyRule>) -> ClusterRole {
+        ClusterRole {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                labels: Some(
+                    labels
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            rules: Some(rules),
+            ..Default::default()
+        }
+    }
+
+    fn subject(kind: &str, name: &str, namespace: Option<&str>) -> Subject {
+        Subject {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            namespace: namespace.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn role_binding(
+        namespace: &str,
+        name: &str,
+        role_kind: &str,
+        role_name: &str,
+        subjects: Vec<Subject>,
+    ) -> RoleBinding {
+        RoleBinding {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(namespace.to_string()),
+                ..Default::default()
+            },
+            role_ref: RoleRef {
+                api_group: "rbac.authorization.k8s.io".to_string(),
+                kind: role_kind.to_string(),
+                name: role_name.to_string(),
+            },
+            subjects: Some(subjects),
+        }
+    }
+
+    #[test]
+    fn service_account_role_binding_grants_namespaced_permissions() {
+        let roles = vec![Role {
+            metadata: ObjectMeta {
+                name: Some("pod-reader".to_string()),
+                namespace: Some("team-a".to_string()),
+                ..Default::default()
+            },
+            rules: Some(vec![rule(&[""], &["pods"], &["get", "list"])]),
+        }];
+        // SA subject without namespace defaults to the binding namespace
+        let bindings = vec![role_binding(
+            "team-a",
+            "read-pods",
+            "Role",
+            "pod-reader",
+            vec![subject("ServiceAccount", "builder", None)],
+        )];
+        let sa = RbacSubject {
+            kind: "ServiceAccount".to_string(),
+            name: "builder".to_string(),
+            namespace: Some("team-a".to_string()),
+        };
+
+        let result = compute_effective_permissions(&sa, &roles, &[], &bindings, &[]);
+
+        assert_eq!(result.bindings, vec!["RoleBinding:team-a/read-pods"]);
+        assert_eq!(result.permissions.len(), 2);
+        assert!(result
+            .permissions
+            .iter()
+            .all(|p| p.namespace == "team-a" && p.resource == "pods"));
+        assert_eq!(result.permissions[0].role, "Role:pod-reader");
+    }
+
+    #[test]
+    fn service_account_in_other_namespace_does_not_match() {
+        let bindings = vec![role_binding(
+            "team-a",
+            "read-pods",
+            "ClusterRole",
+            "view",
+            vec![subject("ServiceAccount", "builder", Some("team-a"))],
+        )];
+        let sa = RbacSubject {
+            kind: "ServiceAccount".to_string(),
+            name: "builder".to_string(),
+            namespace: Some("team-b".to_string()),
+        };
+
+        let result = compute_effective_permissions(&sa, &[], &[], &bindings, &[]);
+        assert!(result.bindings.is_empty());
+        assert!(result.permissions.is_empty());
+    }
+
+    #[test]
+    fn aggregated_cluster_role_pulls_in_labelled_rules() {
+        let mut aggregate = cluster_role("monitoring", &[], vec![]);
+        aggregate.aggregation_rule = Some(AggregationRule {
+            cluster_role_selectors: Some(vec![LabelSelector {
+                match_labels: Some(BTreeMap::from([(
+                    "rbac.example.com/aggregate-to-monitoring".to_string(),
+                    "true".to_string(),
+                )])),
+                ..Default::default()
+            }]),
+        });
+        let cluster_roles = vec![
+            aggregate,
+            cluster_role(
+                "metrics-reader",
+                &[("rbac.example.com/aggregate-to-monitoring", "true")],
+                vec![rule(&["metrics.k8s.io"], &["pods"], &["get"])],
+            ),
+            cluster_role(
+                "unrelated",
+                &[],
+                vec![rule(&[""], &["secrets"], &["delete"])],
+            ),
+        ];
+        let crbs = vec![ClusterRoleBinding {
+            metadata: ObjectMeta {
+                name: Some("monitoring-binding".to_string()),
+                ..Default::default()
+            },
+            role_ref: RoleRef {
+                api_group: "rbac.authorization.k8s.io".to_string(),
+                kind: "ClusterRole".to_string(),
+                name: "monitoring".to_string(),
+            },
+            subjects: Some(vec![subject("Group", "sre", None)]),
+        }];
+        let group = RbacSubject {
+            kind: "Group".to_string(),
+            name: "sre".to_string(),
+            namespace: None,
+        };
+
+        let result = compute_effective_permissions(&group, &[], &cluster_roles, &[], &crbs);
+
+        assert_eq!(result.permissions.len(), 1);
+        let grant = &result.permissions[0];
+        assert_eq!(grant.namespace, "*");
+        assert_eq!(grant.api_group, "metrics.k8s.io");
+        assert_eq!(grant.binding, "ClusterRoleBinding:monitoring-binding");
+        assert_eq!(grant.role, "ClusterRole:monitoring");
+    }
+
+    #[test]
+    fn implicit_groups_apply_to_service_accounts() {
+        let cluster_roles = vec![cluster_role(
+            "discovery",
+            &[],
+            vec![PolicyRule {
+                non_resource_urls: Some(vec!["/healthz".to_string()]),
+                verbs: vec!["get".to_string()],
+                ..Default::default()
+            }],
+        )];
+        let crbs = vec![ClusterRoleBinding {
+            metadata: ObjectMeta {
+                name: Some("system:discovery".to_string()),
+                ..Default::default()
+            },
+            role_ref: RoleRef {
+                api_group: "rbac.authorization.k8s.io".to_string(),
+                kind: "ClusterRole".to_string(),
+                name: "discovery".to_string(),
+            },
+            subjects: Some(vec![subject("Group", "system:authenticated", None)]),
+        }];
+        let sa = RbacSubject {
+            kind: "ServiceAccount".to_string(),
+            name: "default".to_string(),
+            namespace: Some("default".to_string()),
+        };
+
+        let result = compute_effective_permissions(&sa, &[], &cluster_roles, &[], &crbs);
+
+        assert_eq!(result.permissions.len(), 1);
+        assert!(result.permissions[0].non_resource_url);
+        assert_eq!(result.permissions[0].resource, "/healthz");
+    }
+
+    #[test]
+    fn access_cache_clear_only_affects_one_cluster() {
+        let check = AccessCheck {
+            verb: "delete".to_string(),
+            resource: "pods".to_string(),
+            group: None,
+            subresource: None,
+            namespace: Some("default".to_string()),
+            name: None,
+        };
+        let result = AccessCheckResult {
+            check: check.clone(),
+            allowed: false,
+            denied: false,
+            reason: None,
+            evaluation_error: None,
+        };
+        let mut entries = AccessReviewCacheEntries::default();
+        entries.checks.insert(
+            ("a".to_string(), check.clone()),
+            (Instant::now(), result.clone()),
+        );
+        entries
+            .checks
+            .insert(("b".to_string(), check.clone()), (Instant::now(), result));
+
+        entries.clear_cluster("a");
+
+        assert!(!entries
+            .checks
+            .contains_key(&("a".to_string(), check.clone())));
+        assert!(entries.checks.contains_key(&("b".to_string(), check)));
+    }
+}
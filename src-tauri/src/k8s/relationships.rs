@@ -0,0 +1,785 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::dynamic::{dynamic_api, resolve_api_resource, split_api_version};
+use k8s_openapi::api::apps::v1::{
+    ControllerRevision, DaemonSet, Deployment, ReplicaSet, StatefulSet,
+};
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{PersistentVolumeClaim, Pod, PodSpec, Service};
+use k8s_openapi::api::discovery::v1::EndpointSlice;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use kube::api::{Api, DynamicObject, ListParams};
+use kube::core::GroupVersionKind;
+use kube::discovery::{self, ApiResource, Scope};
+use kube::Client;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tauri::State;
+
+/// Upper bound on ownerReference hops, guarding against cycles from broken owners
+const MAX_OWNER_DEPTH: usize = 16;
+
+/// A ConfigMap, Secret, PVC or ServiceAccount referenced from a pod spec
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SpecReference {
+    pub kind: String,
+    pub name: String,
+    pub field_path: String, // Relative to the pod spec, e.g. "volumes[0].configMap.name"
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RelationshipNode {
+    pub id: String,
+    pub kind: String,
+    pub api_version: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub uid: Option<String>,
+    pub missing: bool, // Referenced owner could not be fetched
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RelationshipEdge {
+    pub from: String,
+    pub to: String,
+    pub relation: String, // "owns" or "uses"
+    pub controller: bool,
+    pub field_path: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RelationshipGraph {
+    pub target: String,
+    pub root: String,
+    pub nodes: Vec<RelationshipNode>,
+    pub edges: Vec<RelationshipEdge>,
+    pub warnings: Vec<String>,
+}
+
+/// Object data needed to place it in the graph
+#[derive(Debug, Clone)]
+pub struct GraphObject {
+    pub kind: String,
+    pub api_version: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub uid: Option<String>,
+    pub owner_references: Vec<OwnerReference>,
+    pub pod_spec: Option<PodSpec>,
+    pub missing: bool,
+}
+
+/// Link from an object to one of its owners
+#[derive(Debug, Clone)]
+pub struct OwnerLink {
+    pub child: String,
+    pub owner: GraphObject,
+    pub controller: bool,
+}
+
+fn node_id(kind: &str, namespace: Option<&str>, name: &str) -> String {
+    match namespace.filter(|ns| !ns.is_empty()) {
+        Some(ns) => format!("{}/{}/{}", kind, ns, name),
+        None => format!("{}/{}", kind, name),
+    }
+}
+
+impl GraphObject {
+    pub fn id(&self) -> String {
+        node_id(&self.kind, self.namespace.as_deref(), &self.name)
+    }
+
+    pub fn from_dynamic(object: &DynamicObject, resource: &ApiResource) -> Self {
+        GraphObject {
+            kind: resource.kind.clone(),
+            api_version: resource.api_version.clone(),
+            name: object.metadata.name.clone().unwrap_or_default(),
+            namespace: object.metadata.namespace.clone(),
+            uid: object.metadata.uid.clone(),
+            owner_references: object.metadata.owner_references.clone().unwrap_or_default(),
+            pod_spec: pod_spec_from_data(&resource.kind, &object.data),
+            missing: false,
+        }
+    }
+
+    /// Placeholder for an owner that could not be fetched
+    fn from_owner_reference(owner: &OwnerReference, namespace: Option<String>) -> Self {
+        GraphObject {
+            kind: owner.kind.clone(),
+            api_version: owner.api_version.clone(),
+            name: owner.name.clone(),
+            namespace,
+            uid: Some(owner.uid.clone()),
+            owner_references: vec![],
+            pod_spec: None,
+            missing: true,
+        }
+    }
+
+    fn to_node(&self) -> RelationshipNode {
+        RelationshipNode {
+            id: self.id(),
+            kind: self.kind.clone(),
+            api_version: self.api_version.clone(),
+            name: self.name.clone(),
+            namespace: self.namespace.clone(),
+            uid: self.uid.clone(),
+            missing: self.missing,
+        }
+    }
+}
+
+/// Locate the pod spec (or pod template spec) inside a raw object body
+pub(crate) fn pod_spec_from_data(kind: &str, data: &serde_json::Value) -> Option<PodSpec> {
+    let pointer = match kind {
+        "Pod" => "/spec",
+        "CronJob" => "/spec/jobTemplate/spec/template/spec",
+        "Deployment"
+        | "ReplicaSet"
+        | "StatefulSet"
+        | "DaemonSet"
+        | "Job"
+        | "ReplicationController" => "/spec/template/spec",
+        _ => return None,
+    };
+    data.pointer(pointer)
+        .and_then(|spec| serde_json::from_value(spec.clone()).ok())
+}
+
+/// All ConfigMaps, Secrets, PVCs and the ServiceAccount referenced by a pod spec
+pub fn pod_spec_references(spec: &PodSpec) -> Vec<SpecReference> {
+    let mut refs = Vec::new();
+    let mut push = |kind: &str, name: &str, field_path: String, optional: Option<bool>| {
+        if !name.is_empty() {
+            refs.push(SpecReference {
+                kind: kind.to_string(),
+                name: name.to_string(),
+                field_path,
+                optional: optional.unwrap_or(false),
+            });
+        }
+    };
+
+    for (i, volume) in spec.volumes.iter().flatten().enumerate() {
+        if let Some(cm) = volume.config_map.as_ref() {
+            push(
+                "ConfigMap",
+                &cm.name,
+                format!("volumes[{}].configMap.name", i),
+                cm.optional,
+            );
+        }
+        if let Some(secret) = volume.secret.as_ref() {
+            push(
+                "Secret",
+                secret.secret_name.as_deref().unwrap_or_default(),
+                format!("volumes[{}].secret.secretName", i),
+                secret.optional,
+            );
+        }
+        if let Some(pvc) = volume.persistent_volume_claim.as_ref() {
+            push(
+                "PersistentVolumeClaim",
+                &pvc.claim_name,
+                format!("volumes[{}].persistentVolumeClaim.claimName", i),
+                None,
+            );
+        }
+        if let Some(projected) = volume.projected.as_ref() {
+            for (j, source) in projected.sources.iter().flatten().enumerate() {
+                if let Some(cm) = source.config_map.as_ref() {
+                    push(
+                        "ConfigMap",
+                        &cm.name,
+                        format!("volumes[{}].projected.sources[{}].configMap.name", i, j),
+                        cm.optional,
+                    );
+                }
+                if let Some(secret) = source.secret.as_ref() {
+                    push(
+                        "Secret",
+                        &secret.name,
+                        format!("volumes[{}].projected.sources[{}].secret.name", i, j),
+                        secret.optional,
+                    );
+                }
+            }
+        }
+    }
+
+    let containers = spec
+        .init_containers
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, c)| (format!("initContainers[{}]", i), c))
+        .chain(
+            spec.containers
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (format!("containers[{}]", i), c)),
+        );
+
+    for (prefix, container) in containers {
+        for (i, env) in container.env.iter().flatten().enumerate() {
+            let Some(value_from) = env.value_from.as_ref() else {
+                continue;
+            };
+            if let Some(cm) = value_from.config_map_key_ref.as_ref() {
+                push(
+                    "ConfigMap",
+                    &cm.name,
+                    format!("{}.env[{}].valueFrom.configMapKeyRef.name", prefix, i),
+                    cm.optional,
+                );
+            }
+            if let Some(secret) = value_from.secret_key_ref.as_ref() {
+                push(
+                    "Secret",
+                    &secret.name,
+                    format!("{}.env[{}].valueFrom.secretKeyRef.name", prefix, i),
+                    secret.optional,
+                );
+            }
+        }
+        for (i, env_from) in container.env_from.iter().flatten().enumerate() {
+            if let Some(cm) = env_from.config_map_ref.as_ref() {
+                push(
+                    "ConfigMap",
+                    &cm.name,
+                    format!("{}.envFrom[{}].configMapRef.name", prefix, i),
+                    cm.optional,
+                );
+            }
+            if let Some(secret) = env_from.secret_ref.as_ref() {
+                push(
+                    "Secret",
+                    &secret.name,
+                    format!("{}.envFrom[{}].secretRef.name", prefix, i),
+                    secret.optional,
+                );
+            }
+        }
+    }
+
+    for (i, pull_secret) in spec.image_pull_secrets.iter().flatten().enumerate() {
+        push(
+            "Secret",
+            &pull_secret.name,
+            format!("imagePullSecrets[{}].name", i),
+            None,
+        );
+    }
+
+    push(
+        "ServiceAccount",
+        spec.service_account_name.as_deref().unwrap_or("default"),
+        "serviceAccountName".to_string(),
+        None,
+    );
+
+    refs
+}
+
+#[derive(Default)]
+struct GraphBuilder {
+    nodes: Vec<RelationshipNode>,
+    seen_nodes: HashSet<String>,
+    edges: Vec<RelationshipEdge>,
+    seen_edges: HashSet<(String, String, String)>,
+}
+
+impl GraphBuilder {
+    fn add_object(&mut self, object: &GraphObject) -> String {
+        let id = object.id();
+        if self.seen_nodes.insert(id.clone()) {
+            self.nodes.push(object.to_node());
+            self.add_uses(object);
+        }
+        id
+    }
+
+    fn add_edge(
+        &mut self,
+        from: String,
+        to: String,
+        relation: &str,
+        controller: bool,
+        field_path: Option<String>,
+    ) {
+        if self
+            .seen_edges
+            .insert((from.clone(), to.clone(), relation.to_string()))
+        {
+            self.edges.push(RelationshipEdge {
+                from,
+                to,
+                relation: relation.to_string(),
+                controller,
+                field_path,
+            });
+        }
+    }
+
+    fn add_uses(&mut self, object: &GraphObject) {
+        let Some(spec) = object.pod_spec.as_ref() else {
+            return;
+        };
+        let from = object.id();
+        for reference in pod_spec_references(spec) {
+            let id = node_id(
+                &reference.kind,
+                object.namespace.as_deref(),
+                &reference.name,
+            );
+            if self.seen_nodes.insert(id.clone()) {
+                self.nodes.push(RelationshipNode {
+                    id: id.clone(),
+                    kind: reference.kind.clone(),
+                    api_version: "v1".to_string(),
+                    name: reference.name.clone(),
+                    namespace: object.namespace.clone(),
+                    uid: None,
+                    missing: false,
+                });
+            }
+            self.add_edge(from.clone(), id, "uses", false, Some(reference.field_path));
+        }
+    }
+}
+
+/// Assemble the graph from the target, its fetched owner chain and candidate dependents
+pub fn build_relationship_graph(
+    target: &GraphObject,
+    owners: &[OwnerLink],
+    candidates: &[GraphObject],
+    warnings: Vec<String>,
+) -> RelationshipGraph {
+    let mut builder = GraphBuilder::default();
+    let target_id = builder.add_object(target);
+
+    for link in owners {
+        let owner_id = builder.add_object(&link.owner);
+        builder.add_edge(owner_id, link.child.clone(), "owns", link.controller, None);
+    }
+
+    // Root controller: follow controller owners (or the first owner) from the target
+    let mut root = target_id.clone();
+    let mut visited = HashSet::from([root.clone()]);
+    while let Some(link) = owners
+        .iter()
+        .filter(|l| l.child == root)
+        .max_by_key(|l| l.controller)
+    {
+        let owner_id = link.owner.id();
+        if !visited.insert(owner_id.clone()) {
+            break;
+        }
+        root = owner_id;
+    }
+
+    // Dependents: breadth-first over candidates whose ownerReferences point at a known uid
+    let mut by_owner: HashMap<&str, Vec<(&GraphObject, bool)>> = HashMap::new();
+    for candidate in candidates {
+        for owner in &candidate.owner_references {
+            by_owner
+                .entry(owner.uid.as_str())
+                .or_default()
+                .push((candidate, owner.controller.unwrap_or(false)));
+        }
+    }
+
+    let mut queue: VecDeque<&GraphObject> = VecDeque::from([target]);
+    let mut expanded: HashSet<String> = HashSet::new();
+    while let Some(parent) = queue.pop_front() {
+        let Some(uid) = parent.uid.as_deref() else {
+            continue;
+        };
+        if !expanded.insert(uid.to_string()) {
+            continue;
+        }
+        for (child, controller) in by_owner.get(uid).into_iter().flatten() {
+            let child_id = builder.add_object(child);
+            builder.add_edge(parent.id(), child_id, "owns", *controller, None);
+            queue.push_back(child);
+        }
+    }
+
+    RelationshipGraph {
+        target: target_id,
+        root,
+        nodes: builder.nodes,
+        edges: builder.edges,
+        warnings,
+    }
+}
+
+/// Walk ownerReferences upwards, fetching each owner through discovery
+async fn fetch_owner_chain(client: &Client, target: &GraphObject) -> Vec<OwnerLink> {
+    let mut links = Vec::new();
+    let mut queue: VecDeque<(GraphObject, usize)> = VecDeque::from([(target.clone(), 0)]);
+    let mut visited: HashSet<String> = HashSet::new();
+
+    while let Some((child, depth)) = queue.pop_front() {
+        if depth >= MAX_OWNER_DEPTH {
+            continue;
+        }
+        for owner_ref in &child.owner_references {
+            let (group, version) = split_api_version(&owner_ref.api_version);
+            let gvk = GroupVersionKind::gvk(&group, &version, &owner_ref.kind);
+
+            let fetched = match discovery::pinned_kind(client, &gvk).await {
+                Ok((resource, capabilities)) => {
+                    let namespace = match capabilities.scope {
+                        Scope::Namespaced => child.namespace.as_deref(),
+                        Scope::Cluster => None,
+                    };
+                    dynamic_api(client.clone(), &resource, &capabilities, namespace)
+                        .get(&owner_ref.name)
+                        .await
+                        .ok()
+                        .map(|obj| GraphObject::from_dynamic(&obj, &resource))
+                }
+                Err(_) => None,
+            };
+
+            let owner = fetched.unwrap_or_else(|| {
+                GraphObject::from_owner_reference(owner_ref, child.namespace.clone())
+            });
+
+            links.push(OwnerLink {
+                child: child.id(),
+                owner: owner.clone(),
+                controller: owner_ref.controller.unwrap_or(false),
+            });
+            if !owner.missing && visited.insert(owner_ref.uid.clone()) {
+                queue.push_back((owner, depth + 1));
+            }
+        }
+    }
+
+    links
+}
+
+/// List the kinds that are commonly owned by other objects
+async fn list_dependent_candidates(
+    client: &Client,
+    namespace: Option<&str>,
+) -> (Vec<GraphObject>, Vec<String>) {
+    let resources = [
+        ApiResource::erase::<Deployment>(&()),
+        ApiResource::erase::<ReplicaSet>(&()),
+        ApiResource::erase::<StatefulSet>(&()),
+        ApiResource::erase::<DaemonSet>(&()),
+        ApiResource::erase::<ControllerRevision>(&()),
+        ApiResource::erase::<CronJob>(&()),
+        ApiResource::erase::<Job>(&()),
+        ApiResource::erase::<Pod>(&()),
+        ApiResource::erase::<Service>(&()),
+        ApiResource::erase::<EndpointSlice>(&()),
+        ApiResource::erase::<PersistentVolumeClaim>(&()),
+    ];
+
+    let lists = futures::future::join_all(resources.iter().map(|resource| async move {
+        let api: Api<DynamicObject> = match namespace {
+            Some(ns) => Api::namespaced_with(client.clone(), ns, resource),
+            None => Api::all_with(client.clone(), resource),
+        };
+        (resource, api.list(&ListParams::default()).await)
+    }))
+    .await;
+
+    let mut candidates = Vec::new();
+    let mut warnings = Vec::new();
+    for (resource, result) in lists {
+        match result {
+            Ok(list) => candidates.extend(
+                list.items
+                    .iter()
+                    .map(|obj| GraphObject::from_dynamic(obj, resource)),
+            ),
+            Err(e) => warnings.push(format!("Failed to list {}: {}", resource.plural, e)),
+        }
+    }
+    (candidates, warnings)
+}
+
+/// Build the ownership graph of any object: owners up to the root controller, all
+/// dependents below it, and the ConfigMaps/Secrets/PVCs/ServiceAccounts its pod specs use.
+#[tauri::command]
+pub async fn cluster_get_resource_relationships(
+    cluster_id: String,
+    kind: String,
+    name: String,
+    namespace: Option<String>,
+    api_version: Option<String>,
+    state: State<'_, ClusterManagerState>,
+) -> Result<RelationshipGraph, String> {
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let (resource, capabilities) =
+        resolve_api_resource(&client, api_version.as_deref(), &kind).await?;
+
+    let object = dynamic_api(
+        client.clone(),
+        &resource,
+        &capabilities,
+        namespace.as_deref(),
+    )
+    .get(&name)
+    .await
+    .map_err(|e| format!("Failed to get {} '{}': {}", resource.kind, name, e))?;
+    let target = GraphObject::from_dynamic(&object, &resource);
+
+    let (owners, (candidates, warnings)) = futures::join!(
+        fetch_owner_chain(&client, &target),
+        list_dependent_candidates(&client, target.namespace.as_deref()),
+    );
+
+    Ok(build_relationship_graph(
+        &target,
+        &owners,
+        &candidates,
+        warnings,
+    ))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{
+        ConfigMapVolumeSource, Container, EnvFromSource, EnvVar, EnvVarSource,
+        LocalObjectReference, PersistentVolumeClaimVolumeSource, SecretEnvSource,
+        SecretKeySelector, Volume,
+    };
+
+    fn object(kind: &str, name: &str, uid: &str, owner: Option<(&str, &str, &str)>) -> GraphObject {
+        GraphObject {
+            kind: kind.to_string(),
+            api_version: "v1".to_string(),
+            name: name.to_string(),
+            namespace: Some("prod".to_string()),
+            uid: Some(uid.to_string()),
+            owner_references: owner
+                .map(|(kind, name, uid)| {
+                    vec![OwnerReference {
+                        api_version: "apps/v1".to_string(),
+                        kind: kind.to_string(),
+                        name: name.to_string(),
+                        uid: uid.to_string(),
+                        controller: Some(true),
+                        ..Default::default()
+                    }]
+                })
+                .unwrap_or_default(),
+            pod_spec: None,
+            missing: false,
+        }
+    }
+
+    fn sample_pod_spec() -> PodSpec {
+        PodSpec {
+            containers: vec![Container {
+                name: "app".to_string(),
+                env: Some(vec![EnvVar {
+                    name: "PASSWORD".to_string(),
+                    value_from: Some(EnvVarSource {
+                        secret_key_ref: Some(SecretKeySelector {
+                            name: "db-creds".to_string(),
+                            key: "password".to_string(),
+                            optional: None,
+                        }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }]),
+                env_from: Some(vec![EnvFromSource {
+                    secret_ref: Some(SecretEnvSource {
+                        name: "extra".to_string(),
+                        optional: Some(true),
+                    }),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }],
+            volumes: Some(vec![
+                Volume {
+                    name: "config".to_string(),
+                    config_map: Some(ConfigMapVolumeSource {
+                        name: "app-config".to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                Volume {
+                    name: "data".to_string(),
+                    persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                        claim_name: "data-pvc".to_string(),
+                        read_only: None,
+                    }),
+                    ..Default::default()
+                },
+            ]),
+            image_pull_secrets: Some(vec![LocalObjectReference {
+                name: "registry".to_string(),
+            }]),
+            service_account_name: Some("app-sa".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pod_spec_references_collects_all_sources() {
+        let refs = pod_spec_references(&sample_pod_spec());
+        let summary: Vec<(&str, &str, &str, bool)> = refs
+            .iter()
+            .map(|r| {
+                (
+                    r.kind.as_str(),
+                    r.name.as_str(),
+                    r.field_path.as_str(),
+                    r.optional,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "ConfigMap",
+                    "app-config",
+                    "volumes[0].configMap.name",
+                    false
+                ),
+                (
+                    "PersistentVolumeClaim",
+                    "data-pvc",
+                    "volumes[1].persistentVolumeClaim.claimName",
+                    false
+                ),
+                (
+                    "Secret",
+                    "db-creds",
+                    "containers[0].env[0].valueFrom.secretKeyRef.name",
+                    false
+                ),
+                (
+                    "Secret",
+                    "extra",
+                    "containers[0].envFrom[0].secretRef.name",
+                    true
+                ),
+                ("Secret", "registry", "imagePullSecrets[0].name", false),
+                ("ServiceAccount", "app-sa", "serviceAccountName", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn pod_spec_from_data_follows_templates() {
+        let data = serde_json::json!({
+            "spec": {"jobTemplate": {"spec": {"template": {"spec": {
+                "containers": [{"name": "job"}]
+            }}}}}
+        });
+        let spec = pod_spec_from_data("CronJob", &data).unwrap();
+        assert_eq!(spec.containers[0].name, "job");
+        assert!(pod_spec_from_data("ConfigMap", &data).is_none());
+    }
+
+    #[test]
+    fn graph_walks_up_to_root_and_down_to_dependents() {
+        let mut pod = object(
+            "Pod",
+            "web-abc-1",
+            "pod-1",
+            Some(("ReplicaSet", "web-abc", "rs-1")),
+        );
+        pod.pod_spec = Some(sample_pod_spec());
+        let rs = object(
+            "ReplicaSet",
+            "web-abc",
+            "rs-1",
+            Some(("Deployment", "web", "dep-1")),
+        );
+        let deployment = object("Deployment", "web", "dep-1", None);
+        let other_pod = object(
+            "Pod",
+            "web-abc-2",
+            "pod-2",
+            Some(("ReplicaSet", "web-abc", "rs-1")),
+        );
+
+        // From the Deployment: dependents only
+        let graph = build_relationship_graph(
+            &deployment,
+            &[],
+            &[pod.clone(), rs.clone(), other_pod.clone()],
+            vec![],
+        );
+        assert_eq!(graph.root, "Deployment/prod/web");
+        let owns: Vec<(&str, &str)> = graph
+            .edges
+            .iter()
+            .filter(|e| e.relation == "owns")
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+        assert_eq!(
+            owns,
+            vec![
+                ("Deployment/prod/web", "ReplicaSet/prod/web-abc"),
+                ("ReplicaSet/prod/web-abc", "Pod/prod/web-abc-1"),
+                ("ReplicaSet/prod/web-abc", "Pod/prod/web-abc-2"),
+            ]
+        );
+        assert!(graph
+            .edges
+            .iter()
+            .any(|e| e.relation == "uses" && e.to == "ConfigMap/prod/app-config"));
+
+        // From the pod: owner chain up to the Deployment
+        let owners = vec![
+            OwnerLink {
+                child: pod.id(),
+                owner: rs.clone(),
+                controller: true,
+            },
+            OwnerLink {
+                child: rs.id(),
+                owner: deployment.clone(),
+                controller: true,
+            },
+        ];
+        let graph = build_relationship_graph(&pod, &owners, &[], vec![]);
+        assert_eq!(graph.target, "Pod/prod/web-abc-1");
+        assert_eq!(graph.root, "Deployment/prod/web");
+    }
+
+    #[test]
+    fn missing_owner_becomes_placeholder_root() {
+        let pod = object(
+            "Pod",
+            "orphan",
+            "pod-1",
+            Some(("ReplicaSet", "gone", "rs-x")),
+        );
+        let placeholder =
+            GraphObject::from_owner_reference(&pod.owner_references[0], pod.namespace.clone());
+        let graph = build_relationship_graph(
+            &pod,
+            &[OwnerLink {
+                child: pod.id(),
+                owner: placeholder,
+                controller: true,
+            }],
+            &[],
+            vec![],
+        );
+        assert_eq!(graph.root, "ReplicaSet/prod/gone");
+        assert!(graph
+            .nodes
+            .iter()
+            .any(|n| n.id == "ReplicaSet/prod/gone" && n.missing));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::tasks::{BackgroundTaskState, TaskInfo};
+use futures::StreamExt;
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{ConfigMap, PersistentVolumeClaim, Pod, Service};
+use k8s_openapi::api::networking::v1::Ingress;
+use kube::runtime::reflector::{self, Store};
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::{Api, Client, Resource, ResourceExt};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tauri::{State, Window};
+
+/// How current a cached kind is, so list views can show a freshness indicator
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct CacheFreshness {
+    pub kind: String,
+    pub ready: bool, // Initial list completed; list commands are served from the cache
+    pub stopped: bool, // Watch ended (cluster closed, window destroyed); cache is no longer used
+    pub synced_at: Option<i64>, // Last completed (re-)list
+    pub last_event_at: Option<i64>,
+    pub object_count: usize,
+    pub last_error: Option<String>, // Most recent watch error; the watch retries with backoff
+}
+
+struct CacheEntry {
+    store: Box<dyn Any + Send + Sync>, // Store<K> for the kind this entry was created for
+    len: Box<dyn Fn() -> usize + Send + Sync>,
+    freshness: Arc<Mutex<CacheFreshness>>,
+}
+
+/// Reflector stores per cluster, keyed by kind
+#[derive(Default)]
+pub struct ResourceCaches {
+    clusters: HashMap<String, HashMap<String, CacheEntry>>,
+}
+
+impl ResourceCaches {
+    fn insert<K>(
+        &mut self,
+        cluster_id: &str,
+        store: Store<K>,
+        freshness: Arc<Mutex<CacheFreshness>>,
+    ) where
+        K: Resource<DynamicType = ()> + Clone + Send + Sync + 'static,
+    {
+        let reader = store.clone();
+        let entry = CacheEntry {
+            store: Box::new(store),
+            len: Box::new(move || reader.len()),
+            freshness,
+        };
+        self.clusters
+            .entry(cluster_id.to_string())
+            .or_default()
+            .insert(K::kind(&()).to_string(), entry);
+    }
+
+    /// Objects of kind `K` from a warm cache, or None when the kind is not cached,
+    /// still syncing, or its watch has stopped
+    pub fn list<K>(&self, cluster_id: &str, namespace: Option<&str>) -> Option<Vec<K>>
+    where
+        K: Resource<DynamicType = ()> + Clone + 'static,
+    {
+        let entry = self.clusters.get(cluster_id)?.get(K::kind(&()).as_ref())?;
+        {
+            let freshness = entry.freshness.lock().ok()?;
+            if !freshness.ready || freshness.stopped {
+                return None;
+            }
+        }
+        let store = entry.store.downcast_ref::<Store<K>>()?;
+
+        let namespace = namespace.filter(|ns| !ns.is_empty() && *ns != "all");
+        Some(
+            store
+                .state()
+                .into_iter()
+                .filter(|obj| namespace.is_none() || obj.namespace().as_deref() == namespace)
+                .map(|obj| (*obj).clone())
+                .collect(),
+        )
+    }
+
+    pub fn is_enabled(&self, cluster_id: &str) -> bool {
+        self.clusters.contains_key(cluster_id)
+    }
+
+    pub fn status(&self, cluster_id: &str) -> Vec<CacheFreshness> {
+        let mut status: Vec<CacheFreshness> = self
+            .clusters
+            .get(cluster_id)
+            .into_iter()
+            .flat_map(|kinds| kinds.values())
+            .filter_map(|entry| {
+                let mut freshness = entry.freshness.lock().ok()?.clone();
+                freshness.object_count = (entry.len)();
+                Some(freshness)
+            })
+            .collect();
+        status.sort_by(|a, b| a.kind.cmp(&b.kind));
+        status
+    }
+
+    fn remove_cluster(&mut self, cluster_id: &str) -> bool {
+        self.clusters.remove(cluster_id).is_some()
+    }
+
+    /// Drop a cluster's stores once every cache watch of it has stopped, so it can be re-enabled
+    fn remove_cluster_if_stopped(&mut self, cluster_id: &str) -> bool {
+        let all_stopped = self.clusters.get(cluster_id).is_some_and(|kinds| {
+            kinds
+                .values()
+                .all(|entry| entry.freshness.lock().map_or(true, |f| f.stopped))
+        });
+        all_stopped && self.remove_cluster(cluster_id)
+    }
+}
+
+/// Opt-in per-cluster cache of commonly listed kinds, fed by watches
+#[derive(Default)]
+pub struct ResourceCacheState(pub Arc<Mutex<ResourceCaches>>);
+
+impl ResourceCacheState {
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ResourceCaches>, String> {
+        self.0
+            .lock()
+            .map_err(|e| format!("Resource cache lock poisoned: {}", e))
+    }
+
+    /// Serve a list from the cache when it is warm; callers fall back to a live LIST on None
+    pub fn list<K>(&self, cluster_id: &str, namespace: Option<&str>) -> Option<Vec<K>>
+    where
+        K: Resource<DynamicType = ()> + Clone + 'static,
+    {
+        self.lock().ok()?.list(cluster_id, namespace)
+    }
+}
+
+/// Marks the cache entry as stopped when its watch task ends or is aborted, and
+/// removes the cluster's caches when the last of its watches is gone
+struct StopGuard {
+    caches: Arc<Mutex<ResourceCaches>>,
+    cluster_id: String,
+    freshness: Arc<Mutex<CacheFreshness>>,
+}
+
+impl Drop for StopGuard {
+    fn drop(&mut self) {
+        if let Ok(mut freshness) = self.freshness.lock() {
+            freshness.ready = false;
+            freshness.stopped = true;
+        }
+        if let Ok(mut caches) = self.caches.lock() {
+            caches.remove_cluster_if_stopped(&self.cluster_id);
+        }
+    }
+}
+
+fn record_watch_event<K>(
+    freshness: &Mutex<CacheFreshness>,
+    result: &Result<watcher::Event<K>, watcher::Error>,
+) {
+    let Ok(mut freshness) = freshness.lock() else {
+        return;
+    };
+    let now = chrono::Utc::now().timestamp();
+    match result {
+        Ok(watcher::Event::InitDone) => {
+            freshness.ready = true;
+            freshness.synced_at = Some(now);
+            freshness.last_event_at = Some(now);
+            freshness.last_error = None;
+        }
+        Ok(_) => freshness.last_event_at = Some(now),
+        Err(e) => freshness.last_error = Some(e.to_string()),
+    }
+}
+
+fn start_cached_kind<K>(
+    client: Client,
+    cluster_id: &str,
+    window: &Window,
+    caches: &ResourceCacheState,
+    tasks: &BackgroundTaskState,
+) -> Result<(), String>
+where
+    K: Resource<DynamicType = ()>
+        + Clone
+        + serde::de::DeserializeOwned
+        + Debug
+        + Send
+        + Sync
+        + 'static,
+{
+    let kind = K::kind(&()).to_string();
+    let (store, writer) = reflector::store::<K>();
+    let freshness = Arc::new(Mutex::new(CacheFreshness {
+        kind: kind.clone(),
+        ..Default::default()
+    }));
+    caches.lock()?.insert(cluster_id, store, freshness.clone());
+    let guard = StopGuard {
+        caches: caches.0.clone(),
+        cluster_id: cluster_id.to_string(),
+        freshness: freshness.clone(),
+    };
+
+    let api: Api<K> = Api::all(client);
+    let info = TaskInfo::new(
+        format!("cache:{}:{}", cluster_id, kind),
+        "cache",
+        Some(cluster_id),
+        window,
+        format!("{} cache", kind),
+    );
+
+    tasks.spawn(info, async move {
+        let _guard = guard;
+        let mut stream = watcher(api, watcher::Config::default())
+            .default_backoff()
+            // Managed fields are never shown in list views and dominate object size
+            .modify(|obj| obj.managed_fields_mut().clear())
+            .reflect(writer)
+            .boxed();
+
+        while let Some(result) = stream.next().await {
+            record_watch_event(&freshness, &result);
+        }
+    })
+}
+
+/// Start watch-fed caches for the commonly viewed kinds of a cluster. Returns the
+/// current cache status; calling it again for an enabled cluster is a no-op.
+#[tauri::command]
+pub async fn cluster_enable_cache(
+    cluster_id: String,
+    window: Window,
+    state: State<'_, ClusterManagerState>,
+    caches: State<'_, ResourceCacheState>,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<Vec<CacheFreshness>, String> {
+    if caches.lock()?.is_enabled(&cluster_id) {
+        return Ok(caches.lock()?.status(&cluster_id));
+    }
+
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let caches = caches.inner();
+    let tasks = tasks.inner();
+
+    start_cached_kind::<Pod>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<Deployment>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<StatefulSet>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<DaemonSet>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<ReplicaSet>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<Job>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<CronJob>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<Service>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<Ingress>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<ConfigMap>(client.clone(), &cluster_id, &window, caches, tasks)?;
+    start_cached_kind::<PersistentVolumeClaim>(client, &cluster_id, &window, caches, tasks)?;
+
+    Ok(caches.lock()?.status(&cluster_id))
+}
+
+/// Stop the cache watches of a cluster and drop its stores (e.g. when the cluster is closed)
+#[tauri::command]
+pub fn cluster_disable_cache(
+    cluster_id: String,
+    caches: State<'_, ResourceCacheState>,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<bool, String> {
+    tasks.stop_prefix(&format!("cache:{}:", cluster_id))?;
+    Ok(caches.lock()?.remove_cluster(&cluster_id))
+}
+
+#[tauri::command]
+pub fn cluster_get_cache_status(
+    cluster_id: String,
+    caches: State<'_, ResourceCacheState>,
+) -> Result<Vec<CacheFreshness>, String> {
+    Ok(caches.lock()?.status(&cluster_id))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    fn pod(namespace: &str, name: &str) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(namespace.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn synced_pod_cache(caches: &mut ResourceCaches) -> Arc<Mutex<CacheFreshness>> {
+        let (store, mut writer) = reflector::store::<Pod>();
+        let freshness = Arc::new(Mutex::new(CacheFreshness::default()));
+        caches.insert("c1", store, freshness.clone());
+
+        writer.apply_watcher_event(&watcher::Event::Init);
+        writer.apply_watcher_event(&watcher::Event::InitApply(pod("default", "a")));
+        writer.apply_watcher_event(&watcher::Event::InitApply(pod("kube-system", "b")));
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        freshness
+    }
+
+    #[test]
+    fn test_cache_not_served_until_synced() {
+        let mut caches = ResourceCaches::default();
+        let freshness = synced_pod_cache(&mut caches);
+        assert!(caches.list::<Pod>("c1", None).is_none());
+
+        record_watch_event::<Pod>(&freshness, &Ok(watcher::Event::InitDone));
+        assert_eq!(caches.list::<Pod>("c1", None).unwrap().len(), 2);
+        assert!(freshness.lock().unwrap().synced_at.is_some());
+    }
+
+    #[test]
+    fn test_cache_filters_by_namespace() {
+        let mut caches = ResourceCaches::default();
+        let freshness = synced_pod_cache(&mut caches);
+        record_watch_event::<Pod>(&freshness, &Ok(watcher::Event::InitDone));
+
+        let pods = caches.list::<Pod>("c1", Some("default")).unwrap();
+        assert_eq!(pods.len(), 1);
+        assert_eq!(pods[0].name_any(), "a");
+        assert_eq!(caches.list::<Pod>("c1", Some("all")).unwrap().len(), 2);
+
+        // Other kinds and clusters are not cached
+        assert!(caches.list::<Deployment>("c1", None).is_none());
+        assert!(caches.list::<Pod>("c2", None).is_none());
+    }
+
+    #[test]
+    fn test_stopped_or_removed_cache_is_not_served() {
+        let caches = Arc::new(Mutex::new(ResourceCaches::default()));
+        let pods = synced_pod_cache(&mut caches.lock().unwrap());
+        record_watch_event::<Pod>(&pods, &Ok(watcher::Event::InitDone));
+        let (store, _writer) = reflector::store::<Deployment>();
+        let deployments = Arc::new(Mutex::new(CacheFreshness::default()));
+        caches
+            .lock()
+            .unwrap()
+            .insert("c1", store, deployments.clone());
+
+        let guard = |freshness: &Arc<Mutex<CacheFreshness>>| StopGuard {
+            caches: caches.clone(),
+            cluster_id: "c1".to_string(),
+            freshness: freshness.clone(),
+        };
+
+        drop(guard(&pods));
+        {
+            let caches = caches.lock().unwrap();
+            assert!(caches.list::<Pod>("c1", None).is_none());
+            let status = caches.status("c1");
+            assert!(status.iter().any(|s| s.stopped && s.object_count == 2));
+            assert!(caches.is_enabled("c1"));
+        }
+
+        // The last watch ending frees the stores so the cache can be enabled again
+        drop(guard(&deployments));
+        let caches = caches.lock().unwrap();
+        assert!(!caches.is_enabled("c1"));
+        assert!(caches.status("c1").is_empty());
+    }
+}
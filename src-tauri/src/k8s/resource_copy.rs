@@ -0,0 +1,368 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::mutate::{apply_resource_yaml, get_resource_yaml};
+use crate::k8s::relationships::{pod_spec_from_data, pod_spec_references};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use tauri::State;
+
+/// Metadata owned by the source cluster; re-applying it elsewhere is rejected or misleading
+const SOURCE_METADATA: &[&str] = &[
+    "uid",
+    "resourceVersion",
+    "generation",
+    "creationTimestamp",
+    "deletionTimestamp",
+    "deletionGracePeriodSeconds",
+    "managedFields",
+    "selfLink",
+    "ownerReferences",
+    "finalizers",
+];
+
+const SOURCE_ANNOTATIONS: &[&str] = &[
+    "kubectl.kubernetes.io/last-applied-configuration",
+    "deployment.kubernetes.io/revision",
+];
+
+/// Secrets the target cluster generates for itself
+const GENERATED_SECRET_TYPES: &[&str] = &["kubernetes.io/service-account-token"];
+
+/// Strip everything the source cluster assigned (status, identity, allocated
+/// IPs and node placement) and optionally move the object to another namespace.
+pub fn clean_for_copy(mut value: Value, target_namespace: Option<&str>) -> Value {
+    let Some(object) = value.as_object_mut() else {
+        return value;
+    };
+    object.remove("status");
+    let kind = object
+        .get("kind")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    if let Some(metadata) = object.get_mut("metadata").and_then(Value::as_object_mut) {
+        for key in SOURCE_METADATA {
+            metadata.remove(*key);
+        }
+        if let Some(annotations) = metadata
+            .get_mut("annotations")
+            .and_then(Value::as_object_mut)
+        {
+            for key in SOURCE_ANNOTATIONS {
+                annotations.remove(*key);
+            }
+            if annotations.is_empty() {
+                metadata.remove("annotations");
+            }
+        }
+        // Only namespaced objects carry a namespace; cluster-scoped ones stay as they are
+        if let Some(ns) = target_namespace.filter(|ns| !ns.is_empty()) {
+            if metadata.contains_key("namespace") {
+                metadata.insert("namespace".to_string(), Value::String(ns.to_string()));
+            }
+        }
+    }
+
+    if let Some(spec) = object.get_mut("spec").and_then(Value::as_object_mut) {
+        match kind.as_str() {
+            "Service" => {
+                // "None" marks a headless Service; it is authored, not allocated
+                let headless = spec.get("clusterIP").and_then(Value::as_str) == Some("None");
+                let allocated: &[&str] = if headless {
+                    &["healthCheckNodePort"]
+                } else {
+                    &["clusterIP", "clusterIPs", "healthCheckNodePort"]
+                };
+                for key in allocated {
+                    spec.remove(*key);
+                }
+                if let Some(ports) = spec.get_mut("ports").and_then(Value::as_array_mut) {
+                    for port in ports.iter_mut().filter_map(Value::as_object_mut) {
+                        port.remove("nodePort");
+                    }
+                }
+            }
+            "Pod" => {
+                spec.remove("nodeName");
+            }
+            "PersistentVolumeClaim" => {
+                spec.remove("volumeName");
+            }
+            _ => {}
+        }
+    }
+    value
+}
+
+/// ConfigMaps and Secrets an object's pod spec requires; optional references are skipped
+pub fn referenced_config(value: &Value) -> (BTreeSet<String>, BTreeSet<String>) {
+    let mut config_maps = BTreeSet::new();
+    let mut secrets = BTreeSet::new();
+    let kind = value
+        .get("kind")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let Some(spec) = pod_spec_from_data(kind, value) else {
+        return (config_maps, secrets);
+    };
+
+    for reference in pod_spec_references(&spec) {
+        if reference.optional {
+            continue;
+        }
+        match reference.kind.as_str() {
+            "ConfigMap" => config_maps.insert(reference.name),
+            "Secret" => secrets.insert(reference.name),
+            _ => false,
+        };
+    }
+    (config_maps, secrets)
+}
+
+fn to_manifest(objects: &[Value]) -> Result<String, String> {
+    let docs = objects
+        .iter()
+        .map(|o| serde_yaml::to_string(o).map_err(|e| format!("Failed to serialize YAML: {}", e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(docs.join("---\n"))
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct CopyResourceParams {
+    pub kind: String,
+    pub name: String,
+    pub source_cluster_id: String,
+    pub source_namespace: Option<String>,
+    pub target_cluster_id: String,
+    pub target_namespace: Option<String>, // None keeps the source namespace
+    pub include_dependencies: Option<bool>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CopiedObject {
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CopyResourceResult {
+    pub dry_run: bool,
+    pub objects: Vec<CopiedObject>, // Dependencies first, in apply order
+    pub skipped: Vec<String>,       // Dependencies that should not be copied
+    pub manifest: String,
+    pub output: String, // kubectl apply output
+}
+
+fn fetch_cleaned(
+    cluster_id: &str,
+    kind: &str,
+    name: &str,
+    namespace: Option<&str>,
+    target_namespace: Option<&str>,
+    state: &State<'_, ClusterManagerState>,
+) -> Result<Value, String> {
+    let yaml = get_resource_yaml(cluster_id, kind, name, namespace, state)?;
+    let value: Value = serde_yaml::from_str(&yaml)
+        .map_err(|e| format!("Failed to parse {} {}: {}", kind, name, e))?;
+    Ok(clean_for_copy(value, target_namespace))
+}
+
+fn copied_object(value: &Value) -> CopiedObject {
+    let field = |pointer: &str| {
+        value
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    CopiedObject {
+        kind: field("/kind").unwrap_or_default(),
+        name: field("/metadata/name").unwrap_or_default(),
+        namespace: field("/metadata/namespace"),
+    }
+}
+
+/// Copy an object (and optionally the ConfigMaps/Secrets it uses) to another
+/// cluster and/or namespace. Call with `dry_run` first to preview the server's verdict.
+#[tauri::command]
+pub async fn cluster_copy_resource(
+    params: CopyResourceParams,
+    state: State<'_, ClusterManagerState>,
+) -> Result<CopyResourceResult, String> {
+    let source_ns = params.source_namespace.as_deref();
+    let target_ns = params.target_namespace.as_deref();
+    if params.source_cluster_id == params.target_cluster_id
+        && target_ns.is_none_or(|ns| Some(ns) == source_ns)
+    {
+        return Err(
+            "Source and target are the same; pick another cluster or namespace".to_string(),
+        );
+    }
+
+    let primary = fetch_cleaned(
+        &params.source_cluster_id,
+        &params.kind,
+        &params.name,
+        source_ns,
+        target_ns,
+        &state,
+    )?;
+
+    let mut objects = Vec::new();
+    let mut skipped = Vec::new();
+    if params.include_dependencies.unwrap_or(false) {
+        let (config_maps, secrets) = referenced_config(&primary);
+        let dependencies = config_maps
+            .iter()
+            .map(|name| ("configmap", name))
+            .chain(secrets.iter().map(|name| ("secret", name)));
+        for (kind, name) in dependencies {
+            // Only required references are listed, so the copy fails rather than
+            // leaving the target pods stuck in CreateContainerConfigError
+            let object = fetch_cleaned(
+                &params.source_cluster_id,
+                kind,
+                name,
+                source_ns,
+                target_ns,
+                &state,
+            )
+            .map_err(|e| format!("Failed to copy required {} '{}': {}", kind, name, e))?;
+            let secret_type = object.get("type").and_then(Value::as_str);
+            if secret_type.is_some_and(|t| GENERATED_SECRET_TYPES.contains(&t)) {
+                skipped.push(format!(
+                    "{}/{}: generated by the target cluster",
+                    kind, name
+                ));
+            } else {
+                objects.push(object);
+            }
+        }
+    }
+    objects.push(primary);
+
+    let manifest = to_manifest(&objects)?;
+    let output = apply_resource_yaml(&params.target_cluster_id, &manifest, params.dry_run, &state)?;
+
+    Ok(CopyResourceResult {
+        dry_run: params.dry_run,
+        objects: objects.iter().map(copied_object).collect(),
+        skipped,
+        manifest,
+        output,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn deployment() -> Value {
+        json!({
+            "apiVersion": "apps/v1",
+            "kind": "Deployment",
+            "metadata": {
+                "name": "api",
+                "namespace": "staging",
+                "uid": "abc",
+                "resourceVersion": "42",
+                "labels": {"app": "api"},
+                "annotations": {"kubectl.kubernetes.io/last-applied-configuration": "{}"}
+            },
+            "spec": {
+                "replicas": 2,
+                "template": {"spec": {
+                    "imagePullSecrets": [{"name": "registry"}],
+                    "volumes": [
+                        {"name": "cfg", "configMap": {"name": "api-config"}},
+                        {"name": "tls", "secret": {"secretName": "api-tls"}},
+                        {"name": "all", "projected": {"sources": [{"configMap": {"name": "shared"}}]}}
+                    ],
+                    "containers": [{
+                        "name": "api",
+                        "image": "api:1",
+                        "env": [
+                            {"name": "DB", "valueFrom": {"secretKeyRef": {"name": "db", "key": "url"}}},
+                            {"name": "FLAG", "valueFrom": {"configMapKeyRef": {"name": "flags", "key": "on", "optional": true}}}
+                        ],
+                        "envFrom": [{"configMapRef": {"name": "api-env"}}]
+                    }]
+                }}
+            },
+            "status": {"readyReplicas": 2}
+        })
+    }
+
+    #[test]
+    fn test_clean_for_copy_rewrites_namespace() {
+        let cleaned = clean_for_copy(deployment(), Some("prod"));
+        assert!(cleaned.get("status").is_none());
+        assert!(cleaned["metadata"].get("uid").is_none());
+        assert!(cleaned["metadata"].get("resourceVersion").is_none());
+        assert!(cleaned["metadata"].get("annotations").is_none());
+        assert_eq!(cleaned["metadata"]["namespace"], "prod");
+        assert_eq!(cleaned["metadata"]["labels"]["app"], "api");
+        assert_eq!(cleaned["spec"]["replicas"], 2);
+
+        let kept = clean_for_copy(deployment(), None);
+        assert_eq!(kept["metadata"]["namespace"], "staging");
+    }
+
+    #[test]
+    fn test_clean_for_copy_drops_allocated_fields() {
+        let service = json!({
+            "kind": "Service",
+            "metadata": {"name": "api", "namespace": "staging"},
+            "spec": {"clusterIP": "10.0.0.7", "clusterIPs": ["10.0.0.7"], "ports": [{"port": 80, "nodePort": 30080}]}
+        });
+        let cleaned = clean_for_copy(service, None);
+        assert!(cleaned["spec"].get("clusterIP").is_none());
+        assert!(cleaned["spec"].get("clusterIPs").is_none());
+        assert_eq!(cleaned["spec"]["ports"], json!([{"port": 80}]));
+
+        let pod = json!({"kind": "Pod", "metadata": {"name": "p"}, "spec": {"nodeName": "node-1", "containers": []}});
+        assert!(clean_for_copy(pod, None)["spec"].get("nodeName").is_none());
+
+        let headless = json!({
+            "kind": "Service",
+            "metadata": {"name": "db", "namespace": "staging"},
+            "spec": {"clusterIP": "None", "clusterIPs": ["None"], "ports": [{"port": 5432}]}
+        });
+        let cleaned = clean_for_copy(headless, None);
+        assert_eq!(cleaned["spec"]["clusterIP"], "None");
+        assert_eq!(cleaned["spec"]["clusterIPs"], json!(["None"]));
+
+        // Cluster-scoped objects never gain a namespace
+        let class = json!({"kind": "StorageClass", "metadata": {"name": "fast"}});
+        assert!(clean_for_copy(class, Some("prod"))["metadata"]
+            .get("namespace")
+            .is_none());
+    }
+
+    #[test]
+    fn test_referenced_config() {
+        let (config_maps, secrets) = referenced_config(&deployment());
+        assert_eq!(
+            config_maps.into_iter().collect::<Vec<_>>(),
+            vec!["api-config", "api-env", "shared"]
+        );
+        assert_eq!(
+            secrets.into_iter().collect::<Vec<_>>(),
+            vec!["api-tls", "db", "registry"]
+        );
+
+        let config_map = json!({"kind": "ConfigMap", "data": {"a": "b"}});
+        let (config_maps, secrets) = referenced_config(&config_map);
+        assert!(config_maps.is_empty() && secrets.is_empty());
+    }
+
+    #[test]
+    fn test_manifest_joins_documents() {
+        let manifest =
+            to_manifest(&[json!({"kind": "ConfigMap"}), json!({"kind": "Deployment"})]).unwrap();
+        assert_eq!(manifest, "kind: ConfigMap\n---\nkind: Deployment\n");
+    }
+}
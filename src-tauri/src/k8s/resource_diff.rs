@@ -0,0 +1,554 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::mutate::get_resource_yaml;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use tauri::State;
+
+/// Server-managed metadata that always differs between copies of an object
+const METADATA_NOISE: &[&str] = &[
+    "uid",
+    "resourceVersion",
+    "generation",
+    "creationTimestamp",
+    "deletionTimestamp",
+    "deletionGracePeriodSeconds",
+    "managedFields",
+    "selfLink",
+    "ownerReferences",
+    "namespace",
+];
+
+/// Annotations written by controllers and kubectl rather than by the object's author
+const ANNOTATION_NOISE_PREFIXES: &[&str] = &[
+    "kubectl.kubernetes.io/last-applied-configuration",
+    "kubectl.kubernetes.io/restartedAt",
+    "deployment.kubernetes.io/",
+    "pv.kubernetes.io/",
+    "volume.kubernetes.io/",
+    "volume.beta.kubernetes.io/",
+    "control-plane.alpha.kubernetes.io/",
+];
+
+/// Service fields allocated by the cluster
+const SERVICE_ALLOCATED_FIELDS: &[&str] = &[
+    "clusterIP",
+    "clusterIPs",
+    "ipFamilies",
+    "healthCheckNodePort",
+];
+
+/// API server defaults of a pod spec
+fn pod_spec_defaults() -> Vec<(&'static str, Value)> {
+    vec![
+        ("restartPolicy", json!("Always")),
+        ("dnsPolicy", json!("ClusterFirst")),
+        ("schedulerName", json!("default-scheduler")),
+        ("terminationGracePeriodSeconds", json!(30)),
+        ("securityContext", json!({})),
+    ]
+}
+
+fn container_defaults() -> Vec<(&'static str, Value)> {
+    vec![
+        ("terminationMessagePath", json!("/dev/termination-log")),
+        ("terminationMessagePolicy", json!("File")),
+    ]
+}
+
+fn probe_defaults() -> Vec<(&'static str, Value)> {
+    vec![
+        ("timeoutSeconds", json!(1)),
+        ("periodSeconds", json!(10)),
+        ("successThreshold", json!(1)),
+        ("failureThreshold", json!(3)),
+    ]
+}
+
+/// Container and Service ports
+fn port_defaults() -> Vec<(&'static str, Value)> {
+    vec![("protocol", json!("TCP"))]
+}
+
+fn service_spec_defaults() -> Vec<(&'static str, Value)> {
+    vec![
+        ("sessionAffinity", json!("None")),
+        ("ipFamilyPolicy", json!("SingleStack")),
+        ("internalTrafficPolicy", json!("Cluster")),
+    ]
+}
+
+/// API server defaults of a workload's own spec, outside its pod template
+fn workload_spec_defaults(kind: &str) -> Vec<(&'static str, Value)> {
+    let mut defaults = vec![("revisionHistoryLimit", json!(10))];
+    match kind {
+        "Deployment" => {
+            defaults.push(("progressDeadlineSeconds", json!(600)));
+            defaults.push((
+                "strategy",
+                json!({"type": "RollingUpdate", "rollingUpdate": {"maxSurge": "25%", "maxUnavailable": "25%"}}),
+            ));
+        }
+        "StatefulSet" => defaults.push(("podManagementPolicy", json!("OrderedReady"))),
+        _ => {}
+    }
+    defaults
+}
+
+fn remove_defaults(map: &mut Map<String, Value>, defaults: &[(&'static str, Value)]) {
+    map.retain(|key, v| !defaults.iter().any(|(k, d)| k == key && d == v));
+}
+
+fn object_at<'a>(value: &'a mut Value, path: &[&str]) -> Option<&'a mut Map<String, Value>> {
+    path.iter()
+        .try_fold(value, |value, key| value.get_mut(*key))?
+        .as_object_mut()
+}
+
+fn strip_port_defaults(map: &mut Map<String, Value>) {
+    let defaults = port_defaults();
+    if let Some(ports) = map.get_mut("ports").and_then(Value::as_array_mut) {
+        for port in ports.iter_mut().filter_map(Value::as_object_mut) {
+            remove_defaults(port, &defaults);
+        }
+    }
+}
+
+fn strip_pod_spec_defaults(spec: &mut Map<String, Value>) {
+    remove_defaults(spec, &pod_spec_defaults());
+    let (container_defaults, probe_defaults) = (container_defaults(), probe_defaults());
+    for key in ["initContainers", "containers"] {
+        let Some(containers) = spec.get_mut(key).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for container in containers.iter_mut().filter_map(Value::as_object_mut) {
+            remove_defaults(container, &container_defaults);
+            strip_port_defaults(container);
+            for probe in ["livenessProbe", "readinessProbe", "startupProbe"] {
+                if let Some(probe) = container.get_mut(probe).and_then(Value::as_object_mut) {
+                    remove_defaults(probe, &probe_defaults);
+                }
+            }
+        }
+    }
+}
+
+/// Drop API server defaults at the locations the schema defaults them, so a
+/// same-named key elsewhere (e.g. a ConfigMap data key) is left alone
+fn strip_defaults(value: &mut Value) {
+    let kind = value
+        .get("kind")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let template_path: &[&str] = match kind.as_str() {
+        "Pod" => {
+            if let Some(spec) = object_at(value, &["spec"]) {
+                strip_pod_spec_defaults(spec);
+            }
+            return;
+        }
+        "Service" => {
+            if let Some(spec) = object_at(value, &["spec"]) {
+                remove_defaults(spec, &service_spec_defaults());
+                strip_port_defaults(spec);
+            }
+            return;
+        }
+        "Deployment" | "StatefulSet" | "DaemonSet" | "ReplicaSet" | "Job" => {
+            if let Some(spec) = object_at(value, &["spec"]) {
+                remove_defaults(spec, &workload_spec_defaults(&kind));
+            }
+            &["spec", "template"]
+        }
+        "CronJob" => &["spec", "jobTemplate", "spec", "template"],
+        _ => return,
+    };
+
+    let Some(template) = object_at(value, template_path) else {
+        return;
+    };
+    if let Some(metadata) = template.get_mut("metadata").and_then(Value::as_object_mut) {
+        remove_defaults(metadata, &[("creationTimestamp", Value::Null)]);
+    }
+    if let Some(spec) = template.get_mut("spec").and_then(Value::as_object_mut) {
+        strip_pod_spec_defaults(spec);
+    }
+}
+
+/// Reduce an object to what its author controls: no status, no server-managed
+/// metadata or annotations, no cluster-allocated Service fields and, unless
+/// `keep_defaults`, no fields equal to their API server default.
+pub fn normalize_object(mut value: Value, keep_defaults: bool) -> Value {
+    let Some(object) = value.as_object_mut() else {
+        return value;
+    };
+    object.remove("status");
+    let is_service = object.get("kind").and_then(Value::as_str) == Some("Service");
+
+    if let Some(metadata) = object.get_mut("metadata").and_then(Value::as_object_mut) {
+        for key in METADATA_NOISE {
+            metadata.remove(*key);
+        }
+        if let Some(annotations) = metadata
+            .get_mut("annotations")
+            .and_then(Value::as_object_mut)
+        {
+            annotations.retain(|key, _| {
+                !ANNOTATION_NOISE_PREFIXES
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix))
+            });
+            if annotations.is_empty() {
+                metadata.remove("annotations");
+            }
+        }
+    }
+
+    if is_service {
+        if let Some(spec) = object.get_mut("spec").and_then(Value::as_object_mut) {
+            for key in SERVICE_ALLOCATED_FIELDS {
+                spec.remove(*key);
+            }
+            if let Some(ports) = spec.get_mut("ports").and_then(Value::as_array_mut) {
+                for port in ports.iter_mut().filter_map(Value::as_object_mut) {
+                    port.remove("nodePort");
+                }
+            }
+        }
+    }
+
+    if !keep_defaults {
+        strip_defaults(&mut value);
+    }
+    value
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffChange {
+    Added,   // Only on the right
+    Removed, // Only on the left
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FieldDiff {
+    pub path: String, // e.g. spec.template.spec.containers[name=api].image
+    pub change: DiffChange,
+    pub left: Option<Value>,
+    pub right: Option<Value>,
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    // Keys like "app.kubernetes.io/name" would be ambiguous in a dotted path
+    if key.contains(['.', '/']) {
+        format!("{}[\"{}\"]", path, key)
+    } else if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn element_name(value: &Value) -> Option<&str> {
+    value.get("name").and_then(Value::as_str)
+}
+
+fn diff_entry(path: String, left: Option<&Value>, right: Option<&Value>, out: &mut Vec<FieldDiff>) {
+    match (left, right) {
+        (Some(l), Some(r)) => diff_values(&path, l, r, out),
+        (Some(l), None) => out.push(FieldDiff {
+            path,
+            change: DiffChange::Removed,
+            left: Some(l.clone()),
+            right: None,
+        }),
+        (None, Some(r)) => out.push(FieldDiff {
+            path,
+            change: DiffChange::Added,
+            left: None,
+            right: Some(r.clone()),
+        }),
+        (None, None) => {}
+    }
+}
+
+fn diff_objects(
+    path: &str,
+    left: &Map<String, Value>,
+    right: &Map<String, Value>,
+    out: &mut Vec<FieldDiff>,
+) {
+    let keys: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+    for key in keys {
+        diff_entry(child_path(path, key), left.get(key), right.get(key), out);
+    }
+}
+
+/// Arrays of named elements (containers, env, ports, volumes) are matched by name
+/// so that reordering or inserting one entry does not shift every later index.
+fn diff_arrays(path: &str, left: &[Value], right: &[Value], out: &mut Vec<FieldDiff>) {
+    let named = |items: &[Value]| items.iter().all(|v| element_name(v).is_some());
+    if !left.is_empty() && !right.is_empty() && named(left) && named(right) {
+        let mut names: Vec<&str> = left.iter().filter_map(element_name).collect();
+        for name in right.iter().filter_map(element_name) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        for name in names {
+            let element_path = format!("{}[name={}]", path, name);
+            let find = |items: &[Value]| items.iter().position(|v| element_name(v) == Some(name));
+            let left_item = find(left).map(|i| &left[i]);
+            let right_item = find(right).map(|i| &right[i]);
+            diff_entry(element_path, left_item, right_item, out);
+        }
+        return;
+    }
+
+    for i in 0..left.len().max(right.len()) {
+        diff_entry(format!("{}[{}]", path, i), left.get(i), right.get(i), out);
+    }
+}
+
+fn diff_values(path: &str, left: &Value, right: &Value, out: &mut Vec<FieldDiff>) {
+    match (left, right) {
+        (Value::Object(l), Value::Object(r)) => diff_objects(path, l, r, out),
+        (Value::Array(l), Value::Array(r)) => diff_arrays(path, l, r, out),
+        _ if left != right => out.push(FieldDiff {
+            path: path.to_string(),
+            change: DiffChange::Changed,
+            left: Some(left.clone()),
+            right: Some(right.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Field-level differences from `left` to `right`
+pub fn diff_objects_by_field(left: &Value, right: &Value) -> Vec<FieldDiff> {
+    let mut out = Vec::new();
+    diff_values("", left, right, &mut out);
+    out
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct DiffTarget {
+    pub cluster_id: String,
+    pub namespace: Option<String>,
+    pub name: Option<String>, // Defaults to the request's name
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ResourceDiffParams {
+    pub kind: String,
+    pub name: String,
+    pub left: DiffTarget,
+    pub right: DiffTarget,
+    pub keep_defaults: Option<bool>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ResourceDiff {
+    pub identical: bool,
+    pub differences: Vec<FieldDiff>,
+    pub left_yaml: String, // Normalized objects, for a side-by-side view
+    pub right_yaml: String,
+}
+
+fn fetch_normalized(
+    kind: &str,
+    default_name: &str,
+    target: &DiffTarget,
+    keep_defaults: bool,
+    state: &State<'_, ClusterManagerState>,
+) -> Result<Value, String> {
+    let name = target.name.as_deref().unwrap_or(default_name);
+    let yaml = get_resource_yaml(
+        &target.cluster_id,
+        kind,
+        name,
+        target.namespace.as_deref(),
+        state,
+    )?;
+    let value: Value = serde_yaml::from_str(&yaml)
+        .map_err(|e| format!("Failed to parse {} {}: {}", kind, name, e))?;
+    Ok(normalize_object(value, keep_defaults))
+}
+
+/// Compare the same object across clusters and/or namespaces
+#[tauri::command]
+pub async fn cluster_diff_resource(
+    params: ResourceDiffParams,
+    state: State<'_, ClusterManagerState>,
+) -> Result<ResourceDiff, String> {
+    let keep_defaults = params.keep_defaults.unwrap_or(false);
+    let left = fetch_normalized(
+        &params.kind,
+        &params.name,
+        &params.left,
+        keep_defaults,
+        &state,
+    )?;
+    let right = fetch_normalized(
+        &params.kind,
+        &params.name,
+        &params.right,
+        keep_defaults,
+        &state,
+    )?;
+
+    let differences = diff_objects_by_field(&left, &right);
+    let to_yaml = |value: &Value| {
+        serde_yaml::to_string(value).map_err(|e| format!("Failed to serialize YAML: {}", e))
+    };
+
+    Ok(ResourceDiff {
+        identical: differences.is_empty(),
+        left_yaml: to_yaml(&left)?,
+        right_yaml: to_yaml(&right)?,
+        differences,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn deployment(namespace: &str, image: &str, replicas: i64) -> Value {
+        json!({
+            "apiVersion": "apps/v1",
+            "kind": "Deployment",
+            "metadata": {
+                "name": "api",
+                "namespace": namespace,
+                "uid": format!("uid-{}", namespace),
+                "resourceVersion": "123",
+                "labels": {"app.kubernetes.io/name": "api"},
+                "annotations": {"deployment.kubernetes.io/revision": "4"}
+            },
+            "spec": {
+                "replicas": replicas,
+                "revisionHistoryLimit": 10,
+                "template": {
+                    "metadata": {"creationTimestamp": null},
+                    "spec": {
+                        "restartPolicy": "Always",
+                        "containers": [
+                            {"name": "api", "image": image, "terminationMessagePolicy": "File"},
+                            {"name": "sidecar", "image": "envoy:1.29"}
+                        ]
+                    }
+                }
+            },
+            "status": {"readyReplicas": replicas}
+        })
+    }
+
+    #[test]
+    fn test_normalize_strips_noise_and_defaults() {
+        let normalized = normalize_object(deployment("prod", "api:1", 3), false);
+        assert!(normalized.get("status").is_none());
+        assert!(normalized["metadata"].get("uid").is_none());
+        assert!(normalized["metadata"].get("namespace").is_none());
+        assert!(normalized["metadata"].get("annotations").is_none());
+        assert!(normalized["spec"].get("revisionHistoryLimit").is_none());
+        assert!(normalized["spec"]["template"]["metadata"]
+            .get("creationTimestamp")
+            .is_none());
+        assert_eq!(
+            normalized["metadata"]["labels"]["app.kubernetes.io/name"],
+            "api"
+        );
+
+        let kept = normalize_object(deployment("prod", "api:1", 3), true);
+        assert_eq!(kept["spec"]["revisionHistoryLimit"], 10);
+    }
+
+    #[test]
+    fn test_defaults_are_only_stripped_where_the_schema_defaults_them() {
+        let config_map = json!({
+            "kind": "ConfigMap",
+            "metadata": {"name": "settings"},
+            "data": {"protocol": "TCP", "restartPolicy": "Always"}
+        });
+        let normalized = normalize_object(config_map, false);
+        assert_eq!(
+            normalized["data"],
+            json!({"protocol": "TCP", "restartPolicy": "Always"})
+        );
+
+        let pod = json!({
+            "kind": "Pod",
+            "spec": {
+                "dnsPolicy": "ClusterFirst",
+                "containers": [{
+                    "name": "api",
+                    "ports": [{"containerPort": 80, "protocol": "TCP"}],
+                    "readinessProbe": {"periodSeconds": 10, "initialDelaySeconds": 5},
+                    "env": [{"name": "protocol", "value": "TCP"}]
+                }]
+            }
+        });
+        let normalized = normalize_object(pod, false);
+        assert!(normalized["spec"].get("dnsPolicy").is_none());
+        let container = &normalized["spec"]["containers"][0];
+        assert_eq!(container["ports"], json!([{"containerPort": 80}]));
+        assert_eq!(
+            container["readinessProbe"],
+            json!({"initialDelaySeconds": 5})
+        );
+        assert_eq!(
+            container["env"],
+            json!([{"name": "protocol", "value": "TCP"}])
+        );
+    }
+
+    #[test]
+    fn test_normalize_strips_allocated_service_fields() {
+        let service = json!({
+            "kind": "Service",
+            "metadata": {"name": "api"},
+            "spec": {"clusterIP": "10.0.0.1", "type": "NodePort", "ports": [{"port": 80, "nodePort": 30080}]}
+        });
+        let normalized = normalize_object(service, false);
+        assert!(normalized["spec"].get("clusterIP").is_none());
+        assert_eq!(normalized["spec"]["ports"], json!([{"port": 80}]));
+    }
+
+    #[test]
+    fn test_diff_reports_only_authored_differences() {
+        let left = normalize_object(deployment("staging", "api:2", 1), false);
+        let right = normalize_object(deployment("prod", "api:1", 3), false);
+        let diff = diff_objects_by_field(&left, &right);
+
+        let paths: Vec<&str> = diff.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "spec.replicas",
+                "spec.template.spec.containers[name=api].image"
+            ]
+        );
+        assert_eq!(diff[1].left, Some(json!("api:2")));
+        assert_eq!(diff[1].change, DiffChange::Changed);
+    }
+
+    #[test]
+    fn test_diff_matches_named_elements_and_reports_added_removed() {
+        let left = json!({"env": [{"name": "A", "value": "1"}, {"name": "B", "value": "2"}]});
+        let right = json!({"env": [{"name": "B", "value": "2"}, {"name": "C", "value": "3"}]});
+        let diff = diff_objects_by_field(&left, &right);
+
+        assert_eq!(diff.len(), 2);
+        assert_eq!(diff[0].path, "env[name=A]");
+        assert_eq!(diff[0].change, DiffChange::Removed);
+        assert_eq!(diff[1].path, "env[name=C]");
+        assert_eq!(diff[1].change, DiffChange::Added);
+
+        let diff = diff_objects_by_field(&json!({"args": ["a", "b"]}), &json!({"args": ["a"]}));
+        assert_eq!(diff[0].path, "args[1]");
+        assert_eq!(diff[0].change, DiffChange::Removed);
+    }
+}
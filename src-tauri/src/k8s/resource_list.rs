@@ -0,0 +1,544 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::WorkloadSummary;
+use crate::k8s::dynamic::{dynamic_api, map_dynamic_to_summary, resolve_api_resource};
+use crate::k8s::selectors::selector_list_params;
+use crate::k8s::tasks::{BackgroundTaskState, TaskInfo};
+use crate::k8s::workload::*;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::{FutureExt, StreamExt, TryStreamExt};
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{
+    ConfigMap, Endpoints, LimitRange, PersistentVolume, PersistentVolumeClaim, ResourceQuota,
+    Secret, Service, ServiceAccount,
+};
+use k8s_openapi::api::networking::v1::{Ingress, NetworkPolicy};
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::api::rbac::v1::{ClusterRole, ClusterRoleBinding, Role, RoleBinding};
+use k8s_openapi::api::storage::v1::StorageClass;
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use kube::api::{DynamicObject, ListParams};
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::{Api, Client};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use tauri::{Emitter, State, Window};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 500;
+const MAX_PAGE_SIZE: u32 = 5000;
+const MAX_LIST_RESTARTS: usize = 3;
+
+pub type SummaryEventStream = BoxStream<'static, Result<watcher::Event<WorkloadSummary>, String>>;
+
+/// One page of a LIST, already summarized
+#[derive(Debug, Clone, Default)]
+pub struct SummaryPage {
+    pub items: Vec<WorkloadSummary>,
+    pub annotations: Vec<BTreeMap<String, String>>, // Parallel to `items`; summaries omit them
+    pub continue_token: Option<String>,
+    pub remaining_item_count: Option<i64>,
+}
+
+/// A typed or dynamic API for one kind, paired with the mapper that summarizes its objects
+pub trait SummaryApi: Send + Sync {
+    fn list_page<'a>(
+        &'a self,
+        params: &'a ListParams,
+    ) -> BoxFuture<'a, Result<SummaryPage, kube::Error>>;
+
+    fn watch(&self, config: watcher::Config) -> SummaryEventStream;
+}
+
+struct MappedApi<K> {
+    api: Api<K>,
+    map: fn(K) -> WorkloadSummary,
+}
+
+pub(crate) fn map_watch_event<K, T>(
+    event: watcher::Event<K>,
+    map: fn(K) -> T,
+) -> watcher::Event<T> {
+    match event {
+        watcher::Event::Init => watcher::Event::Init,
+        watcher::Event::InitApply(obj) => watcher::Event::InitApply(map(obj)),
+        watcher::Event::InitDone => watcher::Event::InitDone,
+        watcher::Event::Apply(obj) => watcher::Event::Apply(map(obj)),
+        watcher::Event::Delete(obj) => watcher::Event::Delete(map(obj)),
+    }
+}
+
+impl<K> SummaryApi for MappedApi<K>
+where
+    K: kube::Resource + Clone + serde::de::DeserializeOwned + Debug + Send + Sync + 'static,
+{
+    fn list_page<'a>(
+        &'a self,
+        params: &'a ListParams,
+    ) -> BoxFuture<'a, Result<SummaryPage, kube::Error>> {
+        async move {
+            let list = self.api.list(params).await?;
+            let mut page = SummaryPage {
+                continue_token: list.metadata.continue_.filter(|token| !token.is_empty()),
+                remaining_item_count: list.metadata.remaining_item_count,
+                ..Default::default()
+            };
+            for mut obj in list.items {
+                page.annotations.push(std::mem::take(
+                    obj.meta_mut().annotations.get_or_insert_default(),
+                ));
+                page.items.push((self.map)(obj));
+            }
+            Ok(page)
+        }
+        .boxed()
+    }
+
+    fn watch(&self, config: watcher::Config) -> SummaryEventStream {
+        let map = self.map;
+        watcher(self.api.clone(), config)
+            .default_backoff()
+            .map_ok(move |event| map_watch_event(event, map))
+            .map_err(|e| e.to_string())
+            .boxed()
+    }
+}
+
+fn is_typed_kind<K: k8s_openapi::Resource>(kind: &str, api_version: Option<&str>) -> bool {
+    kind.eq_ignore_ascii_case(K::KIND)
+        && api_version.is_none_or(|v| v.is_empty() || v == K::API_VERSION)
+}
+
+macro_rules! typed_summary_api {
+    ($client:expr, $kind:expr, $api_version:expr, $namespace:expr,
+     namespaced: [$(($ns_ty:ty, $ns_map:ident)),* $(,)?],
+     cluster: [$(($cl_ty:ty, $cl_map:ident)),* $(,)?]) => {
+        $(
+            if is_typed_kind::<$ns_ty>($kind, $api_version) {
+                let api: Api<$ns_ty> = match $namespace {
+                    Some(ns) => Api::namespaced($client, ns),
+                    None => Api::all($client),
+                };
+                return Ok(Box::new(MappedApi { api, map: $ns_map }));
+            }
+        )*
+        $(
+            if is_typed_kind::<$cl_ty>($kind, $api_version) {
+                let api: Api<$cl_ty> = Api::all($client);
+                return Ok(Box::new(MappedApi { api, map: $cl_map }));
+            }
+        )*
+    };
+}
+
+/// API for a kind: built-in list kinds use their typed summary mapper, anything
+/// else (including CRD instances) goes through discovery.
+pub async fn summary_api(
+    client: Client,
+    kind: &str,
+    api_version: Option<&str>,
+    namespace: Option<&str>,
+) -> Result<Box<dyn SummaryApi>, String> {
+    typed_summary_api!(
+        client, kind, api_version, namespace,
+        namespaced: [
+            (Deployment, map_deployment_to_summary),
+            (StatefulSet, map_statefulset_to_summary),
+            (DaemonSet, map_daemonset_to_summary),
+            (ReplicaSet, map_replicaset_to_summary),
+            (Job, map_job_to_summary),
+            (CronJob, map_cronjob_to_summary),
+            (ConfigMap, map_configmap_to_summary),
+            (Secret, map_secret_to_summary),
+            (ResourceQuota, map_resource_quota_to_summary),
+            (LimitRange, map_limit_range_to_summary),
+            (HorizontalPodAutoscaler, map_hpa_to_summary),
+            (PodDisruptionBudget, map_pdb_to_summary),
+            (Service, map_service_to_summary),
+            (Endpoints, map_endpoints_to_summary),
+            (Ingress, map_ingress_to_summary),
+            (NetworkPolicy, map_network_policy_to_summary),
+            (PersistentVolumeClaim, map_pvc_to_summary),
+            (ServiceAccount, map_service_account_to_summary),
+            (Role, map_role_to_summary),
+            (RoleBinding, map_role_binding_to_summary),
+        ],
+        cluster: [
+            (PersistentVolume, map_pv_to_summary),
+            (StorageClass, map_storage_class_to_summary),
+            (ClusterRole, map_cluster_role_to_summary),
+            (ClusterRoleBinding, map_cluster_role_binding_to_summary),
+            (CustomResourceDefinition, map_crd_to_summary),
+        ]
+    );
+
+    let (resource, capabilities) = resolve_api_resource(&client, api_version, kind).await?;
+    Ok(dynamic_summary_api(dynamic_api(
+        client,
+        &resource,
+        &capabilities,
+        namespace,
+    )))
+}
+
+/// Summary API over an already resolved dynamic resource (e.g. a CRD's kind)
+pub fn dynamic_summary_api(api: Api<DynamicObject>) -> Box<dyn SummaryApi> {
+    Box::new(MappedApi {
+        api,
+        map: map_dynamic_to_summary,
+    })
+}
+
+/// Follow limit/continue through every page of a LIST. `fetch` returns the
+/// items kept from one page and the page's continue token.
+pub async fn list_all_pages<T, F, Fut>(
+    params: ListParams,
+    mut fetch: F,
+) -> Result<Vec<T>, kube::Error>
+where
+    F: FnMut(ListParams) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<T>, Option<String>), kube::Error>>,
+{
+    let mut items = Vec::new();
+    let mut params = params.limit(DEFAULT_PAGE_SIZE);
+    loop {
+        let (page, token) = fetch(params.clone()).await?;
+        items.extend(page);
+        match token.filter(|token| !token.is_empty()) {
+            Some(token) => params = params.continue_token(&token),
+            None => return Ok(items),
+        }
+    }
+}
+
+/// List every page of a kind, returning each summary with its annotations
+pub async fn list_all_summaries(
+    api: &dyn SummaryApi,
+    params: ListParams,
+) -> Result<Vec<(WorkloadSummary, BTreeMap<String, String>)>, kube::Error> {
+    list_all_pages(params, |params| async move {
+        let page = api.list_page(&params).await?;
+        let items = page.items.into_iter().zip(page.annotations).collect();
+        Ok((items, page.continue_token))
+    })
+    .await
+}
+
+/// Progress of a paginated list, emitted on `resource_list_{list_id}`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum ListPageEvent {
+    Page {
+        items: Vec<WorkloadSummary>,
+        page: usize,
+        total_so_far: usize,
+        remaining_item_count: Option<i64>, // Server estimate, not always available
+    },
+    // The continue token expired (410 Gone); discard received pages, the list starts over
+    Restarted {
+        reason: String,
+    },
+    Done {
+        total: usize,
+        pages: usize,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+/// Tracks a limit/continue listing across pages and restarts
+struct Pager {
+    base: ListParams,
+    continue_token: Option<String>,
+    pages: usize,
+    total: usize,
+    restarts: usize,
+}
+
+impl Pager {
+    fn new(base: ListParams, page_size: u32) -> Self {
+        Pager {
+            base: base.limit(page_size),
+            continue_token: None,
+            pages: 0,
+            total: 0,
+            restarts: 0,
+        }
+    }
+
+    fn params(&self) -> ListParams {
+        match &self.continue_token {
+            Some(token) => self.base.clone().continue_token(token),
+            None => self.base.clone(),
+        }
+    }
+
+    /// Record a page; the bool is true when it was the last one
+    fn on_page(&mut self, page: SummaryPage) -> (ListPageEvent, bool) {
+        self.pages += 1;
+        self.total += page.items.len();
+        self.continue_token = page.continue_token;
+
+        let event = ListPageEvent::Page {
+            items: page.items,
+            page: self.pages,
+            total_so_far: self.total,
+            remaining_item_count: page.remaining_item_count,
+        };
+        (event, self.continue_token.is_none())
+    }
+
+    /// Start over from the first page after the continue token expired
+    fn restart(&mut self, reason: String) -> Result<ListPageEvent, String> {
+        self.restarts += 1;
+        if self.restarts > MAX_LIST_RESTARTS {
+            return Err(format!(
+                "List restarted {} times, giving up: {}",
+                MAX_LIST_RESTARTS, reason
+            ));
+        }
+        self.continue_token = None;
+        self.pages = 0;
+        self.total = 0;
+        Ok(ListPageEvent::Restarted { reason })
+    }
+
+    fn done(&self) -> ListPageEvent {
+        ListPageEvent::Done {
+            total: self.total,
+            pages: self.pages,
+        }
+    }
+}
+
+fn is_expired_continue(error: &kube::Error) -> bool {
+    matches!(error, kube::Error::Api(status) if status.code == 410)
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct PagedListParams {
+    pub cluster_id: String,
+    pub kind: String,
+    pub api_version: Option<String>,
+    pub namespace: Option<String>, // None lists all namespaces
+    pub label_selector: Option<String>,
+    pub field_selector: Option<String>,
+    pub page_size: Option<u32>,
+    pub list_id: String,
+}
+
+/// List any kind page by page, emitting `ListPageEvent`s on `resource_list_{list_id}`.
+/// Cancel with `cluster_cancel_list`.
+#[tauri::command]
+pub async fn cluster_list_resources_paged(
+    params: PagedListParams,
+    window: Window,
+    state: State<'_, ClusterManagerState>,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    let base = selector_list_params(
+        params.label_selector.as_deref(),
+        params.field_selector.as_deref(),
+    )?;
+    let page_size = params
+        .page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let client = create_client_for_cluster(&params.cluster_id, &state).await?;
+    let namespace = params.namespace.as_deref().filter(|ns| !ns.is_empty());
+    let api = summary_api(
+        client,
+        &params.kind,
+        params.api_version.as_deref(),
+        namespace,
+    )
+    .await?;
+
+    let info = TaskInfo::new(
+        format!("list:{}", params.list_id),
+        "list",
+        Some(&params.cluster_id),
+        &window,
+        format!(
+            "Listing {} in {}",
+            params.kind,
+            namespace.unwrap_or("all namespaces")
+        ),
+    );
+    let event_name = format!("resource_list_{}", params.list_id);
+
+    tasks.spawn(info, async move {
+        let mut pager = Pager::new(base, page_size);
+
+        loop {
+            let (events, finished) = match api.list_page(&pager.params()).await {
+                Ok(page) => {
+                    let (event, last) = pager.on_page(page);
+                    if last {
+                        (vec![event, pager.done()], true)
+                    } else {
+                        (vec![event], false)
+                    }
+                }
+                Err(e) if is_expired_continue(&e) => match pager.restart(e.to_string()) {
+                    Ok(event) => (vec![event], false),
+                    Err(error) => (vec![ListPageEvent::Failed { error }], true),
+                },
+                Err(e) => {
+                    let error = format!("Failed to list {}: {}", params.kind, e);
+                    (vec![ListPageEvent::Failed { error }], true)
+                }
+            };
+
+            for event in events {
+                if let Err(e) = window.emit(&event_name, event) {
+                    println!("Failed to emit event: {}", e);
+                    return;
+                }
+            }
+            if finished {
+                return;
+            }
+        }
+    })
+}
+
+#[tauri::command]
+pub async fn cluster_cancel_list(
+    list_id: String,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<bool, String> {
+    tasks.stop(&format!("list:{}", list_id))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn summary(name: &str) -> WorkloadSummary {
+        WorkloadSummary {
+            id: name.to_string(),
+            name: name.to_string(),
+            namespace: "default".to_string(),
+            age: "1d".to_string(),
+            labels: Default::default(),
+            status: "-".to_string(),
+            images: vec![],
+            created_at: 0,
+        }
+    }
+
+    fn page(names: &[&str], token: Option<&str>) -> SummaryPage {
+        SummaryPage {
+            items: names.iter().map(|n| summary(n)).collect(),
+            annotations: vec![],
+            continue_token: token.map(str::to_string),
+            remaining_item_count: None,
+        }
+    }
+
+    #[test]
+    fn test_list_all_pages_stops_at_empty_continue_token() {
+        let pages = [(vec![1, 2], Some("t1")), (vec![3], Some(""))];
+        let mut seen = Vec::new();
+        let items = futures::executor::block_on(list_all_pages(ListParams::default(), |params| {
+            seen.push((params.limit, params.continue_token.clone()));
+            let (items, token) = pages[seen.len() - 1].clone();
+            async move { Ok((items, token.map(str::to_string))) }
+        }))
+        .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(
+            seen,
+            vec![
+                (Some(DEFAULT_PAGE_SIZE), None),
+                (Some(DEFAULT_PAGE_SIZE), Some("t1".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pager_follows_continue_tokens() {
+        let mut pager = Pager::new(ListParams::default().labels("app=api"), 2);
+        assert_eq!(pager.params().limit, Some(2));
+        assert!(pager.params().continue_token.is_none());
+
+        let (event, last) = pager.on_page(page(&["a", "b"], Some("t1")));
+        assert!(!last);
+        assert!(matches!(
+            event,
+            ListPageEvent::Page {
+                page: 1,
+                total_so_far: 2,
+                ..
+            }
+        ));
+
+        let params = pager.params();
+        assert_eq!(params.continue_token.as_deref(), Some("t1"));
+        assert_eq!(params.label_selector.as_deref(), Some("app=api"));
+
+        let (event, last) = pager.on_page(page(&["c"], None));
+        assert!(last);
+        assert!(matches!(
+            event,
+            ListPageEvent::Page {
+                page: 2,
+                total_so_far: 3,
+                ..
+            }
+        ));
+        assert!(matches!(
+            pager.done(),
+            ListPageEvent::Done { total: 3, pages: 2 }
+        ));
+    }
+
+    #[test]
+    fn test_pager_restart_resets_progress() {
+        let mut pager = Pager::new(ListParams::default(), 2);
+        pager.on_page(page(&["a", "b"], Some("t1")));
+
+        let event = pager.restart("expired".to_string()).unwrap();
+        assert!(matches!(event, ListPageEvent::Restarted { .. }));
+        assert!(pager.params().continue_token.is_none());
+
+        let (event, _) = pager.on_page(page(&["a"], None));
+        assert!(matches!(
+            event,
+            ListPageEvent::Page {
+                page: 1,
+                total_so_far: 1,
+                ..
+            }
+        ));
+
+        for _ in 1..MAX_LIST_RESTARTS {
+            pager.restart("expired".to_string()).unwrap();
+        }
+        assert!(pager.restart("expired".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_is_expired_continue() {
+        let gone = kube::Error::Api(
+            kube::core::Status::failure("continue token expired", "Expired")
+                .with_code(410)
+                .boxed(),
+        );
+        assert!(is_expired_continue(&gone));
+
+        let forbidden = kube::Error::Api(
+            kube::core::Status::failure("forbidden", "Forbidden")
+                .with_code(403)
+                .boxed(),
+        );
+        assert!(!is_expired_continue(&forbidden));
+    }
+}
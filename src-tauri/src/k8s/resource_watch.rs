@@ -0,0 +1,252 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::WorkloadSummary;
+use crate::k8s::resource_list::summary_api;
+use crate::k8s::tasks::{BackgroundTaskState, TaskInfo};
+use futures::StreamExt;
+use kube::runtime::watcher;
+use std::collections::HashSet;
+use tauri::{Emitter, State, Window};
+
+/// A change to a watched list, ready to be applied by a list view
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum WatchDelta<T> {
+    Synced(Vec<T>), // Full snapshot after the initial list or a re-list; replaces the view
+    Added(T),
+    Modified(T),
+    Deleted(T),
+}
+
+/// Turns raw watcher events into list deltas. Objects listed between `Init` and
+/// `InitDone` are buffered and emitted as one snapshot, and later `Apply` events
+/// are reported as Added or Modified depending on whether the object is known.
+pub struct WatchTracker<T> {
+    key: fn(&T) -> String,
+    known: HashSet<String>,
+    pending: Option<Vec<T>>,
+}
+
+impl<T> WatchTracker<T> {
+    pub fn new(key: fn(&T) -> String) -> Self {
+        WatchTracker {
+            key,
+            known: HashSet::new(),
+            pending: None,
+        }
+    }
+
+    pub fn apply(&mut self, event: watcher::Event<T>) -> Option<WatchDelta<T>> {
+        match event {
+            watcher::Event::Init => {
+                self.pending = Some(Vec::new());
+                None
+            }
+            watcher::Event::InitApply(obj) => {
+                self.pending.get_or_insert_with(Vec::new).push(obj);
+                None
+            }
+            watcher::Event::InitDone => {
+                let items = self.pending.take().unwrap_or_default();
+                self.known = items.iter().map(self.key).collect();
+                Some(WatchDelta::Synced(items))
+            }
+            watcher::Event::Apply(obj) => {
+                if self.known.insert((self.key)(&obj)) {
+                    Some(WatchDelta::Added(obj))
+                } else {
+                    Some(WatchDelta::Modified(obj))
+                }
+            }
+            watcher::Event::Delete(obj) => {
+                self.known.remove(&(self.key)(&obj));
+                Some(WatchDelta::Deleted(obj))
+            }
+        }
+    }
+}
+
+/// Identity of a summary: the uid, or namespace/name for objects without one
+pub fn summary_key(summary: &WorkloadSummary) -> String {
+    if summary.id.is_empty() {
+        format!("{}/{}", summary.namespace, summary.name)
+    } else {
+        summary.id.clone()
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ResourceWatchParams {
+    pub cluster_id: String,
+    pub kind: String,
+    pub api_version: Option<String>,
+    pub namespace: Option<String>, // None watches all namespaces
+    pub watch_id: String,
+}
+
+/// Watch any list-able kind and emit `WatchDelta<WorkloadSummary>` events on
+/// `resource_watch_{watch_id}`
+#[tauri::command]
+pub async fn cluster_watch_resources(
+    params: ResourceWatchParams,
+    window: Window,
+    state: State<'_, ClusterManagerState>,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    let client = create_client_for_cluster(&params.cluster_id, &state).await?;
+    let namespace = params.namespace.as_deref().filter(|ns| !ns.is_empty());
+    let mut stream = summary_api(
+        client,
+        &params.kind,
+        params.api_version.as_deref(),
+        namespace,
+    )
+    .await?
+    .watch(watcher::Config::default());
+
+    let info = TaskInfo::new(
+        format!("resource_watch:{}", params.watch_id),
+        "resource_watch",
+        Some(&params.cluster_id),
+        &window,
+        format!(
+            "{} in {}",
+            params.kind,
+            namespace.unwrap_or("all namespaces")
+        ),
+    );
+    let event_name = format!("resource_watch_{}", params.watch_id);
+
+    tasks.spawn(info, async move {
+        let mut tracker = WatchTracker::new(summary_key);
+
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(event) => {
+                    let Some(delta) = tracker.apply(event) else {
+                        continue;
+                    };
+                    if let Err(e) = window.emit(&event_name, delta) {
+                        println!("Failed to emit event: {}", e);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    println!("Watch error: {}", e);
+                }
+            }
+        }
+    })
+}
+
+#[tauri::command]
+pub async fn cluster_stop_resource_watch(
+    watch_id: String,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<(), String> {
+    tasks.stop(&format!("resource_watch:{}", watch_id))?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::k8s::dynamic::map_dynamic_to_summary;
+
+    fn key(item: &(&'static str, u32)) -> String {
+        item.0.to_string()
+    }
+
+    #[test]
+    fn test_tracker_buffers_initial_list_until_init_done() {
+        let mut tracker = WatchTracker::new(key);
+
+        assert!(tracker.apply(watcher::Event::Init).is_none());
+        assert!(tracker.apply(watcher::Event::InitApply(("a", 1))).is_none());
+        assert!(tracker.apply(watcher::Event::InitApply(("b", 1))).is_none());
+
+        let delta = tracker.apply(watcher::Event::InitDone);
+        assert!(
+            matches!(&delta, Some(WatchDelta::Synced(items)) if *items == vec![("a", 1), ("b", 1)]),
+            "expected snapshot, got {:?}",
+            delta
+        );
+    }
+
+    #[test]
+    fn test_tracker_distinguishes_added_and_modified() {
+        let mut tracker = WatchTracker::new(key);
+        tracker.apply(watcher::Event::Init);
+        tracker.apply(watcher::Event::InitApply(("a", 1)));
+        tracker.apply(watcher::Event::InitDone);
+
+        assert!(matches!(
+            tracker.apply(watcher::Event::Apply(("a", 2))),
+            Some(WatchDelta::Modified(("a", 2)))
+        ));
+        assert!(matches!(
+            tracker.apply(watcher::Event::Apply(("b", 1))),
+            Some(WatchDelta::Added(("b", 1)))
+        ));
+        assert!(matches!(
+            tracker.apply(watcher::Event::Apply(("b", 2))),
+            Some(WatchDelta::Modified(("b", 2)))
+        ));
+
+        assert!(matches!(
+            tracker.apply(watcher::Event::Delete(("a", 2))),
+            Some(WatchDelta::Deleted(("a", 2)))
+        ));
+        // A recreated object is new again
+        assert!(matches!(
+            tracker.apply(watcher::Event::Apply(("a", 3))),
+            Some(WatchDelta::Added(("a", 3)))
+        ));
+    }
+
+    #[test]
+    fn test_tracker_relist_replaces_known_set() {
+        let mut tracker = WatchTracker::new(key);
+        tracker.apply(watcher::Event::Init);
+        tracker.apply(watcher::Event::InitApply(("a", 1)));
+        tracker.apply(watcher::Event::InitDone);
+
+        // Re-list after the watch desynced: "a" is gone, "b" appeared
+        tracker.apply(watcher::Event::Init);
+        tracker.apply(watcher::Event::InitApply(("b", 1)));
+        let delta = tracker.apply(watcher::Event::InitDone);
+        assert!(
+            matches!(&delta, Some(WatchDelta::Synced(items)) if *items == vec![("b", 1)]),
+            "expected snapshot, got {:?}",
+            delta
+        );
+
+        assert!(matches!(
+            tracker.apply(watcher::Event::Apply(("a", 2))),
+            Some(WatchDelta::Added(_))
+        ));
+        assert!(matches!(
+            tracker.apply(watcher::Event::Apply(("b", 2))),
+            Some(WatchDelta::Modified(_))
+        ));
+    }
+
+    #[test]
+    fn test_map_dynamic_to_summary() {
+        let obj: kube::api::DynamicObject = serde_json::from_value(serde_json::json!({
+            "apiVersion": "example.com/v1",
+            "kind": "Widget",
+            "metadata": {"name": "w1", "namespace": "default", "uid": "123"},
+            "spec": {"template": {"spec": {"containers": [{"name": "c", "image": "nginx:1.25"}]}}},
+            "status": {"conditions": [{"type": "Ready", "status": "False"}]}
+        }))
+        .unwrap();
+
+        let summary = map_dynamic_to_summary(obj);
+        assert_eq!(summary.id, "123");
+        assert_eq!(summary.status, "NotReady");
+        assert_eq!(summary.images, vec!["nginx:1.25".to_string()]);
+        assert_eq!(summary_key(&summary), "123");
+    }
+}
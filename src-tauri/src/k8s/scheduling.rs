@@ -0,0 +1,904 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::label_selector_matches;
+use crate::k8s::metrics::{parse_cpu, parse_memory};
+use k8s_openapi::api::core::v1::{
+    Node, NodeSelectorRequirement, NodeSelectorTerm, PersistentVolume, PersistentVolumeClaim, Pod,
+    PodAffinityTerm, PodSpec, Taint, Toleration,
+};
+use k8s_openapi::api::storage::v1::StorageClass;
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::api::Api;
+use std::collections::{BTreeMap, HashMap};
+use tauri::State;
+
+/// Why the pod can or cannot be placed on one node
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NodeFit {
+    pub node: String,
+    pub fits: bool,
+    pub reasons: Vec<String>, // Empty when the pod fits
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SchedulingExplanation {
+    pub pod: String,
+    pub namespace: String,
+    pub assigned_node: Option<String>,
+    pub requests: BTreeMap<String, String>, // Effective requests, including init containers and overhead
+    pub pod_issues: Vec<String>,            // Problems that block every node, e.g. an unbound claim
+    pub nodes: Vec<NodeFit>,                // Fitting nodes first
+}
+
+/// Everything the explainer evaluates locally
+pub struct SchedulingInput<'a> {
+    pub pod: &'a Pod,
+    pub nodes: &'a [Node],
+    pub pods: &'a [Pod], // Pods in all namespaces
+    pub claims: &'a [PersistentVolumeClaim],
+    pub volumes: &'a [PersistentVolume],
+    pub storage_classes: &'a [StorageClass],
+}
+
+fn parse_quantity(resource: &str, q: &Quantity) -> f64 {
+    if resource == "cpu" {
+        parse_cpu(&q.0)
+    } else {
+        parse_memory(&q.0)
+    }
+}
+
+fn format_quantity(resource: &str, value: f64) -> String {
+    const MI: f64 = 1024.0 * 1024.0;
+    if resource == "cpu" {
+        format!("{}m", (value * 1000.0).round())
+    } else if resource == "memory"
+        || resource == "ephemeral-storage"
+        || resource.starts_with("hugepages-")
+    {
+        if value >= 1024.0 * MI {
+            format!("{:.1}Gi", value / (1024.0 * MI))
+        } else {
+            format!("{:.0}Mi", value / MI)
+        }
+    } else {
+        format!("{}", value)
+    }
+}
+
+/// Requests the scheduler reserves for a pod: the larger of the summed app containers and
+/// any single init container, plus the runtime class overhead
+pub fn pod_requests(spec: &PodSpec) -> BTreeMap<String, f64> {
+    let mut requests: BTreeMap<String, f64> = BTreeMap::new();
+    let container_requests = |c: &k8s_openapi::api::core::v1::Container| {
+        c.resources
+            .as_ref()
+            .and_then(|r| r.requests.clone())
+            .unwrap_or_default()
+    };
+
+    for container in &spec.containers {
+        for (resource, q) in container_requests(container) {
+            *requests.entry(resource.clone()).or_default() += parse_quantity(&resource, &q);
+        }
+    }
+    for container in spec.init_containers.iter().flatten() {
+        for (resource, q) in container_requests(container) {
+            let value = parse_quantity(&resource, &q);
+            let entry = requests.entry(resource).or_default();
+            *entry = entry.max(value);
+        }
+    }
+    for (resource, q) in spec.overhead.iter().flatten() {
+        *requests.entry(resource.clone()).or_default() += parse_quantity(resource, q);
+    }
+    requests.retain(|_, v| *v > 0.0);
+    requests
+}
+
+fn is_active(pod: &Pod) -> bool {
+    let phase = pod.status.as_ref().and_then(|s| s.phase.as_deref());
+    !matches!(phase, Some("Succeeded") | Some("Failed"))
+}
+
+fn node_name(node: &Node) -> &str {
+    node.metadata.name.as_deref().unwrap_or_default()
+}
+
+fn node_labels(node: &Node) -> BTreeMap<String, String> {
+    node.metadata.labels.clone().unwrap_or_default()
+}
+
+fn check_resources(
+    requests: &BTreeMap<String, f64>,
+    node: &Node,
+    node_pods: &[&Pod],
+    reasons: &mut Vec<String>,
+) {
+    let allocatable = node
+        .status
+        .as_ref()
+        .and_then(|s| s.allocatable.clone())
+        .unwrap_or_default();
+
+    let mut used: BTreeMap<String, f64> = BTreeMap::new();
+    for pod in node_pods {
+        for (resource, value) in pod.spec.as_ref().map(pod_requests).unwrap_or_default() {
+            *used.entry(resource).or_default() += value;
+        }
+    }
+
+    for (resource, requested) in requests {
+        let total = allocatable
+            .get(resource)
+            .map(|q| parse_quantity(resource, q))
+            .unwrap_or(0.0);
+        let free = total - used.get(resource).copied().unwrap_or(0.0);
+        if *requested > free {
+            reasons.push(format!(
+                "Insufficient {}: requests {}, {} free of {} allocatable",
+                resource,
+                format_quantity(resource, *requested),
+                format_quantity(resource, free.max(0.0)),
+                format_quantity(resource, total)
+            ));
+        }
+    }
+
+    let max_pods = allocatable.get("pods").map(|q| parse_cpu(&q.0));
+    if let Some(max_pods) = max_pods {
+        if node_pods.len() as f64 + 1.0 > max_pods {
+            reasons.push(format!(
+                "Too many pods: {} of {} already running",
+                node_pods.len(),
+                max_pods
+            ));
+        }
+    }
+}
+
+/// Whether a toleration covers a taint, following the scheduler's matching rules
+pub fn tolerates(toleration: &Toleration, taint: &Taint) -> bool {
+    let effect = toleration.effect.as_deref().unwrap_or_default();
+    if !effect.is_empty() && effect != taint.effect {
+        return false;
+    }
+    let key = toleration.key.as_deref().unwrap_or_default();
+    match toleration.operator.as_deref().unwrap_or("Equal") {
+        "Exists" => key.is_empty() || key == taint.key,
+        _ => {
+            key == taint.key
+                && toleration.value.as_deref().unwrap_or_default()
+                    == taint.value.as_deref().unwrap_or_default()
+        }
+    }
+}
+
+fn describe_taint(taint: &Taint) -> String {
+    match &taint.value {
+        Some(value) => format!("{}={}:{}", taint.key, value, taint.effect),
+        None => format!("{}:{}", taint.key, taint.effect),
+    }
+}
+
+fn check_taints(spec: &PodSpec, node: &Node, reasons: &mut Vec<String>) {
+    let tolerations = spec.tolerations.as_deref().unwrap_or_default();
+    let taints = node
+        .spec
+        .as_ref()
+        .and_then(|s| s.taints.as_deref())
+        .unwrap_or_default();
+
+    for taint in taints {
+        if taint.effect == "PreferNoSchedule" {
+            continue;
+        }
+        if !tolerations.iter().any(|t| tolerates(t, taint)) {
+            reasons.push(format!("Untolerated taint {}", describe_taint(taint)));
+        }
+    }
+
+    let cordoned = node.spec.as_ref().and_then(|s| s.unschedulable) == Some(true);
+    let has_cordon_taint = taints
+        .iter()
+        .any(|t| t.key == "node.kubernetes.io/unschedulable");
+    if cordoned && !has_cordon_taint {
+        reasons.push("Node is cordoned".to_string());
+    }
+}
+
+fn requirement_matches(req: &NodeSelectorRequirement, values: &BTreeMap<String, String>) -> bool {
+    let wanted = req.values.as_deref().unwrap_or_default();
+    let current = values.get(&req.key);
+    let compare = |ordering: std::cmp::Ordering| {
+        let current = current.and_then(|v| v.parse::<i64>().ok());
+        let bound = wanted.first().and_then(|v| v.parse::<i64>().ok());
+        matches!((current, bound), (Some(c), Some(b)) if c.cmp(&b) == ordering)
+    };
+    match req.operator.as_str() {
+        "In" => current.is_some_and(|v| wanted.contains(v)),
+        "NotIn" => current.is_none_or(|v| !wanted.contains(v)),
+        "Exists" => current.is_some(),
+        "DoesNotExist" => current.is_none(),
+        "Gt" => compare(std::cmp::Ordering::Greater),
+        "Lt" => compare(std::cmp::Ordering::Less),
+        _ => false,
+    }
+}
+
+/// An empty term matches no node, as in the scheduler
+pub fn node_selector_term_matches(term: &NodeSelectorTerm, node: &Node) -> bool {
+    let expressions = term.match_expressions.as_deref().unwrap_or_default();
+    let fields = term.match_fields.as_deref().unwrap_or_default();
+    if expressions.is_empty() && fields.is_empty() {
+        return false;
+    }
+    let labels = node_labels(node);
+    let node_fields = BTreeMap::from([("metadata.name".to_string(), node_name(node).to_string())]);
+    expressions.iter().all(|r| requirement_matches(r, &labels))
+        && fields.iter().all(|r| requirement_matches(r, &node_fields))
+}
+
+fn describe_term(term: &NodeSelectorTerm) -> String {
+    term.match_expressions
+        .iter()
+        .flatten()
+        .chain(term.match_fields.iter().flatten())
+        .map(|r| {
+            let values = r.values.clone().unwrap_or_default().join(", ");
+            match r.operator.as_str() {
+                "Exists" => r.key.clone(),
+                "DoesNotExist" => format!("!{}", r.key),
+                op => format!("{} {} ({})", r.key, op.to_lowercase(), values),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_terms(terms: &[NodeSelectorTerm]) -> String {
+    terms
+        .iter()
+        .map(describe_term)
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+fn node_affinity_terms(spec: &PodSpec) -> Option<&[NodeSelectorTerm]> {
+    spec.affinity
+        .as_ref()?
+        .node_affinity
+        .as_ref()?
+        .required_during_scheduling_ignored_during_execution
+        .as_ref()
+        .map(|s| s.node_selector_terms.as_slice())
+}
+
+/// nodeSelector and required node affinity; the scheduler requires both
+fn check_node_selection(spec: &PodSpec, node: &Node, reasons: &mut Vec<String>) {
+    let labels = node_labels(node);
+    for (key, value) in spec.node_selector.iter().flatten() {
+        match labels.get(key) {
+            Some(actual) if actual == value => {}
+            Some(actual) => reasons.push(format!(
+                "nodeSelector {}={} does not match (node has {}={})",
+                key, value, key, actual
+            )),
+            None => reasons.push(format!(
+                "nodeSelector {}={} does not match (node has no {} label)",
+                key, value, key
+            )),
+        }
+    }
+
+    if let Some(terms) = node_affinity_terms(spec) {
+        if !terms.iter().any(|t| node_selector_term_matches(t, node)) {
+            reasons.push(format!(
+                "Required node affinity does not match: {}",
+                describe_terms(terms)
+            ));
+        }
+    }
+}
+
+fn passes_node_selection(spec: &PodSpec, node: &Node) -> bool {
+    let mut reasons = Vec::new();
+    check_node_selection(spec, node, &mut reasons);
+    reasons.is_empty()
+}
+
+fn pod_labels(pod: &Pod) -> BTreeMap<String, String> {
+    pod.metadata.labels.clone().unwrap_or_default()
+}
+
+fn check_topology_spread(
+    pod: &Pod,
+    node: &Node,
+    input: &SchedulingInput,
+    candidates: &[&Node],
+    reasons: &mut Vec<String>,
+) {
+    let Some(spec) = &pod.spec else {
+        return;
+    };
+    let namespace = pod.metadata.namespace.as_deref().unwrap_or("default");
+    let constraints = spec.topology_spread_constraints.iter().flatten();
+
+    for constraint in constraints.filter(|c| c.when_unsatisfiable == "DoNotSchedule") {
+        let Some(selector) = &constraint.label_selector else {
+            continue;
+        };
+        let key = &constraint.topology_key;
+        let Some(domain) = node_labels(node).get(key).cloned() else {
+            reasons.push(format!(
+                "Node has no {} label required by a topology spread constraint",
+                key
+            ));
+            continue;
+        };
+
+        // Domains come from nodes that pass the pod's nodeSelector/affinity
+        let mut counts: BTreeMap<String, i32> = candidates
+            .iter()
+            .filter_map(|n| node_labels(n).get(key).cloned())
+            .map(|d| (d, 0))
+            .collect();
+        let node_domains: HashMap<&str, String> = candidates
+            .iter()
+            .filter_map(|n| Some((node_name(n), node_labels(n).get(key)?.clone())))
+            .collect();
+        for other in input.pods.iter().filter(|p| is_active(p)) {
+            let on_node = other.spec.as_ref().and_then(|s| s.node_name.as_deref());
+            let Some(other_domain) = on_node.and_then(|n| node_domains.get(n)) else {
+                continue;
+            };
+            if other.metadata.namespace.as_deref() == Some(namespace)
+                && label_selector_matches(selector, &pod_labels(other))
+            {
+                *counts.entry(other_domain.clone()).or_default() += 1;
+            }
+        }
+
+        let self_match = i32::from(label_selector_matches(selector, &pod_labels(pod)));
+        let min = counts.values().copied().min().unwrap_or(0);
+        let skew = counts.get(&domain).copied().unwrap_or(0) + self_match - min;
+        if skew > constraint.max_skew {
+            reasons.push(format!(
+                "Topology spread on {}: placing here gives skew {} (maxSkew {})",
+                key, skew, constraint.max_skew
+            ));
+        }
+    }
+}
+
+fn affinity_term_pods<'a>(
+    term: &'a PodAffinityTerm,
+    pod_namespace: &'a str,
+    pods: &'a [&'a Pod],
+) -> impl Iterator<Item = &'a Pod> + 'a {
+    pods.iter().copied().filter(move |other| {
+        let namespace = other.metadata.namespace.as_deref().unwrap_or_default();
+        // A namespaceSelector is approximated as "all namespaces"
+        let namespace_matches = match (&term.namespaces, &term.namespace_selector) {
+            (Some(namespaces), _) if !namespaces.is_empty() => {
+                namespaces.iter().any(|n| n == namespace)
+            }
+            (_, Some(_)) => true,
+            _ => namespace == pod_namespace,
+        };
+        namespace_matches
+            && is_active(other)
+            && term
+                .label_selector
+                .as_ref()
+                .is_some_and(|s| label_selector_matches(s, &pod_labels(other)))
+    })
+}
+
+fn check_pod_affinity(
+    pod: &Pod,
+    node: &Node,
+    nodes_by_name: &HashMap<&str, &Node>,
+    others: &[&Pod],
+    reasons: &mut Vec<String>,
+) {
+    let Some(affinity) = pod.spec.as_ref().and_then(|s| s.affinity.as_ref()) else {
+        return;
+    };
+    let namespace = pod.metadata.namespace.as_deref().unwrap_or("default");
+    let same_domain = |other: &Pod, key: &str| {
+        let Some(domain) = node_labels(node).get(key).cloned() else {
+            return false;
+        };
+        other
+            .spec
+            .as_ref()
+            .and_then(|s| s.node_name.as_deref())
+            .and_then(|n| nodes_by_name.get(n))
+            .is_some_and(|n| node_labels(n).get(key) == Some(&domain))
+    };
+
+    let required_affinity = affinity
+        .pod_affinity
+        .as_ref()
+        .and_then(|a| {
+            a.required_during_scheduling_ignored_during_execution
+                .as_deref()
+        })
+        .unwrap_or_default();
+    for term in required_affinity {
+        let mut matching = affinity_term_pods(term, namespace, others).peekable();
+        // The first pod of a self-affine group may go anywhere
+        if matching.peek().is_none()
+            && term
+                .label_selector
+                .as_ref()
+                .is_some_and(|s| label_selector_matches(s, &pod_labels(pod)))
+        {
+            continue;
+        }
+        if !matching.any(|p| same_domain(p, &term.topology_key)) {
+            reasons.push(format!(
+                "Pod affinity: no matching pod in the same {}",
+                term.topology_key
+            ));
+        }
+    }
+
+    let required_anti_affinity = affinity
+        .pod_anti_affinity
+        .as_ref()
+        .and_then(|a| {
+            a.required_during_scheduling_ignored_during_execution
+                .as_deref()
+        })
+        .unwrap_or_default();
+    for term in required_anti_affinity {
+        if let Some(conflict) =
+            affinity_term_pods(term, namespace, others).find(|p| same_domain(p, &term.topology_key))
+        {
+            reasons.push(format!(
+                "Pod anti-affinity: {}/{} already runs in the same {}",
+                conflict.metadata.namespace.as_deref().unwrap_or_default(),
+                conflict.metadata.name.as_deref().unwrap_or_default(),
+                term.topology_key
+            ));
+        }
+    }
+}
+
+/// Node constraints from the pod's claims, and issues that block every node
+struct VolumeConstraints {
+    node_terms: Vec<(String, Vec<NodeSelectorTerm>)>, // (claim, terms), one set must match
+    pod_issues: Vec<String>,
+}
+
+fn volume_constraints(pod: &Pod, input: &SchedulingInput) -> VolumeConstraints {
+    let mut constraints = VolumeConstraints {
+        node_terms: vec![],
+        pod_issues: vec![],
+    };
+    let namespace = pod.metadata.namespace.as_deref().unwrap_or("default");
+    let claims = pod
+        .spec
+        .iter()
+        .flat_map(|s| s.volumes.iter().flatten())
+        .filter_map(|v| v.persistent_volume_claim.as_ref().map(|c| &c.claim_name));
+
+    for claim_name in claims {
+        let Some(claim) = input.claims.iter().find(|c| {
+            c.metadata.name.as_ref() == Some(claim_name)
+                && c.metadata.namespace.as_deref() == Some(namespace)
+        }) else {
+            constraints
+                .pod_issues
+                .push(format!("PersistentVolumeClaim {} not found", claim_name));
+            continue;
+        };
+        let claim_spec = claim.spec.as_ref();
+
+        if let Some(volume_name) = claim_spec.and_then(|s| s.volume_name.as_ref()) {
+            let terms = input
+                .volumes
+                .iter()
+                .find(|v| v.metadata.name.as_ref() == Some(volume_name))
+                .and_then(|v| v.spec.as_ref()?.node_affinity.as_ref()?.required.clone())
+                .map(|s| s.node_selector_terms);
+            if let Some(terms) = terms {
+                constraints
+                    .node_terms
+                    .push((format!("{} (volume {})", claim_name, volume_name), terms));
+            }
+            continue;
+        }
+
+        let class = claim_spec
+            .and_then(|s| s.storage_class_name.as_ref())
+            .and_then(|name| {
+                input
+                    .storage_classes
+                    .iter()
+                    .find(|c| c.metadata.name.as_ref() == Some(name))
+            });
+        let waits_for_consumer =
+            class.and_then(|c| c.volume_binding_mode.as_deref()) == Some("WaitForFirstConsumer");
+        if !waits_for_consumer {
+            constraints.pod_issues.push(format!(
+                "PersistentVolumeClaim {} is not bound to a volume",
+                claim_name
+            ));
+            continue;
+        }
+        // Provisioned after scheduling, but only in the class's allowed topologies
+        let terms: Vec<NodeSelectorTerm> = class
+            .and_then(|c| c.allowed_topologies.as_ref())
+            .into_iter()
+            .flatten()
+            .map(|t| NodeSelectorTerm {
+                match_expressions: Some(
+                    t.match_label_expressions
+                        .iter()
+                        .flatten()
+                        .map(|r| NodeSelectorRequirement {
+                            key: r.key.clone(),
+                            operator: "In".to_string(),
+                            values: Some(r.values.clone()),
+                        })
+                        .collect(),
+                ),
+                match_fields: None,
+            })
+            .collect();
+        if !terms.is_empty() {
+            constraints
+                .node_terms
+                .push((format!("{} (storage class topology)", claim_name), terms));
+        }
+    }
+    constraints
+}
+
+/// Evaluate every node for the pod the way the scheduler's filters would
+pub fn explain_scheduling(input: &SchedulingInput) -> SchedulingExplanation {
+    let pod = input.pod;
+    let spec = pod.spec.clone().unwrap_or_default();
+    let requests = pod_requests(&spec);
+    let volumes = volume_constraints(pod, input);
+    let nodes_by_name: HashMap<&str, &Node> =
+        input.nodes.iter().map(|n| (node_name(n), n)).collect();
+    let candidates: Vec<&Node> = input
+        .nodes
+        .iter()
+        .filter(|n| passes_node_selection(&spec, n))
+        .collect();
+    let others: Vec<&Pod> = input
+        .pods
+        .iter()
+        .filter(|p| {
+            p.metadata.name != pod.metadata.name || p.metadata.namespace != pod.metadata.namespace
+        })
+        .collect();
+    let mut pods_by_node: HashMap<&str, Vec<&Pod>> = HashMap::new();
+    for other in others.iter().copied().filter(|p| is_active(p)) {
+        if let Some(name) = other.spec.as_ref().and_then(|s| s.node_name.as_deref()) {
+            pods_by_node.entry(name).or_default().push(other);
+        }
+    }
+
+    let mut nodes: Vec<NodeFit> = input
+        .nodes
+        .iter()
+        .map(|node| {
+            let name = node_name(node);
+            let node_pods = pods_by_node
+                .get(name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+
+            let mut reasons = Vec::new();
+            check_taints(&spec, node, &mut reasons);
+            check_node_selection(&spec, node, &mut reasons);
+            check_resources(&requests, node, node_pods, &mut reasons);
+            for (claim, terms) in &volumes.node_terms {
+                if !terms.iter().any(|t| node_selector_term_matches(t, node)) {
+                    reasons.push(format!(
+                        "Volume {} is restricted to nodes with {}",
+                        claim,
+                        describe_terms(terms)
+                    ));
+                }
+            }
+            check_topology_spread(pod, node, input, &candidates, &mut reasons);
+            check_pod_affinity(pod, node, &nodes_by_name, &others, &mut reasons);
+
+            NodeFit {
+                node: name.to_string(),
+                fits: reasons.is_empty(),
+                reasons,
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| b.fits.cmp(&a.fits).then_with(|| a.node.cmp(&b.node)));
+
+    let mut pod_issues = volumes.pod_issues;
+    if input.nodes.is_empty() {
+        pod_issues.push("The cluster has no nodes".to_string());
+    }
+
+    SchedulingExplanation {
+        pod: pod.metadata.name.clone().unwrap_or_default(),
+        namespace: pod.metadata.namespace.clone().unwrap_or_default(),
+        assigned_node: spec.node_name.clone(),
+        requests: requests
+            .iter()
+            .map(|(r, v)| (r.clone(), format_quantity(r, *v)))
+            .collect(),
+        pod_issues,
+        nodes,
+    }
+}
+
+#[tauri::command]
+pub async fn cluster_explain_pod_scheduling(
+    cluster_id: String,
+    namespace: String,
+    pod_name: String,
+    state: State<'_, ClusterManagerState>,
+) -> Result<SchedulingExplanation, String> {
+    let client = create_client_for_cluster(&cluster_id, &state).await?;
+    let lp = Default::default();
+
+    let pod = Api::<Pod>::namespaced(client.clone(), &namespace)
+        .get(&pod_name)
+        .await
+        .map_err(|e| format!("Failed to get pod: {}", e))?;
+    let nodes = Api::<Node>::all(client.clone())
+        .list(&lp)
+        .await
+        .map_err(|e| format!("Failed to list nodes: {}", e))?
+        .items;
+    let pods = Api::<Pod>::all(client.clone())
+        .list(&lp)
+        .await
+        .map_err(|e| format!("Failed to list pods: {}", e))?
+        .items;
+
+    let has_claims = pod
+        .spec
+        .iter()
+        .flat_map(|s| s.volumes.iter().flatten())
+        .any(|v| v.persistent_volume_claim.is_some());
+    let (claims, volumes, storage_classes) = if has_claims {
+        (
+            Api::<PersistentVolumeClaim>::namespaced(client.clone(), &namespace)
+                .list(&lp)
+                .await
+                .map_err(|e| format!("Failed to list persistent volume claims: {}", e))?
+                .items,
+            Api::<PersistentVolume>::all(client.clone())
+                .list(&lp)
+                .await
+                .map_err(|e| format!("Failed to list persistent volumes: {}", e))?
+                .items,
+            Api::<StorageClass>::all(client)
+                .list(&lp)
+                .await
+                .map_err(|e| format!("Failed to list storage classes: {}", e))?
+                .items,
+        )
+    } else {
+        (vec![], vec![], vec![])
+    };
+
+    Ok(explain_scheduling(&SchedulingInput {
+        pod: &pod,
+        nodes: &nodes,
+        pods: &pods,
+        claims: &claims,
+        volumes: &volumes,
+        storage_classes: &storage_classes,
+    }))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn node(name: &str, zone: &str, extra: Value) -> Node {
+        let mut node = json!({
+            "metadata": {"name": name, "labels": {"topology.kubernetes.io/zone": zone, "kubernetes.io/hostname": name}},
+            "spec": {},
+            "status": {"allocatable": {"cpu": "2", "memory": "4Gi", "pods": "110"}}
+        });
+        merge(&mut node, extra);
+        serde_json::from_value(node).unwrap()
+    }
+
+    fn pod(name: &str, node_name: Option<&str>, extra: Value) -> Pod {
+        let mut pod = json!({
+            "metadata": {"name": name, "namespace": "prod", "labels": {"app": "web"}},
+            "spec": {
+                "nodeName": node_name,
+                "containers": [{"name": "web", "resources": {"requests": {"cpu": "500m", "memory": "1Gi"}}}]
+            },
+            "status": {"phase": if node_name.is_some() { "Running" } else { "Pending" }}
+        });
+        merge(&mut pod, extra);
+        serde_json::from_value(pod).unwrap()
+    }
+
+    fn merge(target: &mut Value, patch: Value) {
+        match (target, patch) {
+            (Value::Object(t), Value::Object(p)) => {
+                for (k, v) in p {
+                    merge(t.entry(k).or_insert(Value::Null), v);
+                }
+            }
+            (t, p) => *t = p,
+        }
+    }
+
+    fn explain(pod: &Pod, nodes: &[Node], pods: &[Pod]) -> SchedulingExplanation {
+        explain_scheduling(&SchedulingInput {
+            pod,
+            nodes,
+            pods,
+            claims: &[],
+            volumes: &[],
+            storage_classes: &[],
+        })
+    }
+
+    fn reasons<'a>(explanation: &'a SchedulingExplanation, node: &str) -> &'a [String] {
+        &explanation
+            .nodes
+            .iter()
+            .find(|n| n.node == node)
+            .unwrap()
+            .reasons
+    }
+
+    #[test]
+    fn test_pod_requests_include_init_containers_and_overhead() {
+        let pod = pod(
+            "p",
+            None,
+            json!({"spec": {
+                "initContainers": [{"name": "init", "resources": {"requests": {"cpu": "2", "memory": "128Mi"}}}],
+                "overhead": {"cpu": "100m"}
+            }}),
+        );
+        let requests = pod_requests(pod.spec.as_ref().unwrap());
+        assert_eq!(requests["cpu"], 2.1);
+        assert_eq!(requests["memory"], 1024.0 * 1024.0 * 1024.0);
+    }
+
+    #[test]
+    fn test_resource_fit_and_taints() {
+        let nodes = [
+            node("full", "a", json!({})),
+            node(
+                "tainted",
+                "a",
+                json!({"spec": {"taints": [{"key": "gpu", "value": "true", "effect": "NoSchedule"}]}}),
+            ),
+            node("free", "a", json!({})),
+        ];
+        let running = [pod(
+            "big",
+            Some("full"),
+            json!({"spec": {"containers": [{"name": "big", "resources": {"requests": {"cpu": "1800m"}}}]}}),
+        )];
+        let pending = pod("web-1", None, json!({}));
+        let explanation = explain(&pending, &nodes, &running);
+
+        assert_eq!(explanation.requests["cpu"], "500m");
+        assert_eq!(explanation.nodes[0].node, "free");
+        assert!(explanation.nodes[0].fits);
+        assert_eq!(
+            reasons(&explanation, "full"),
+            ["Insufficient cpu: requests 500m, 200m free of 2000m allocatable"]
+        );
+        assert_eq!(
+            reasons(&explanation, "tainted"),
+            ["Untolerated taint gpu=true:NoSchedule"]
+        );
+
+        let tolerant = pod(
+            "web-2",
+            None,
+            json!({"spec": {"tolerations": [{"key": "gpu", "operator": "Exists"}]}}),
+        );
+        assert!(reasons(&explain(&tolerant, &nodes, &running), "tainted").is_empty());
+    }
+
+    #[test]
+    fn test_node_selector_and_affinity() {
+        let nodes = [node("a-1", "a", json!({})), node("b-1", "b", json!({}))];
+        let pending = pod(
+            "web-1",
+            None,
+            json!({"spec": {
+                "nodeSelector": {"disk": "ssd"},
+                "affinity": {"nodeAffinity": {"requiredDuringSchedulingIgnoredDuringExecution": {"nodeSelectorTerms": [
+                    {"matchExpressions": [{"key": "topology.kubernetes.io/zone", "operator": "In", "values": ["a"]}]}
+                ]}}}
+            }}),
+        );
+        let explanation = explain(&pending, &nodes, &[]);
+
+        assert_eq!(
+            reasons(&explanation, "a-1"),
+            ["nodeSelector disk=ssd does not match (node has no disk label)"]
+        );
+        assert_eq!(
+            reasons(&explanation, "b-1")[1],
+            "Required node affinity does not match: topology.kubernetes.io/zone in (a)"
+        );
+    }
+
+    #[test]
+    fn test_topology_spread_anti_affinity_and_volume_zone() {
+        let nodes = [node("a-1", "a", json!({})), node("b-1", "b", json!({}))];
+        let running = [pod("web-0", Some("a-1"), json!({}))];
+        let spread = pod(
+            "web-1",
+            None,
+            json!({"spec": {"topologySpreadConstraints": [{
+                "maxSkew": 1, "topologyKey": "topology.kubernetes.io/zone", "whenUnsatisfiable": "DoNotSchedule",
+                "labelSelector": {"matchLabels": {"app": "web"}}
+            }]}}),
+        );
+        let explanation = explain(&spread, &nodes, &running);
+        assert_eq!(
+            reasons(&explanation, "a-1"),
+            ["Topology spread on topology.kubernetes.io/zone: placing here gives skew 2 (maxSkew 1)"]
+        );
+        assert!(reasons(&explanation, "b-1").is_empty());
+
+        let anti = pod(
+            "web-1",
+            None,
+            json!({"spec": {"affinity": {"podAntiAffinity": {"requiredDuringSchedulingIgnoredDuringExecution": [{
+                "topologyKey": "kubernetes.io/hostname", "labelSelector": {"matchLabels": {"app": "web"}}
+            }]}}}}),
+        );
+        assert_eq!(
+            reasons(&explain(&anti, &nodes, &running), "a-1"),
+            ["Pod anti-affinity: prod/web-0 already runs in the same kubernetes.io/hostname"]
+        );
+
+        let with_claim = pod(
+            "db-0",
+            None,
+            json!({"spec": {"volumes": [{"name": "data", "persistentVolumeClaim": {"claimName": "data-db-0"}}]}}),
+        );
+        let claim: PersistentVolumeClaim = serde_json::from_value(json!({
+            "metadata": {"name": "data-db-0", "namespace": "prod"},
+            "spec": {"volumeName": "pv-1"}
+        }))
+        .unwrap();
+        let volume: PersistentVolume = serde_json::from_value(json!({
+            "metadata": {"name": "pv-1"},
+            "spec": {"nodeAffinity": {"required": {"nodeSelectorTerms": [
+                {"matchExpressions": [{"key": "topology.kubernetes.io/zone", "operator": "In", "values": ["b"]}]}
+            ]}}}
+        }))
+        .unwrap();
+        let explanation = explain_scheduling(&SchedulingInput {
+            pod: &with_claim,
+            nodes: &nodes,
+            pods: &[],
+            claims: &[claim],
+            volumes: &[volume],
+            storage_classes: &[],
+        });
+        assert_eq!(
+            reasons(&explanation, "a-1"),
+            ["Volume data-db-0 (volume pv-1) is restricted to nodes with topology.kubernetes.io/zone in (b)"]
+        );
+        assert!(reasons(&explanation, "b-1").is_empty());
+    }
+}
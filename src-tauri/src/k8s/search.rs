@@ -0,0 +1,531 @@
+use crate::cluster_manager::ClusterManagerState;
+use crate::k8s::client::create_client_for_cluster;
+use crate::k8s::common::{calculate_age, get_created_at, WorkloadSummary};
+use crate::k8s::pod::{map_pod_to_summary, PodSummary};
+use crate::k8s::resource_list::{
+    dynamic_summary_api, list_all_pages, list_all_summaries, summary_api, SummaryApi,
+};
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::{ConfigMap, Pod, Secret};
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::NamespaceResourceScope;
+use kube::api::{DynamicObject, ListParams};
+use kube::core::GroupVersionKind;
+use kube::discovery::ApiResource;
+use kube::{Api, Client, ResourceExt};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use tauri::State;
+
+const DEFAULT_SEARCH_LIMIT: usize = 100;
+const SEARCH_CONCURRENCY: usize = 8;
+const MAX_ANNOTATION_VALUE_LEN: usize = 1024;
+const LAST_APPLIED_ANNOTATION: &str = "kubectl.kubernetes.io/last-applied-configuration";
+
+/// Built-in kinds covered by search, besides Pods, ConfigMaps and Secrets (searched by
+/// metadata only) and CRD instances
+const SEARCH_KINDS: &[(&str, &str)] = &[
+    ("apps/v1", "Deployment"),
+    ("apps/v1", "StatefulSet"),
+    ("apps/v1", "DaemonSet"),
+    ("apps/v1", "ReplicaSet"),
+    ("batch/v1", "Job"),
+    ("batch/v1", "CronJob"),
+    ("v1", "Service"),
+    ("networking.k8s.io/v1", "Ingress"),
+    ("networking.k8s.io/v1", "NetworkPolicy"),
+    ("v1", "PersistentVolumeClaim"),
+    ("v1", "PersistentVolume"),
+    ("storage.k8s.io/v1", "StorageClass"),
+    ("autoscaling/v1", "HorizontalPodAutoscaler"),
+    ("policy/v1", "PodDisruptionBudget"),
+    ("v1", "ServiceAccount"),
+    ("rbac.authorization.k8s.io/v1", "Role"),
+    ("rbac.authorization.k8s.io/v1", "RoleBinding"),
+    ("rbac.authorization.k8s.io/v1", "ClusterRole"),
+    ("rbac.authorization.k8s.io/v1", "ClusterRoleBinding"),
+    ("apiextensions.k8s.io/v1", "CustomResourceDefinition"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchField {
+    Name,
+    Label,
+    Annotation,
+}
+
+/// The list-view summary of a search hit, so results render like list rows
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum SearchSummary {
+    Pod(Box<PodSummary>),
+    Workload(WorkloadSummary),
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SearchResult {
+    pub kind: String,
+    pub api_version: String,
+    pub name: String,
+    pub namespace: String, // Empty for cluster-scoped objects
+    pub score: u32,
+    pub matched_field: MatchField,
+    pub matched_text: String, // Name, "key=value" label, or annotation key
+    pub summary: SearchSummary,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SearchKindError {
+    pub kind: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SearchResponse {
+    pub results: Vec<SearchResult>,
+    pub total_matches: usize, // Before truncation to the limit
+    pub errors: Vec<SearchKindError>,
+}
+
+/// How well `text` matches a lowercase query: exact, prefix, prefix of a
+/// `-_./`-separated segment, substring, then (optionally) fuzzy subsequence.
+/// Closer length matches score higher within each tier.
+fn text_score(query: &str, text: &str, fuzzy: bool) -> Option<u32> {
+    let text = text.to_lowercase();
+    if query.is_empty() || text.is_empty() {
+        return None;
+    }
+    let closeness = (100 * query.len() / text.len().max(query.len())) as u32;
+
+    if text == query {
+        return Some(1000);
+    }
+    if text.starts_with(query) {
+        return Some(800 + closeness);
+    }
+    let segment_prefix = text
+        .match_indices(['-', '_', '.', '/'])
+        .any(|(i, _)| text[i + 1..].starts_with(query));
+    if segment_prefix {
+        return Some(600 + closeness);
+    }
+    if text.contains(query) {
+        return Some(400 + closeness);
+    }
+    if !fuzzy || query.len() < 2 {
+        return None;
+    }
+
+    // Every query char in order; a tighter span ranks higher
+    let mut chars = text.char_indices();
+    let mut first = None;
+    let mut last = 0;
+    for q in query.chars() {
+        let (i, _) = chars.by_ref().find(|(_, c)| *c == q)?;
+        first.get_or_insert(i);
+        last = i;
+    }
+    let span = last - first.unwrap_or(0) + 1;
+    Some(100 + (200 * query.len() / span.max(query.len())) as u32)
+}
+
+/// Best match of an object's name, labels and annotations. Label and annotation
+/// matches are weighted below name matches.
+fn score_object(
+    query: &str,
+    name: &str,
+    labels: &BTreeMap<String, String>,
+    annotations: &BTreeMap<String, String>,
+) -> Option<(u32, MatchField, String)> {
+    let mut best: Option<(u32, MatchField, String)> = None;
+    let mut consider = |score: Option<u32>, field: MatchField, text: String| {
+        if let Some(score) = score {
+            if best.as_ref().is_none_or(|(b, _, _)| score > *b) {
+                best = Some((score, field, text));
+            }
+        }
+    };
+
+    consider(
+        text_score(query, name, true),
+        MatchField::Name,
+        name.to_string(),
+    );
+
+    for (key, value) in labels {
+        let pair = format!("{}={}", key, value);
+        let score = text_score(query, value, true)
+            .max(text_score(query, &pair, false))
+            .map(|s| s * 6 / 10);
+        consider(score, MatchField::Label, pair);
+    }
+
+    for (key, value) in annotations {
+        if key == LAST_APPLIED_ANNOTATION {
+            continue;
+        }
+        let value_score = (value.len() <= MAX_ANNOTATION_VALUE_LEN)
+            .then(|| text_score(query, value, false))
+            .flatten();
+        let score = value_score
+            .max(text_score(query, key, false))
+            .map(|s| s * 4 / 10);
+        consider(score, MatchField::Annotation, key.clone());
+    }
+
+    best
+}
+
+async fn search_summary_kind(
+    api: Box<dyn SummaryApi>,
+    api_version: String,
+    kind: String,
+    query: String,
+) -> Result<Vec<SearchResult>, SearchKindError> {
+    let items = list_all_summaries(api.as_ref(), ListParams::default())
+        .await
+        .map_err(|e| SearchKindError {
+            kind: kind.clone(),
+            error: format!("Failed to list {}: {}", kind, e),
+        })?;
+
+    Ok(items
+        .into_iter()
+        .filter_map(|(summary, annotations)| {
+            let (score, matched_field, matched_text) =
+                score_object(&query, &summary.name, &summary.labels, &annotations)?;
+            Some(SearchResult {
+                kind: kind.clone(),
+                api_version: api_version.clone(),
+                name: summary.name.clone(),
+                namespace: summary.namespace.clone(),
+                score,
+                matched_field,
+                matched_text,
+                summary: SearchSummary::Workload(summary),
+            })
+        })
+        .collect())
+}
+
+/// Summary built from metadata alone, for kinds listed without their payload
+fn map_metadata_to_summary(meta: ObjectMeta) -> WorkloadSummary {
+    WorkloadSummary {
+        id: meta.uid.clone().unwrap_or_default(),
+        name: meta.name.clone().unwrap_or_default(),
+        namespace: meta.namespace.clone().unwrap_or_default(),
+        age: calculate_age(meta.creation_timestamp.as_ref()),
+        created_at: get_created_at(meta.creation_timestamp.as_ref()),
+        labels: meta.labels.unwrap_or_default(),
+        status: "-".to_string(),
+        images: vec![],
+    }
+}
+
+/// Search a kind through a metadata-only LIST, so ConfigMap and Secret payloads
+/// (large, and sensitive for Secrets) are never fetched
+async fn search_metadata_kind<K>(
+    client: Client,
+    namespace: Option<&str>,
+    query: &str,
+) -> Result<Vec<SearchResult>, SearchKindError>
+where
+    K: kube::Resource<DynamicType = (), Scope = NamespaceResourceScope>
+        + k8s_openapi::Resource
+        + Clone
+        + serde::de::DeserializeOwned
+        + Debug,
+{
+    let api: Api<K> = match namespace {
+        Some(ns) => Api::namespaced(client, ns),
+        None => Api::all(client),
+    };
+    let error = |e: kube::Error| SearchKindError {
+        kind: K::KIND.to_string(),
+        error: format!("Failed to list {}: {}", K::KIND, e),
+    };
+
+    let api = &api;
+    list_all_pages(ListParams::default(), |params| async move {
+        let list = api.list_metadata(&params).await?;
+        let results = list
+            .items
+            .into_iter()
+            .filter_map(|obj| {
+                let name = obj.name_any();
+                let (score, matched_field, matched_text) =
+                    score_object(query, &name, obj.labels(), obj.annotations())?;
+                Some(SearchResult {
+                    kind: K::KIND.to_string(),
+                    api_version: K::API_VERSION.to_string(),
+                    name,
+                    namespace: obj.namespace().unwrap_or_default(),
+                    score,
+                    matched_field,
+                    matched_text,
+                    summary: SearchSummary::Workload(map_metadata_to_summary(obj.metadata)),
+                })
+            })
+            .collect();
+        Ok((results, list.metadata.continue_))
+    })
+    .await
+    .map_err(error)
+}
+
+async fn search_pods(
+    client: Client,
+    namespace: Option<&str>,
+    query: &str,
+) -> Result<Vec<SearchResult>, SearchKindError> {
+    let api: Api<Pod> = match namespace {
+        Some(ns) => Api::namespaced(client, ns),
+        None => Api::all(client),
+    };
+    let api = &api;
+    list_all_pages(ListParams::default(), |params| async move {
+        let list = api.list(&params).await?;
+        let results = list
+            .items
+            .into_iter()
+            .filter_map(|pod| {
+                let (score, matched_field, matched_text) =
+                    score_object(query, &pod.name_any(), pod.labels(), pod.annotations())?;
+                Some(SearchResult {
+                    kind: "Pod".to_string(),
+                    api_version: "v1".to_string(),
+                    name: pod.name_any(),
+                    namespace: pod.namespace().unwrap_or_default(),
+                    score,
+                    matched_field,
+                    matched_text,
+                    summary: SearchSummary::Pod(Box::new(map_pod_to_summary(pod))),
+                })
+            })
+            .collect();
+        Ok((results, list.metadata.continue_))
+    })
+    .await
+    .map_err(|e| SearchKindError {
+        kind: "Pod".to_string(),
+        error: format!("Failed to list pods: {}", e),
+    })
+}
+
+/// (apiVersion, kind, dynamic api) for the served storage version of every CRD
+async fn custom_resource_apis(
+    client: &Client,
+    namespace: Option<&str>,
+) -> Result<Vec<(String, String, Api<DynamicObject>)>, String> {
+    let crds: Api<CustomResourceDefinition> = Api::all(client.clone());
+    let crds = crds
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to list CRDs: {}", e))?;
+
+    Ok(crds
+        .items
+        .into_iter()
+        .filter_map(|crd| {
+            let spec = crd.spec;
+            let version = spec
+                .versions
+                .iter()
+                .find(|v| v.storage && v.served)
+                .or_else(|| spec.versions.iter().find(|v| v.served))?;
+            let gvk = GroupVersionKind::gvk(&spec.group, &version.name, &spec.names.kind);
+            let resource = ApiResource::from_gvk_with_plural(&gvk, &spec.names.plural);
+            let api = match (spec.scope.as_str(), namespace) {
+                ("Namespaced", Some(ns)) => Api::namespaced_with(client.clone(), ns, &resource),
+                _ => Api::all_with(client.clone(), &resource),
+            };
+            Some((resource.api_version, spec.names.kind, api))
+        })
+        .collect())
+}
+
+fn kind_selected(kinds: &Option<Vec<String>>, kind: &str) -> bool {
+    kinds
+        .as_ref()
+        .is_none_or(|kinds| kinds.iter().any(|k| k.eq_ignore_ascii_case(kind)))
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ClusterSearchParams {
+    pub cluster_id: String,
+    pub query: String,
+    pub namespace: Option<String>,  // None searches all namespaces
+    pub kinds: Option<Vec<String>>, // Restrict to these kinds; CRD kinds included
+    pub include_custom_resources: Option<bool>, // Defaults to true
+    pub limit: Option<usize>,
+}
+
+/// Search names, labels and annotations across common kinds and CRD instances
+#[tauri::command]
+pub async fn cluster_search_resources(
+    params: ClusterSearchParams,
+    state: State<'_, ClusterManagerState>,
+) -> Result<SearchResponse, String> {
+    let query = params.query.trim().to_lowercase();
+    if query.is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+    let namespace = params.namespace.as_deref().filter(|ns| !ns.is_empty());
+    let client = create_client_for_cluster(&params.cluster_id, &state).await?;
+
+    let mut apis: Vec<(String, String, Box<dyn SummaryApi>)> = Vec::new();
+    let mut errors = Vec::new();
+
+    for (api_version, kind) in SEARCH_KINDS {
+        if !kind_selected(&params.kinds, kind) {
+            continue;
+        }
+        match summary_api(client.clone(), kind, Some(api_version), namespace).await {
+            Ok(api) => apis.push((api_version.to_string(), kind.to_string(), api)),
+            Err(error) => errors.push(SearchKindError {
+                kind: kind.to_string(),
+                error,
+            }),
+        }
+    }
+
+    if params.include_custom_resources.unwrap_or(true) {
+        match custom_resource_apis(&client, namespace).await {
+            Ok(custom) => {
+                for (api_version, kind, api) in custom {
+                    if kind_selected(&params.kinds, &kind) {
+                        apis.push((api_version, kind, dynamic_summary_api(api)));
+                    }
+                }
+            }
+            Err(error) => errors.push(SearchKindError {
+                kind: "CustomResourceDefinition".to_string(),
+                error,
+            }),
+        }
+    }
+
+    let mut results = Vec::new();
+    if kind_selected(&params.kinds, "Pod") {
+        match search_pods(client.clone(), namespace, &query).await {
+            Ok(found) => results.extend(found),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if kind_selected(&params.kinds, "ConfigMap") {
+        match search_metadata_kind::<ConfigMap>(client.clone(), namespace, &query).await {
+            Ok(found) => results.extend(found),
+            Err(error) => errors.push(error),
+        }
+    }
+    if kind_selected(&params.kinds, "Secret") {
+        match search_metadata_kind::<Secret>(client.clone(), namespace, &query).await {
+            Ok(found) => results.extend(found),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    let searches: Vec<_> = apis
+        .into_iter()
+        .map(|(api_version, kind, api)| search_summary_kind(api, api_version, kind, query.clone()))
+        .collect();
+    let mut searches = futures::stream::iter(searches).buffer_unordered(SEARCH_CONCURRENCY);
+
+    while let Some(found) = searches.next().await {
+        match found {
+            Ok(found) => results.extend(found),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    results.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.kind.cmp(&b.kind))
+            .then_with(|| a.namespace.cmp(&b.namespace))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    let total_matches = results.len();
+    results.truncate(params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+    errors.sort_by(|a, b| a.kind.cmp(&b.kind));
+
+    Ok(SearchResponse {
+        results,
+        total_matches,
+        errors,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_score_tiers() {
+        let exact = text_score("payments-worker", "payments-worker", true).unwrap();
+        let prefix = text_score("payments", "payments-worker", true).unwrap();
+        let segment = text_score("worker", "payments-worker", true).unwrap();
+        let substring = text_score("ments", "payments-worker", true).unwrap();
+        let fuzzy = text_score("pmtwkr", "payments-worker", true).unwrap();
+
+        assert!(exact > prefix);
+        assert!(prefix > segment);
+        assert!(segment > substring);
+        assert!(substring > fuzzy);
+
+        assert!(text_score("pmtwkr", "payments-worker", false).is_none());
+        assert!(text_score("xyz", "payments-worker", true).is_none());
+        // Case-insensitive
+        assert_eq!(text_score("api", "API", true), Some(1000));
+    }
+
+    #[test]
+    fn test_closer_length_ranks_higher() {
+        let short = text_score("api", "api-gateway", true).unwrap();
+        let long = text_score("api", "api-gateway-internal-canary", true).unwrap();
+        assert!(short > long);
+    }
+
+    #[test]
+    fn test_score_object_weights_labels_below_names() {
+        let labels = BTreeMap::from([("app".to_string(), "payments-worker".to_string())]);
+        let annotations = BTreeMap::new();
+
+        // A name prefix outranks an exact label value
+        let (_, field, _) = score_object(
+            "payments-worker",
+            "payments-worker-7d9f",
+            &labels,
+            &annotations,
+        )
+        .unwrap();
+        assert_eq!(field, MatchField::Name);
+
+        let (score, field, text) =
+            score_object("payments-worker", "pw-7d9f", &labels, &annotations).unwrap();
+        assert_eq!(field, MatchField::Label);
+        assert_eq!(text, "app=payments-worker");
+        assert_eq!(score, 600);
+    }
+
+    #[test]
+    fn test_score_object_matches_annotations() {
+        let annotations = BTreeMap::from([
+            ("team".to_string(), "billing-squad".to_string()),
+            (
+                LAST_APPLIED_ANNOTATION.to_string(),
+                "{\"billing\": true}".to_string(),
+            ),
+        ]);
+        let (_, field, text) =
+            score_object("billing", "api", &BTreeMap::new(), &annotations).unwrap();
+        assert_eq!(field, MatchField::Annotation);
+        assert_eq!(text, "team");
+
+        assert!(score_object("nothing", "api", &BTreeMap::new(), &annotations).is_none());
+    }
+}
@@ -0,0 +1,265 @@
+use kube::api::ListParams;
+
+const MAX_LABEL_NAME_LEN: usize = 63;
+const MAX_LABEL_PREFIX_LEN: usize = 253;
+const MAX_SELECTOR_LEN: usize = 4096;
+
+/// Split a selector on top-level commas; commas inside `in (...)` sets are kept
+fn split_requirements(selector: &str) -> Result<Vec<&str>, String> {
+    let mut requirements = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in selector.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| "Unbalanced ')' in selector".to_string())?;
+            }
+            ',' if depth == 0 => {
+                requirements.push(selector[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err("Unbalanced '(' in selector".to_string());
+    }
+    requirements.push(selector[start..].trim());
+
+    if requirements.iter().any(|r| r.is_empty()) {
+        return Err("Selector contains an empty requirement".to_string());
+    }
+    Ok(requirements)
+}
+
+/// Label names and values: up to 63 alphanumerics, '-', '_' or '.', starting and
+/// ending with an alphanumeric. Values may also be empty.
+fn is_valid_label_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_LABEL_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.ends_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+fn is_valid_label_value(value: &str) -> bool {
+    value.is_empty() || is_valid_label_name(value)
+}
+
+/// Label keys: an optional DNS subdomain prefix and '/', then a label name
+fn is_valid_label_key(key: &str) -> bool {
+    match key.split_once('/') {
+        Some((prefix, name)) => {
+            !prefix.is_empty()
+                && prefix.len() <= MAX_LABEL_PREFIX_LEN
+                && prefix.split('.').all(|part| {
+                    !part.is_empty()
+                        && part
+                            .chars()
+                            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                        && !part.starts_with('-')
+                        && !part.ends_with('-')
+                })
+                && is_valid_label_name(name)
+        }
+        None => is_valid_label_name(key),
+    }
+}
+
+fn validate_label_requirement(requirement: &str) -> Result<(), String> {
+    let invalid_key = |key: &str| format!("Invalid label key '{}' in selector", key);
+    let invalid_value = |value: &str| format!("Invalid label value '{}' in selector", value);
+
+    // Existence checks: "key" and "!key"
+    if let Some(key) = requirement.strip_prefix('!') {
+        let key = key.trim();
+        return is_valid_label_key(key)
+            .then_some(())
+            .ok_or_else(|| invalid_key(key));
+    }
+
+    // Set-based: "key in (a,b)" and "key notin (a,b)"
+    if let Some(open) = requirement.find('(') {
+        let mut head = requirement[..open].split_whitespace();
+        let (Some(key), Some(operator), None) = (head.next(), head.next(), head.next()) else {
+            return Err(format!("Invalid set requirement '{}'", requirement));
+        };
+        if operator != "in" && operator != "notin" {
+            return Err(format!(
+                "Unknown operator '{}' in '{}' (expected 'in' or 'notin')",
+                operator, requirement
+            ));
+        }
+        if !is_valid_label_key(key) {
+            return Err(invalid_key(key));
+        }
+        let values = requirement[open + 1..]
+            .strip_suffix(')')
+            .ok_or_else(|| format!("Invalid set requirement '{}'", requirement))?;
+        for value in values.split(',').map(str::trim) {
+            if !is_valid_label_value(value) {
+                return Err(invalid_value(value));
+            }
+        }
+        return Ok(());
+    }
+
+    // Equality-based: "key=value", "key==value" and "key!=value"
+    let (key, value) = if let Some((key, value)) = requirement.split_once("!=") {
+        (key, value)
+    } else if let Some((key, value)) = requirement.split_once("==") {
+        (key, value)
+    } else if let Some((key, value)) = requirement.split_once('=') {
+        (key, value)
+    } else {
+        let key = requirement;
+        return is_valid_label_key(key)
+            .then_some(())
+            .ok_or_else(|| invalid_key(key));
+    };
+
+    let (key, value) = (key.trim(), value.trim());
+    if !is_valid_label_key(key) {
+        return Err(invalid_key(key));
+    }
+    if !is_valid_label_value(value) {
+        return Err(invalid_value(value));
+    }
+    Ok(())
+}
+
+/// Validate a label selector such as `app=api,tier!=cache,env in (prod,staging),!canary`
+pub fn validate_label_selector(selector: &str) -> Result<(), String> {
+    if selector.len() > MAX_SELECTOR_LEN {
+        return Err("Label selector is too long".to_string());
+    }
+    for requirement in split_requirements(selector)? {
+        validate_label_requirement(requirement)?;
+    }
+    Ok(())
+}
+
+/// Validate a field selector such as `status.phase=Running,spec.nodeName!=node-3`
+pub fn validate_field_selector(selector: &str) -> Result<(), String> {
+    if selector.len() > MAX_SELECTOR_LEN {
+        return Err("Field selector is too long".to_string());
+    }
+    for requirement in selector.split(',').map(str::trim) {
+        let field = if let Some((field, _)) = requirement.split_once("!=") {
+            field
+        } else if let Some((field, _)) = requirement.split_once('=') {
+            field
+        } else {
+            return Err(format!(
+                "Invalid field requirement '{}' (expected field=value or field!=value)",
+                requirement
+            ));
+        };
+
+        let field = field.trim();
+        let valid_field = !field.is_empty()
+            && field
+                .split('.')
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+        if !valid_field {
+            return Err(format!("Invalid field '{}' in field selector", field));
+        }
+    }
+    Ok(())
+}
+
+/// Build list params from optional, user-supplied selectors. Blank selectors are ignored.
+pub fn selector_list_params(
+    label_selector: Option<&str>,
+    field_selector: Option<&str>,
+) -> Result<ListParams, String> {
+    let mut params = ListParams::default();
+
+    if let Some(selector) = label_selector.map(str::trim).filter(|s| !s.is_empty()) {
+        validate_label_selector(selector)?;
+        params = params.labels(selector);
+    }
+    if let Some(selector) = field_selector.map(str::trim).filter(|s| !s.is_empty()) {
+        validate_field_selector(selector)?;
+        params = params.fields(selector);
+    }
+
+    Ok(params)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_label_selectors() {
+        for selector in [
+            "app=api",
+            "app=api,tier!=cache",
+            "app==api",
+            "app.kubernetes.io/name=web",
+            "env in (prod, staging),!canary",
+            "tier notin (cache)",
+            "release",
+            "app=",
+        ] {
+            assert!(
+                validate_label_selector(selector).is_ok(),
+                "expected '{}' to be valid",
+                selector
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_label_selectors() {
+        for selector in [
+            "app=api,",
+            "app=-api",
+            "-app=api",
+            "env in (prod",
+            "env within (prod)",
+            "Example.COM/name=x",
+            "app=a b",
+        ] {
+            assert!(
+                validate_label_selector(selector).is_err(),
+                "expected '{}' to be invalid",
+                selector
+            );
+        }
+    }
+
+    #[test]
+    fn test_field_selectors() {
+        assert!(validate_field_selector("status.phase=Running").is_ok());
+        assert!(validate_field_selector("spec.nodeName=node-3,metadata.name!=x").is_ok());
+        assert!(validate_field_selector("status.phase==Running").is_ok());
+        assert!(validate_field_selector("status.phase").is_err());
+        assert!(validate_field_selector("status..phase=Running").is_err());
+        assert!(validate_field_selector("=Running").is_err());
+    }
+
+    #[test]
+    fn test_selector_list_params_ignores_blank_selectors() {
+        let params = selector_list_params(Some("  "), None).unwrap();
+        assert!(params.label_selector.is_none());
+        assert!(params.field_selector.is_none());
+
+        let params = selector_list_params(Some(" app=api "), Some("status.phase=Running")).unwrap();
+        assert_eq!(params.label_selector.as_deref(), Some("app=api"));
+        assert_eq!(
+            params.field_selector.as_deref(),
+            Some("status.phase=Running")
+        );
+
+        assert!(selector_list_params(Some("app in ("), None).is_err());
+    }
+}
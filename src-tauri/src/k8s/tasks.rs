@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tauri::async_runtime::JoinHandle;
+use tauri::{State, Window};
+
+/// Metadata describing a running background task (log stream, watch, port-forward, ...)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TaskInfo {
+    pub id: String,   // Unique key, e.g. "logs:{stream_id}" or "pod_watch:{cluster}:{ns}"
+    pub kind: String, // "logs", "pod_watch", ...
+    pub cluster_id: Option<String>,
+    pub window_label: String,
+    pub description: String,
+    pub started_at: i64,
+}
+
+impl TaskInfo {
+    pub fn new(
+        id: String,
+        kind: &str,
+        cluster_id: Option<&str>,
+        window: &Window,
+        description: String,
+    ) -> Self {
+        TaskInfo {
+            id,
+            kind: kind.to_string(),
+            cluster_id: cluster_id.map(str::to_string),
+            window_label: window.label().to_string(),
+            description,
+            started_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+struct ManagedTask {
+    info: TaskInfo,
+    generation: u64,
+    handle: JoinHandle<()>,
+}
+
+/// Running tasks keyed by id. Registering an id that is already running aborts the old task.
+#[derive(Default)]
+pub struct TaskRegistry {
+    tasks: HashMap<String, ManagedTask>,
+    next_generation: u64,
+}
+
+impl TaskRegistry {
+    fn next_generation(&mut self) -> u64 {
+        self.next_generation += 1;
+        self.next_generation
+    }
+
+    fn insert(&mut self, info: TaskInfo, generation: u64, handle: JoinHandle<()>) {
+        let task = ManagedTask {
+            info,
+            generation,
+            handle,
+        };
+        if let Some(old) = self.tasks.insert(task.info.id.clone(), task) {
+            old.handle.abort();
+        }
+    }
+
+    /// Forget a task that finished on its own, unless it was already replaced
+    fn finish(&mut self, id: &str, generation: u64) {
+        if self
+            .tasks
+            .get(id)
+            .is_some_and(|task| task.generation == generation)
+        {
+            self.tasks.remove(id);
+        }
+    }
+
+    pub fn stop(&mut self, id: &str) -> bool {
+        match self.tasks.remove(id) {
+            Some(task) => {
+                task.handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop every task matching `predicate`, returning how many were stopped
+    pub fn stop_where(&mut self, predicate: impl Fn(&TaskInfo) -> bool) -> usize {
+        let ids: Vec<String> = self
+            .tasks
+            .values()
+            .filter(|task| predicate(&task.info))
+            .map(|task| task.info.id.clone())
+            .collect();
+        ids.iter().filter(|id| self.stop(id)).count()
+    }
+
+    pub fn list(&self) -> Vec<TaskInfo> {
+        let mut tasks: Vec<TaskInfo> = self.tasks.values().map(|t| t.info.clone()).collect();
+        tasks.sort_by(|a, b| a.started_at.cmp(&b.started_at).then(a.id.cmp(&b.id)));
+        tasks
+    }
+}
+
+/// Single registry for every long-running background task
+pub struct BackgroundTaskState(pub Arc<Mutex<TaskRegistry>>);
+
+impl Default for BackgroundTaskState {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(TaskRegistry::default())))
+    }
+}
+
+impl BackgroundTaskState {
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, TaskRegistry>, String> {
+        self.0
+            .lock()
+            .map_err(|e| format!("Task registry lock poisoned: {}", e))
+    }
+
+    /// Spawn `task` and register it under `info.id`, replacing any task with the same id.
+    /// The task unregisters itself when it completes.
+    pub fn spawn<F>(&self, info: TaskInfo, task: F) -> Result<(), String>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let registry = self.0.clone();
+        let mut tasks = self.lock()?;
+        let generation = tasks.next_generation();
+        let id = info.id.clone();
+
+        let handle = tauri::async_runtime::spawn(async move {
+            task.await;
+            match registry.lock() {
+                Ok(mut tasks) => tasks.finish(&id, generation),
+                Err(_) => eprintln!("Warning: failed to clean up background task '{}'", id),
+            }
+        });
+        tasks.insert(info, generation, handle);
+        Ok(())
+    }
+
+    pub fn stop(&self, id: &str) -> Result<bool, String> {
+        Ok(self.lock()?.stop(id))
+    }
+
+    /// Stop all tasks whose id starts with `prefix`, e.g. "cache:{cluster_id}:"
+    pub fn stop_prefix(&self, prefix: &str) -> Result<usize, String> {
+        Ok(self.lock()?.stop_where(|info| info.id.starts_with(prefix)))
+    }
+
+    /// Stop all tasks owned by a window, e.g. when it is destroyed
+    pub fn stop_window(&self, window_label: &str) -> Result<usize, String> {
+        Ok(self
+            .lock()?
+            .stop_where(|info| info.window_label == window_label))
+    }
+}
+
+// Tauri Commands
+
+#[tauri::command]
+pub fn list_background_tasks(
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<Vec<TaskInfo>, String> {
+    Ok(tasks.lock()?.list())
+}
+
+#[tauri::command]
+pub fn stop_background_task(
+    task_id: String,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<bool, String> {
+    tasks.stop(&task_id)
+}
+
+/// Stop every background task, or only those of one window when `window_label` is set
+#[tauri::command]
+pub fn stop_all_background_tasks(
+    window_label: Option<String>,
+    tasks: State<'_, BackgroundTaskState>,
+) -> Result<usize, String> {
+    match window_label {
+        Some(label) => tasks.stop_window(&label),
+        None => Ok(tasks.lock()?.stop_where(|_| true)),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn info(id: &str, window: &str) -> TaskInfo {
+        TaskInfo {
+            id: id.to_string(),
+            kind: "logs".to_string(),
+            cluster_id: Some("c1".to_string()),
+            window_label: window.to_string(),
+            description: String::new(),
+            started_at: 0,
+        }
+    }
+
+    fn pending_handle() -> JoinHandle<()> {
+        tauri::async_runtime::spawn(std::future::pending())
+    }
+
+    #[test]
+    fn registering_same_id_replaces_and_stale_finish_is_ignored() {
+        let mut registry = TaskRegistry::default();
+        let first = registry.next_generation();
+        registry.insert(info("logs:a", "main"), first, pending_handle());
+        let second = registry.next_generation();
+        registry.insert(info("logs:a", "main"), second, pending_handle());
+        assert_eq!(registry.list().len(), 1);
+
+        // The replaced task finishing must not remove its successor
+        registry.finish("logs:a", first);
+        assert_eq!(registry.list().len(), 1);
+        registry.finish("logs:a", second);
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn stop_where_only_stops_matching_window() {
+        let mut registry = TaskRegistry::default();
+        for (id, window) in [("logs:a", "main"), ("logs:b", "popout"), ("watch", "main")] {
+            let generation = registry.next_generation();
+            registry.insert(info(id, window), generation, pending_handle());
+        }
+
+        assert_eq!(registry.stop_where(|i| i.window_label == "main"), 2);
+        let remaining: Vec<String> = registry.list().into_iter().map(|i| i.id).collect();
+        assert_eq!(remaining, vec!["logs:b"]);
+        assert!(registry.stop("logs:b"));
+        assert!(!registry.stop("logs:b"));
+    }
+
+    #[test]
+    fn spawned_tasks_unregister_when_done() {
+        let state = BackgroundTaskState::default();
+        let (tx, rx) = std::sync::mpsc::channel();
+        state
+            .spawn(info("done", "main"), async move {
+                tx.send(()).unwrap();
+            })
+            .unwrap();
+        rx.recv().unwrap();
+
+        for _ in 0..100 {
+            if state.0.lock().unwrap().list().is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(
+            state.0.lock().unwrap().list().is_empty(),
+            "task was not unregistered"
+        );
+    }
+}
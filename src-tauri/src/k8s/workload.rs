@@ -1,6 +1,8 @@
 use crate::cluster_manager::ClusterManagerState;
 use crate::k8s::client::create_client_for_cluster;
 use crate::k8s::common::{calculate_age, get_created_at, WorkloadSummary};
+use crate::k8s::resource_cache::ResourceCacheState;
+use crate::k8s::selectors::selector_list_params;
 use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
 use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
 use k8s_openapi::api::batch::v1::{CronJob, Job};
@@ -22,8 +24,22 @@ macro_rules! impl_workload_commands {
         pub async fn $list_fn(
             cluster_id: String,
             namespace: Option<String>,
+            label_selector: Option<String>,
+            field_selector: Option<String>,
             state: State<'_, ClusterManagerState>,
+            cache: State<'_, ResourceCacheState>,
         ) -> Result<Vec<WorkloadSummary>, String> {
+            let list_params =
+                selector_list_params(label_selector.as_deref(), field_selector.as_deref())?;
+            // The cache holds unfiltered lists; selectors always go to the API server
+            let filtered =
+                list_params.label_selector.is_some() || list_params.field_selector.is_some();
+            if !filtered {
+                if let Some(items) = cache.list::<$resource>(&cluster_id, namespace.as_deref()) {
+                    return Ok(items.into_iter().map($map_fn).collect());
+                }
+            }
+
             let client = create_client_for_cluster(&cluster_id, &state).await?;
             let api: Api<$resource> = if let Some(ns) = namespace {
                 Api::namespaced(client, &ns)
@@ -31,10 +47,7 @@ macro_rules! impl_workload_commands {
                 Api::all(client)
             };
 
-            let list = api
-                .list(&Default::default())
-                .await
-                .map_err(|e| e.to_string())?;
+            let list = api.list(&list_params).await.map_err(|e| e.to_string())?;
             Ok(list.items.into_iter().map($map_fn).collect())
         }
 
@@ -61,15 +74,16 @@ macro_rules! impl_cluster_resource_commands {
         pub async fn $list_fn(
             cluster_id: String,
             _namespace: Option<String>,
+            label_selector: Option<String>,
+            field_selector: Option<String>,
             state: State<'_, ClusterManagerState>,
         ) -> Result<Vec<WorkloadSummary>, String> {
+            let list_params =
+                selector_list_params(label_selector.as_deref(), field_selector.as_deref())?;
             let client = create_client_for_cluster(&cluster_id, &state).await?;
             let api: Api<$resource> = Api::all(client);
 
-            let list = api
-                .list(&Default::default())
-                .await
-                .map_err(|e| e.to_string())?;
+            let list = api.list(&list_params).await.map_err(|e| e.to_string())?;
             Ok(list.items.into_iter().map($map_fn).collect())
         }
 
@@ -90,7 +104,7 @@ macro_rules! impl_cluster_resource_commands {
     };
 }
 
-fn map_deployment_to_summary(d: Deployment) -> WorkloadSummary {
+pub(crate) fn map_deployment_to_summary(d: Deployment) -> WorkloadSummary {
     let meta = d.metadata;
     let spec = d.spec.unwrap_or_default();
     let status = d.status.unwrap_or_default();
@@ -121,7 +135,7 @@ fn map_deployment_to_summary(d: Deployment) -> WorkloadSummary {
     }
 }
 
-fn map_statefulset_to_summary(s: StatefulSet) -> WorkloadSummary {
+pub(crate) fn map_statefulset_to_summary(s: StatefulSet) -> WorkloadSummary {
     let meta = s.metadata;
     let spec = s.spec.unwrap_or_default();
     let status = s.status.unwrap_or_default();
@@ -152,7 +166,7 @@ fn map_statefulset_to_summary(s: StatefulSet) -> WorkloadSummary {
     }
 }
 
-fn map_daemonset_to_summary(d: DaemonSet) -> WorkloadSummary {
+pub(crate) fn map_daemonset_to_summary(d: DaemonSet) -> WorkloadSummary {
     let meta = d.metadata;
     let spec = d.spec.unwrap_or_default();
     let status = d.status.unwrap_or_default();
@@ -183,7 +197,7 @@ fn map_daemonset_to_summary(d: DaemonSet) -> WorkloadSummary {
     }
 }
 
-fn map_replicaset_to_summary(r: ReplicaSet) -> WorkloadSummary {
+pub(crate) fn map_replicaset_to_summary(r: ReplicaSet) -> WorkloadSummary {
     let meta = r.metadata;
     let spec = r.spec.unwrap_or_default();
     let status = r.status.unwrap_or_default();
@@ -218,7 +232,7 @@ fn map_replicaset_to_summary(r: ReplicaSet) -> WorkloadSummary {
     }
 }
 
-fn map_job_to_summary(j: Job) -> WorkloadSummary {
+pub(crate) fn map_job_to_summary(j: Job) -> WorkloadSummary {
     let meta = j.metadata;
     let spec = j.spec.unwrap_or_default();
     let status = j.status.unwrap_or_default();
@@ -249,7 +263,7 @@ fn map_job_to_summary(j: Job) -> WorkloadSummary {
     }
 }
 
-fn map_cronjob_to_summary(c: CronJob) -> WorkloadSummary {
+pub(crate) fn map_cronjob_to_summary(c: CronJob) -> WorkloadSummary {
     let meta = c.metadata;
     let spec = c.spec.unwrap_or_default();
     let status = c.status.unwrap_or_default();
@@ -284,7 +298,7 @@ fn map_cronjob_to_summary(c: CronJob) -> WorkloadSummary {
 }
 
 // Config Maps
-fn map_configmap_to_summary(c: ConfigMap) -> WorkloadSummary {
+pub(crate) fn map_configmap_to_summary(c: ConfigMap) -> WorkloadSummary {
     let meta = c.metadata;
     let count = c.data.map(|d| d.len()).unwrap_or(0) + c.binary_data.map(|d| d.len()).unwrap_or(0);
 
@@ -301,7 +315,7 @@ fn map_configmap_to_summary(c: ConfigMap) -> WorkloadSummary {
 }
 
 // Secrets
-fn map_secret_to_summary(s: Secret) -> WorkloadSummary {
+pub(crate) fn map_secret_to_summary(s: Secret) -> WorkloadSummary {
     let meta = s.metadata;
     let count = s.data.map(|d| d.len()).unwrap_or(0) + s.string_data.map(|d| d.len()).unwrap_or(0);
 
@@ -322,7 +336,7 @@ fn map_secret_to_summary(s: Secret) -> WorkloadSummary {
 }
 
 // Resource Quotas
-fn map_resource_quota_to_summary(r: ResourceQuota) -> WorkloadSummary {
+pub(crate) fn map_resource_quota_to_summary(r: ResourceQuota) -> WorkloadSummary {
     let meta = r.metadata;
 
     WorkloadSummary {
@@ -338,7 +352,7 @@ fn map_resource_quota_to_summary(r: ResourceQuota) -> WorkloadSummary {
 }
 
 // Limit Ranges
-fn map_limit_range_to_summary(l: LimitRange) -> WorkloadSummary {
+pub(crate) fn map_limit_range_to_summary(l: LimitRange) -> WorkloadSummary {
     let meta = l.metadata;
     WorkloadSummary {
         id: meta.uid.clone().unwrap_or_default(),
@@ -353,7 +367,7 @@ fn map_limit_range_to_summary(l: LimitRange) -> WorkloadSummary {
 }
 
 // HPA
-fn map_hpa_to_summary(h: HorizontalPodAutoscaler) -> WorkloadSummary {
+pub(crate) fn map_hpa_to_summary(h: HorizontalPodAutoscaler) -> WorkloadSummary {
     let meta = h.metadata;
     let spec = h.spec.unwrap_or_default();
     let status = h.status.unwrap_or_default();
@@ -378,7 +392,7 @@ fn map_hpa_to_summary(h: HorizontalPodAutoscaler) -> WorkloadSummary {
 }
 
 // PDB
-fn map_pdb_to_summary(p: PodDisruptionBudget) -> WorkloadSummary {
+pub(crate) fn map_pdb_to_summary(p: PodDisruptionBudget) -> WorkloadSummary {
     let meta = p.metadata;
     let status = p.status.unwrap_or_default();
     let allowed = status.disruptions_allowed;
@@ -396,7 +410,7 @@ fn map_pdb_to_summary(p: PodDisruptionBudget) -> WorkloadSummary {
 }
 
 // Services
-fn map_service_to_summary(s: Service) -> WorkloadSummary {
+pub(crate) fn map_service_to_summary(s: Service) -> WorkloadSummary {
     let meta = s.metadata;
     let spec = s.spec.unwrap_or_default();
 
@@ -423,7 +437,7 @@ fn map_service_to_summary(s: Service) -> WorkloadSummary {
 }
 
 // Endpoints
-fn map_endpoints_to_summary(e: Endpoints) -> WorkloadSummary {
+pub(crate) fn map_endpoints_to_summary(e: Endpoints) -> WorkloadSummary {
     let meta = e.metadata;
     let count = e
         .subsets
@@ -447,7 +461,7 @@ fn map_endpoints_to_summary(e: Endpoints) -> WorkloadSummary {
 }
 
 // Ingresses
-fn map_ingress_to_summary(i: Ingress) -> WorkloadSummary {
+pub(crate) fn map_ingress_to_summary(i: Ingress) -> WorkloadSummary {
     let meta = i.metadata;
     let lbs = i
         .status
@@ -474,7 +488,7 @@ fn map_ingress_to_summary(i: Ingress) -> WorkloadSummary {
 }
 
 // Network Policies
-fn map_network_policy_to_summary(n: NetworkPolicy) -> WorkloadSummary {
+pub(crate) fn map_network_policy_to_summary(n: NetworkPolicy) -> WorkloadSummary {
     let meta = n.metadata;
     WorkloadSummary {
         id: meta.uid.clone().unwrap_or_default(),
@@ -489,7 +503,7 @@ fn map_network_policy_to_summary(n: NetworkPolicy) -> WorkloadSummary {
 }
 
 // PVC
-fn map_pvc_to_summary(p: PersistentVolumeClaim) -> WorkloadSummary {
+pub(crate) fn map_pvc_to_summary(p: PersistentVolumeClaim) -> WorkloadSummary {
     let meta = p.metadata;
     let status = p.status.unwrap_or_default();
     let phase = status.phase.unwrap_or_default();
@@ -511,7 +525,7 @@ fn map_pvc_to_summary(p: PersistentVolumeClaim) -> WorkloadSummary {
 }
 
 // PV (Cluster Scoped)
-fn map_pv_to_summary(p: PersistentVolume) -> WorkloadSummary {
+pub(crate) fn map_pv_to_summary(p: PersistentVolume) -> WorkloadSummary {
     let meta = p.metadata;
     let status = p.status.unwrap_or_default();
     let phase = status.phase.unwrap_or_default();
@@ -534,7 +548,7 @@ fn map_pv_to_summary(p: PersistentVolume) -> WorkloadSummary {
 }
 
 // Storage Classes (Cluster Scoped)
-fn map_storage_class_to_summary(s: StorageClass) -> WorkloadSummary {
+pub(crate) fn map_storage_class_to_summary(s: StorageClass) -> WorkloadSummary {
     let meta = s.metadata;
     let provisioner = s.provisioner;
 
@@ -551,7 +565,7 @@ fn map_storage_class_to_summary(s: StorageClass) -> WorkloadSummary {
 }
 
 // Service Accounts
-fn map_service_account_to_summary(s: ServiceAccount) -> WorkloadSummary {
+pub(crate) fn map_service_account_to_summary(s: ServiceAccount) -> WorkloadSummary {
     let meta = s.metadata;
 
     WorkloadSummary {
@@ -567,7 +581,7 @@ fn map_service_account_to_summary(s: ServiceAccount) -> WorkloadSummary {
 }
 
 // Roles
-fn map_role_to_summary(r: Role) -> WorkloadSummary {
+pub(crate) fn map_role_to_summary(r: Role) -> WorkloadSummary {
     let meta = r.metadata;
     WorkloadSummary {
         id: meta.uid.clone().unwrap_or_default(),
@@ -582,7 +596,7 @@ fn map_role_to_summary(r: Role) -> WorkloadSummary {
 }
 
 // Cluster Roles (Cluster Scoped)
-fn map_cluster_role_to_summary(r: ClusterRole) -> WorkloadSummary {
+pub(crate) fn map_cluster_role_to_summary(r: ClusterRole) -> WorkloadSummary {
     let meta = r.metadata;
     WorkloadSummary {
         id: meta.uid.clone().unwrap_or_default(),
@@ -596,7 +610,7 @@ fn map_cluster_role_to_summary(r: ClusterRole) -> WorkloadSummary {
     }
 }
 
-fn map_role_binding_to_summary(r: RoleBinding) -> WorkloadSummary {
+pub(crate) fn map_role_binding_to_summary(r: RoleBinding) -> WorkloadSummary {
     let meta = r.metadata;
     let role = format!("{}:{}", r.role_ref.kind, r.role_ref.name);
     let subjects = r
@@ -624,7 +638,7 @@ fn map_role_binding_to_summary(r: RoleBinding) -> WorkloadSummary {
     }
 }
 
-fn map_cluster_role_binding_to_summary(r: ClusterRoleBinding) -> WorkloadSummary {
+pub(crate) fn map_cluster_role_binding_to_summary(r: ClusterRoleBinding) -> WorkloadSummary {
     let meta = r.metadata;
     let role = format!("{}:{}", r.role_ref.kind, r.role_ref.name);
     let subjects = r
@@ -652,7 +666,7 @@ fn map_cluster_role_binding_to_summary(r: ClusterRoleBinding) -> WorkloadSummary
     }
 }
 
-fn map_crd_to_summary(c: CustomResourceDefinition) -> WorkloadSummary {
+pub(crate) fn map_crd_to_summary(c: CustomResourceDefinition) -> WorkloadSummary {
     let meta = c.metadata;
     let spec = c.spec;
     let scope = spec.scope;
@@ -1,11 +1,15 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod alert_rules;
 mod cluster_manager;
 mod config;
+mod credentials;
 mod image_utils;
 mod import;
 mod input_validation;
 mod k8s;
 
+use tauri::Manager;
+
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
@@ -18,7 +22,7 @@ pub fn run() {
 
     // Initialize cluster manager
     let db_path = config::get_app_config_dir().join("clusters.db");
-    let cluster_manager = match cluster_manager::ClusterManager::new(db_path) {
+    let cluster_manager = match cluster_manager::ClusterManager::new(db_path.clone()) {
         Ok(manager) => manager,
         Err(e) => {
             eprintln!("Failed to initialize cluster manager: {}", e);
@@ -29,6 +33,15 @@ pub fn run() {
         std::sync::Mutex::new(cluster_manager),
     ));
 
+    // Alert rules live next to the clusters table
+    let alert_rules = match alert_rules::AlertRuleStore::new(db_path) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("Failed to initialize alert rules: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     tauri::Builder::default()
         .plugin(tauri_plugin_websocket::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
@@ -37,7 +50,21 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
         .manage(cluster_manager_state)
-        .manage(k8s::WatcherState::default())
+        .manage(alert_rules::AlertRuleState(std::sync::Arc::new(
+            alert_rules,
+        )))
+        .manage(k8s::BackgroundTaskState::default())
+        .manage(k8s::AccessReviewCache::default())
+        .manage(k8s::ResourceCacheState::default())
+        .on_window_event(|window, event| {
+            // Stop streams and watches owned by a window once it is gone
+            if let tauri::WindowEvent::Destroyed = event {
+                let tasks = window.state::<k8s::BackgroundTaskState>();
+                if let Err(e) = tasks.stop_window(window.label()) {
+                    eprintln!("Failed to stop background tasks: {}", e);
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             // Legacy k8s commands (deprecated, kept for backwards compatibility)
@@ -56,14 +83,25 @@ pub fn run() {
             k8s::cluster_list_pods,
             k8s::cluster_delete_pod,
             k8s::cluster_get_pod_events,
+            k8s::cluster_diagnose_pod,
+            k8s::cluster_explain_pod_scheduling,
+            k8s::cluster_lint_rules,
+            k8s::cluster_lint,
+            k8s::cluster_find_broken_references,
             k8s::cluster_stream_container_logs,
             k8s::cluster_start_pod_watch,
+            k8s::cluster_stream_aggregated_logs,
+            k8s::cluster_stop_stream_logs,
+            k8s::cluster_query_container_logs,
+            k8s::cluster_export_container_logs,
             k8s::cluster_get_metrics,
             k8s::cluster_get_events,
             k8s::cluster_list_events,
             k8s::cluster_list_nodes,
             k8s::cluster_get_resource_yaml,
             k8s::cluster_apply_resource_yaml,
+            k8s::cluster_diff_resource,
+            k8s::cluster_copy_resource,
             k8s::cluster_scale_workload,
             k8s::cluster_restart_workload,
             // Workload commands
@@ -100,6 +138,9 @@ pub fn run() {
             k8s::cluster_delete_ingress,
             k8s::cluster_list_network_policies,
             k8s::cluster_delete_network_policy,
+            k8s::cluster_get_pod_network_policies,
+            k8s::cluster_simulate_pod_traffic,
+            k8s::cluster_get_resource_relationships,
             k8s::cluster_list_pvc,
             k8s::cluster_delete_pvc,
             k8s::cluster_list_pv,
@@ -116,8 +157,32 @@ pub fn run() {
             k8s::cluster_delete_cluster_role,
             k8s::cluster_list_cluster_role_bindings,
             k8s::cluster_delete_cluster_role_binding,
+            k8s::cluster_get_subject_permissions,
+            k8s::cluster_get_access_rules,
+            k8s::cluster_check_access,
+            k8s::cluster_clear_access_cache,
             k8s::cluster_list_crds,
             k8s::cluster_delete_crd,
+            // Live list watches
+            k8s::cluster_watch_resources,
+            k8s::cluster_stop_resource_watch,
+            k8s::cluster_watch_events,
+            k8s::cluster_stop_event_watch,
+            k8s::cluster_list_resources_paged,
+            k8s::cluster_cancel_list,
+            k8s::cluster_search_resources,
+            // Multi-cluster
+            k8s::multi_cluster_list_resources,
+            // Resource list cache
+            k8s::cluster_enable_cache,
+            k8s::cluster_disable_cache,
+            k8s::cluster_get_cache_status,
+            // Alert rules
+            alert_rules::db_list_alert_rules,
+            alert_rules::db_save_alert_rule,
+            alert_rules::db_delete_alert_rule,
+            k8s::alerts_start_monitoring,
+            k8s::alerts_stop_monitoring,
             k8s::cluster_check_helm_available,
             k8s::cluster_list_helm_releases,
             k8s::cluster_list_helm_charts,
@@ -141,6 +206,12 @@ pub fn run() {
             import::import_discover_file,
             import::import_discover_folder,
             import::import_add_cluster,
+            // Background tasks
+            k8s::list_background_tasks,
+            k8s::stop_background_task,
+            k8s::stop_all_background_tasks,
+            // Credential expiry
+            credentials::credentials_check_expiry,
             // Image processing
             image_utils::process_icon_file,
             // Legacy config
@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { clustersStore } from './clusters.svelte';
+import { settingsStore } from './settings.svelte';
 
 const STORAGE_KEY = 'kore-active-cluster';
 
@@ -47,6 +48,19 @@ class ActiveClusterStore {
   }
 
   async setCluster(clusterId: string | null) {
+    const previous = this.clusterId;
+    if (previous && previous !== clusterId) {
+      // Closing a cluster tears down its resource cache
+      invoke('cluster_disable_cache', { clusterId: previous }).catch((e) =>
+        console.error('Failed to stop resource cache', e)
+      );
+    }
+    if (clusterId && settingsStore.isClusterCacheEnabled(clusterId)) {
+      invoke('cluster_enable_cache', { clusterId }).catch((e) =>
+        console.error('Failed to start resource cache', e)
+      );
+    }
+
     this.clusterId = clusterId;
     this.activeNamespace = 'all';
     this.save();
@@ -9,6 +9,7 @@ export interface Cluster {
   tags: string; // JSON-encoded array
   created_at: number;
   last_accessed: number;
+  credentials_expires_at?: number | null; // Unix timestamp (seconds)
 }
 
 class ClustersStore {
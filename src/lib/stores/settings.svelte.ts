@@ -5,6 +5,10 @@ export interface Settings {
   theme: Theme;
   codeTheme: CodeTheme;
   refreshInterval: number;
+  credentialExpiryWarningDays: number;
+  cachedClusters: string[];
+  alertClusters: string[];
+  disabledLintRules: string[];
 }
 
 class SettingsStore {
@@ -12,6 +16,10 @@ class SettingsStore {
     theme: 'kore',
     codeTheme: 'same-as-app',
     refreshInterval: 5000,
+    credentialExpiryWarningDays: 7,
+    cachedClusters: [],
+    alertClusters: [],
+    disabledLintRules: [],
   });
 
   constructor() {
@@ -43,6 +51,41 @@ class SettingsStore {
     this.save();
   }
 
+  setCredentialExpiryWarningDays(days: number) {
+    this.value.credentialExpiryWarningDays = days;
+    this.save();
+  }
+
+  isClusterCacheEnabled(clusterId: string): boolean {
+    return this.value.cachedClusters.includes(clusterId);
+  }
+
+  setClusterCacheEnabled(clusterId: string, enabled: boolean) {
+    const others = this.value.cachedClusters.filter((id) => id !== clusterId);
+    this.value.cachedClusters = enabled ? [...others, clusterId] : others;
+    this.save();
+  }
+
+  isAlertMonitoringEnabled(clusterId: string): boolean {
+    return this.value.alertClusters.includes(clusterId);
+  }
+
+  setAlertMonitoringEnabled(clusterId: string, enabled: boolean) {
+    const others = this.value.alertClusters.filter((id) => id !== clusterId);
+    this.value.alertClusters = enabled ? [...others, clusterId] : others;
+    this.save();
+  }
+
+  isLintRuleEnabled(rule: string): boolean {
+    return !this.value.disabledLintRules.includes(rule);
+  }
+
+  setLintRuleEnabled(rule: string, enabled: boolean) {
+    const others = this.value.disabledLintRules.filter((r) => r !== rule);
+    this.value.disabledLintRules = enabled ? others : [...others, rule];
+    this.save();
+  }
+
   get effectiveCodeTheme(): Theme {
     if (this.value.codeTheme === 'same-as-app') {
       return this.value.theme;